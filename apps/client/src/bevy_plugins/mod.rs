@@ -1 +1,2 @@
-pub mod plugin_client_app;
\ No newline at end of file
+pub mod plugin_client_app;
+pub mod plugin_haptics;
\ No newline at end of file
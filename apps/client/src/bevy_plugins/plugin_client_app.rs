@@ -1,6 +1,7 @@
+use std::fs;
 use std::sync::{Arc, Mutex};
-use bevy::log::info;
-use bevy::prelude::{App, Commands, Component, OnEnter, Plugin, PreStartup, Query, ResMut, Startup, States};
+use bevy::log::{info, warn};
+use bevy::prelude::{App, Camera2d, Commands, Component, OnEnter, Plugin, PreStartup, Query, ResMut, Startup, States};
 use bevy_tokio_tasks::TokioTasksRuntime;
 use nogamepads::entry_mutex;
 use nogamepads_core::data::controller::controller_data::ControllerData;
@@ -19,8 +20,8 @@ enum GameState {
 }
 
 #[derive(Component)]
-struct ClientComponent {
-    pad_client: Arc<Mutex<ControllerRuntime>>,
+pub(crate) struct ClientComponent {
+    pub(crate) pad_client: Arc<Mutex<ControllerRuntime>>,
 }
 
 pub struct ClientAppPlugins;
@@ -36,14 +37,11 @@ fn client_init(
     mut commands: Commands
 ) {
     let mut controller_data = ControllerData::default();
-    controller_data.bind_player(
-        Player::register(
-            env!("TEST_PLAYER_ACCOUNT").to_string(),
-            env!("TEST_PLAYER_PASSWORD").to_string())
-    );
+    controller_data.bind_player(test_player());
 
     let runtime = controller_data.runtime();
 
+    commands.spawn(Camera2d);
     commands.spawn(
         ClientComponent {
             pad_client: Arc::clone(&runtime),
@@ -51,6 +49,25 @@ fn client_init(
     );
 }
 
+/// File keeping the hash of the test player, registering it again on every run would create another account
+const TEST_PLAYER_HASH_FILE: &str = "./test_player.hash";
+
+fn test_player() -> Player {
+    let id = env!("TEST_PLAYER_ACCOUNT").to_string();
+    let password = env!("TEST_PLAYER_PASSWORD").to_string();
+
+    if let Ok(hash) = fs::read_to_string(TEST_PLAYER_HASH_FILE)
+        && let Some(player) = Player::login(id.clone(), password.clone(), hash.trim().to_string()) {
+        return player;
+    }
+
+    let player = Player::register(id, password).expect("Failed to register the test player");
+    if let Err(err) = fs::write(TEST_PLAYER_HASH_FILE, &player.account.player_hash) {
+        warn!("Failed to save the test player hash: {}", err);
+    }
+    player
+}
+
 fn client_start(
     client_components: Query<&mut ClientComponent>,
     runtime: ResMut<TokioTasksRuntime>
@@ -0,0 +1,119 @@
+use std::time::Duration;
+use bevy::color::Color;
+use bevy::input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::prelude::{App, BackgroundColor, Commands, Component, Entity, EventWriter, Node, Plugin, PositionType, Query, Res, ResMut, Resource, Startup, Time, Update, Val, With};
+use nogamepads::entry_mutex;
+use nogamepads_core::data::message::message_haptics::HapticPulse;
+use crate::bevy_plugins::plugin_client_app::ClientComponent;
+
+/// Maximum opacity of the overlay shown when the device has no vibration
+const FALLBACK_MAX_ALPHA: f32 = 0.35;
+
+/// Haptic playback
+/// The pattern being played, pulses are played in order
+#[derive(Resource, Default)]
+struct HapticPlayback {
+    pulses: Vec<HapticPulse>,
+    elapsed: Duration,
+    current: Option<usize>,
+}
+
+/// Overlay flashing with the intensity of the pulses, used as the visual fallback
+#[derive(Component)]
+struct HapticOverlay;
+
+pub struct HapticsPlugins;
+impl Plugin for HapticsPlugins {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HapticPlayback>();
+        app.add_systems(Startup, haptics_init);
+        app.add_systems(Update, (haptics_receive, haptics_play));
+    }
+}
+
+fn haptics_init(
+    mut commands: Commands
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..Default::default()
+        },
+        BackgroundColor(Color::NONE),
+        HapticOverlay
+    ));
+}
+
+/// Take the vibration requested by the game, a new vibration interrupts the playing one
+fn haptics_receive(
+    client_components: Query<&ClientComponent>,
+    mut playback: ResMut<HapticPlayback>
+) {
+    for client_component in client_components.iter() {
+        entry_mutex!(client_component.pad_client, |guard| {
+            if let Some(pattern) = guard.take_haptic() {
+                *playback = HapticPlayback { pulses: pattern.pulses(), ..Default::default() };
+            }
+        });
+    }
+}
+
+/// Play the current pulse on the gamepads, or on the overlay if there is no gamepad
+fn haptics_play(
+    time: Res<Time>,
+    mut playback: ResMut<HapticPlayback>,
+    gamepads: Query<Entity, With<Gamepad>>,
+    mut rumble_requests: EventWriter<GamepadRumbleRequest>,
+    mut overlays: Query<&mut BackgroundColor, With<HapticOverlay>>
+) {
+    if playback.pulses.is_empty() {
+        return;
+    }
+
+    playback.elapsed += time.delta();
+
+    // Find the pulse at the elapsed time
+    let mut start = Duration::ZERO;
+    let mut current = None;
+    for (index, pulse) in playback.pulses.iter().enumerate() {
+        let end = start + Duration::from_millis(pulse.duration_ms as u64);
+        if playback.elapsed < end {
+            current = Some((index, *pulse, end - playback.elapsed));
+            break;
+        }
+        start = end;
+    }
+
+    let intensity = match current {
+        None => {
+            *playback = HapticPlayback::default();
+            0.0
+        }
+        Some((index, pulse, remaining)) => {
+            // Each pulse is requested once when it starts
+            if playback.current != Some(index) {
+                playback.current = Some(index);
+                if pulse.intensity > 0.0 {
+                    for gamepad in gamepads.iter() {
+                        rumble_requests.write(GamepadRumbleRequest::Add {
+                            gamepad,
+                            duration: remaining,
+                            intensity: GamepadRumbleIntensity {
+                                strong_motor: pulse.intensity,
+                                weak_motor: pulse.intensity,
+                            },
+                        });
+                    }
+                }
+            }
+            pulse.intensity
+        }
+    };
+
+    let alpha = if gamepads.is_empty() { intensity * FALLBACK_MAX_ALPHA } else { 0.0 };
+    for mut overlay in overlays.iter_mut() {
+        overlay.0 = Color::srgba(1.0, 1.0, 1.0, alpha);
+    }
+}
\ No newline at end of file
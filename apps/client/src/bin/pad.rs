@@ -3,6 +3,7 @@ use bevy::DefaultPlugins;
 use bevy_tokio_tasks::TokioTasksPlugin;
 use tokio::runtime::Builder;
 use nogamepads_client::bevy_plugins::plugin_client_app::ClientAppPlugins;
+use nogamepads_client::bevy_plugins::plugin_haptics::HapticsPlugins;
 
 fn main() {
     let mut app = App::new();
@@ -19,5 +20,8 @@ fn main() {
     // Client App Plugins
     app.add_plugins(ClientAppPlugins);
 
+    // Haptics Plugins
+    app.add_plugins(HapticsPlugins);
+
     app.run();
 }
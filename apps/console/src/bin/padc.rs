@@ -1,4 +1,4 @@
-use clap::{Args, ColorChoice, CommandFactory, Parser, Subcommand};
+use clap::{Args, ColorChoice, CommandFactory, Parser, Subcommand, ValueEnum};
 use nogamepads::string_utils::process_id_text;
 use nogamepads_console::utils::{confirm, read_password, read_password_and_confirm};
 use serde::{Deserialize, Serialize};
@@ -11,17 +11,21 @@ use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::process::exit;
 use std::str::FromStr;
-use std::sync::{Arc};
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::Ordering::SeqCst;
 use clap_complete::{generate, Shell};
 use log::{info, LevelFilter};
 use nogamepads::entry_mutex;
 use nogamepads::logger_utils::logger_build;
-use nogamepads_core::data::controller::controller_cli::{process_controller_cli, ControllerCli};
+use nogamepads_core::data::controller::controller_cli::{build_message_printer, parse_attribute, process_controller_cli, ControllerCli};
 use nogamepads_core::data::controller::controller_data::ControllerData;
 use nogamepads_core::data::game::game_cli::{process_game_cli, GameCli};
-use nogamepads_core::data::game::game_data::{GameData, GameRuntimeDataArchive};
+use nogamepads_core::data::game::game_data::{GameData, GameRuntimeDataArchive, KeyData, KeyKind, NicknamePolicy, TeamPolicy};
+use nogamepads_core::data::game::game_hub::GameHub;
+use nogamepads_core::data::game::game_input::{DeadZoneShape, ResponseCurve};
 use nogamepads_core::data::player::player_data::Player;
+use nogamepads_core::data::player::player_identity::PlayerKey;
+use nogamepads_core::data::player::player_profile;
 use nogamepads_core::service::cli_addition::runtime_consoles::RuntimeConsole;
 use nogamepads_core::service::service_runner::{NoGamepadsService, ServiceRunner};
 use nogamepads_core::service::tcp_network::DEFAULT_PORT;
@@ -68,11 +72,17 @@ enum AccountCommands {
     #[command(about = "Add a account")]
     Add(AccountArgs),
 
+    #[command(about = "Generate a key account, identified by the fingerprint of its public key")]
+    Generate(GenerateAccountArgs),
+
     #[command(about = "Remove a account")]
     Remove(AccountArgs),
 
     #[command(about = "Edit the profile of account")]
-    Edit(EditAccountArgs)
+    Edit(EditAccountArgs),
+
+    #[command(about = "Check the password of account, upgrading its credentials if they are outdated")]
+    Unlock(AccountArgs)
 }
 
 #[derive(Args, Debug)]
@@ -85,6 +95,16 @@ struct AccountArgs{
     password: Option<String>
 }
 
+#[derive(Args, Debug)]
+struct GenerateAccountArgs{
+
+    #[arg(short, long, help = "Nickname")]
+    nickname: Option<String>,
+
+    #[arg(long, value_name = "Secret", help = "Restore the key from its secret (hex) instead of generating one")]
+    secret: Option<String>
+}
+
 #[derive(Args, Debug)]
 struct EditAccountArgs{
 
@@ -95,7 +115,10 @@ struct EditAccountArgs{
     nickname: Option<String>,
 
     #[arg(short = 'c', long = "color", num_args = 3, value_names = ["H", "S", "V"], help = "Player color, h(0 - 360), s(0 - 1), v(0 - 1)")]
-    color: Option<Vec<f64>>
+    color: Option<Vec<f64>>,
+
+    #[arg(short, long = "attr", value_name = "KEY=VALUE", value_parser = parse_attribute, help = "Set a profile attribute, e.g. lang=en, an empty value removes it")]
+    attributes: Vec<(String, String)>
 }
 
 #[derive(Subcommand, Debug)]
@@ -110,8 +133,29 @@ enum GameCommands {
     #[command(about = "Edit the profile of game")]
     Edit(EditGameArgs),
 
+    #[command(about = "Set the maximum number of players")]
+    MaxPlayers(MaxPlayersArgs),
+
+    #[command(about = "Enable or disable motion sensors")]
+    Motion(MotionArgs),
+
+    #[command(about = "Allow or deny players joining as guests")]
+    Guests(GuestsArgs),
+
+    #[command(about = "Set what happens when players use the same nickname")]
+    Nicknames(NicknamePolicyArgs),
+
     #[command(subcommand, about = "Register keys to game")]
-    Register(RegisterKeysCommands)
+    Register(RegisterKeysCommands),
+
+    #[command(subcommand, about = "Manage teams of game")]
+    Team(TeamCommands),
+
+    #[command(subcommand, about = "Manage the allow list of game")]
+    AllowList(AllowListCommands),
+
+    #[command(subcommand, about = "Manage the admins of game")]
+    Admin(AdminCommands)
 }
 
 #[derive(Args, Debug)]
@@ -137,6 +181,165 @@ struct EditGameArgs {
     value: String,
 }
 
+#[derive(Args, Debug)]
+struct MaxPlayersArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String,
+
+    #[arg(value_name = "Max Players", help = "Leave empty for unlimited players")]
+    max_players: Option<u8>,
+}
+
+#[derive(Args, Debug)]
+struct GuestsArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String,
+
+    #[arg(value_name = "Allow", action = clap::ArgAction::Set, help = "true to let guests join, false to deny them")]
+    allow: bool,
+}
+
+#[derive(Args, Debug)]
+struct NicknamePolicyArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String,
+
+    #[arg(value_enum, value_name = "Policy", help = "any: shared nicknames, unique: reject the player, suffix: append a number")]
+    policy: NicknamePolicyArg
+}
+
+#[derive(Args, Debug)]
+struct MotionArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String,
+
+    #[arg(value_name = "Sample Rate", help = "Samples per second, leave empty to disable motion sensors")]
+    sample_rate: Option<u16>,
+}
+
+#[derive(Subcommand, Debug)]
+enum TeamCommands {
+
+    #[command(about = "Add or modify a team")]
+    Add(AddTeamArgs),
+
+    #[command(about = "Remove a team")]
+    Remove(RemoveTeamArgs),
+
+    #[command(about = "List all")]
+    List(ListTeamArgs),
+
+    #[command(about = "Set how players are assigned to teams")]
+    Policy(TeamPolicyArgs)
+}
+
+#[derive(Args, Debug)]
+struct AddTeamArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String,
+
+    #[arg(value_name = "Team")]
+    team: String,
+
+    #[arg(short = 'c', long = "color", num_args = 3, value_names = ["H", "S", "V"], help = "Team color, h(0 - 360), s(0 - 1), v(0 - 1)")]
+    color: Option<Vec<f64>>
+}
+
+#[derive(Args, Debug)]
+struct RemoveTeamArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String,
+
+    #[arg(value_name = "Team")]
+    team: String
+}
+
+#[derive(Args, Debug)]
+struct ListTeamArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String
+}
+
+#[derive(Args, Debug)]
+struct TeamPolicyArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String,
+
+    #[arg(value_enum, value_name = "Policy")]
+    policy: TeamPolicyArg
+}
+
+#[derive(Subcommand, Debug)]
+enum AllowListCommands {
+
+    #[command(about = "Put an account on the allow list")]
+    Add(AllowAccountArgs),
+
+    #[command(about = "Take an account off the allow list")]
+    Remove(AllowAccountArgs),
+
+    #[command(about = "List all")]
+    List(AllowListArgs),
+
+    #[command(about = "Only admit the accounts on the allow list")]
+    Enable(AllowListArgs),
+
+    #[command(about = "Admit every account again")]
+    Disable(AllowListArgs)
+}
+
+#[derive(Args, Debug)]
+struct AllowAccountArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String,
+
+    #[arg(value_name = "Account")]
+    account: String
+}
+
+#[derive(Args, Debug)]
+struct AllowListArgs {
+
+    #[arg(value_name = "Game Name")]
+    name: String
+}
+
+#[derive(Subcommand, Debug)]
+enum AdminCommands {
+
+    #[command(about = "Let an account moderate the game from its controller")]
+    Add(AllowAccountArgs),
+
+    #[command(about = "Remove an admin")]
+    Remove(AllowAccountArgs),
+
+    #[command(about = "List all")]
+    List(AllowListArgs)
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+enum TeamPolicyArg {
+    Manual,
+    RoundRobin,
+    Balanced
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+enum NicknamePolicyArg {
+    Any,
+    Unique,
+    Suffix
+}
+
 #[derive(Subcommand, Debug)]
 enum RegisterKeysCommands {
 
@@ -147,7 +350,10 @@ enum RegisterKeysCommands {
     Axis(KeyManageCommands),
 
     #[command(subcommand, about = "Register a direction")]
-    Direction(KeyManageCommands)
+    Direction(KeyManageCommands),
+
+    #[command(subcommand, about = "Register a touchpad")]
+    Touchpad(KeyManageCommands)
 }
 
 #[derive(Subcommand, Debug)]
@@ -173,7 +379,37 @@ struct AddKeyArgs{
     key: u8,
 
     #[arg(value_name = "Name")]
-    key_name: String
+    key_name: String,
+
+    #[arg(long, value_name = "Min", allow_negative_numbers = true)]
+    min: Option<f64>,
+
+    #[arg(long, value_name = "Max", allow_negative_numbers = true)]
+    max: Option<f64>,
+
+    #[arg(long, value_name = "Default", allow_negative_numbers = true)]
+    default: Option<f64>,
+
+    #[arg(long, value_name = "Description")]
+    description: Option<String>,
+
+    #[arg(long, help = "Clamp values into the range instead of rejecting them")]
+    clamp: bool,
+
+    #[arg(long, help = "Scale direction vectors longer than 1 back to the unit circle")]
+    normalize: bool,
+
+    #[arg(long, value_name = "Dead Zone")]
+    dead_zone: Option<f64>,
+
+    #[arg(long, help = "Measure the dead zone on each component")]
+    axial: bool,
+
+    #[arg(long, value_name = "Smoothing")]
+    smoothing: Option<f64>,
+
+    #[arg(long, value_name = "Curve Exponent")]
+    curve: Option<f64>
 }
 
 #[derive(Args, Debug)]
@@ -199,12 +435,24 @@ struct ConnectArgs {
     #[arg(short, long, value_name = "Account")]
     account: Option<String>,
 
+    #[arg(short, long, help = "Join as a guest without an account", conflicts_with = "account")]
+    guest: bool,
+
+    #[arg(short, long, help = "Nickname of the guest", requires = "guest")]
+    nickname: Option<String>,
+
+    #[arg(short = 'c', long = "color", num_args = 3, value_names = ["H", "S", "V"], requires = "guest", help = "Color of the guest, h(0 - 360), s(0 - 1), v(0 - 1)")]
+    color: Option<Vec<f64>>,
+
     #[arg(short, long, value_name = "Address")]
     tcp_addr: Option<String>,
 
     #[arg(short, long, value_name = "Methods")]
     method: Option<String>,
 
+    #[arg(short, long, value_name = "Room")]
+    room: Option<String>,
+
     #[arg(long)]
     cmd: bool,
 
@@ -215,8 +463,8 @@ struct ConnectArgs {
 #[derive(Args, Debug, Clone)]
 struct ListenArgs {
 
-    #[arg(value_name = "Game")]
-    game: String,
+    #[arg(value_name = "Game", num_args = 1.., required = true, help = "Games to host, each game is hosted in a room named after it")]
+    games: Vec<String>,
 
     #[arg(short = 'a', long, value_name = "Address")]
     tcp_addr: Option<String>,
@@ -250,7 +498,10 @@ struct LocalGameData {
 
 #[derive(Default, Serialize, Deserialize, PartialEq, Debug)]
 struct LocalControllerData {
-    players: HashMap<String, Player>
+    players: HashMap<String, Player>,
+
+    #[serde(default)]
+    keys: HashMap<String, PlayerKey>
 }
 
 fn main () {
@@ -269,6 +520,10 @@ fn main () {
                 AccountCommands::Add(args) => {
                     add_player(&mut data, args.account, args.password);
                 }
+
+                AccountCommands::Generate(args) => {
+                    generate_player(&mut data, args);
+                }
                 
                 AccountCommands::Remove(args) => {
                     remove_player(&mut data, args.account, args.password);
@@ -277,6 +532,10 @@ fn main () {
                 AccountCommands::Edit(args) => {
                     edit_player(&mut data, args);
                 }
+
+                AccountCommands::Unlock(args) => {
+                    unlock_player(&mut data, args.account, args.password);
+                }
             }
         }
 
@@ -329,21 +588,100 @@ fn main () {
                     }
                 }
 
+                GameCommands::MaxPlayers(args) => {
+                    let name = process_id_text(args.name);
+                    let game = data.game_data.games.get_mut(&name);
+                    if game.is_none() {
+                        eprintln!("Edit the game \"{}\" failed: game not found.", name.clone());
+                    } else {
+                        let game = game.unwrap();
+                        game.max_players = args.max_players;
+                        match args.max_players {
+                            None => { println!("Set the maximum number of players to unlimited."); }
+                            Some(max) => { println!("Set the maximum number of players to {}.", max); }
+                        }
+                    }
+                }
+
+                GameCommands::Motion(args) => {
+                    let name = process_id_text(args.name);
+                    let game = data.game_data.games.get_mut(&name);
+                    if game.is_none() {
+                        eprintln!("Edit the game \"{}\" failed: game not found.", name.clone());
+                    } else {
+                        let game = game.unwrap();
+                        match args.sample_rate {
+                            None => {
+                                game.control.motion = None;
+                                println!("Motion sensors disabled.");
+                            }
+                            Some(sample_rate) => {
+                                game.motion(sample_rate);
+                                println!("Motion sensors enabled at {} Hz.", sample_rate.max(1));
+                            }
+                        }
+                    }
+                }
+
+                GameCommands::Guests(args) => {
+                    let name = process_id_text(args.name);
+                    let game = data.game_data.games.get_mut(&name);
+                    if game.is_none() {
+                        eprintln!("Edit the game \"{}\" failed: game not found.", name.clone());
+                    } else {
+                        game.unwrap().allow_guests(args.allow);
+                        if args.allow {
+                            println!("Guests can join the game.");
+                        } else {
+                            println!("Guests are denied.");
+                        }
+                    }
+                }
+
+                GameCommands::Nicknames(args) => {
+                    let name = process_id_text(args.name);
+                    let game = data.game_data.games.get_mut(&name);
+                    if game.is_none() {
+                        eprintln!("Edit the game \"{}\" failed: game not found.", name.clone());
+                    } else {
+                        let policy = match args.policy {
+                            NicknamePolicyArg::Any => NicknamePolicy::Any,
+                            NicknamePolicyArg::Unique => NicknamePolicy::Unique,
+                            NicknamePolicyArg::Suffix => NicknamePolicy::Suffix,
+                        };
+                        game.unwrap().nickname_policy(policy);
+                        println!("Set the nickname policy to {:?}", policy);
+                    }
+                }
+
+                GameCommands::Team(cmds) => {
+                    manage_teams(&mut data, cmds);
+                }
+
+                GameCommands::AllowList(cmds) => {
+                    manage_allow_list(&mut data, cmds);
+                }
+
+                GameCommands::Admin(cmds) => {
+                    manage_admins(&mut data, cmds);
+                }
+
                 GameCommands::Register(cmds) => {
                     match cmds {
                         RegisterKeysCommands::Button(cmds) => {
-                            manage_keys(&mut data, cmds,
-                                        |game| &mut game.control.button_keys);
+                            manage_keys(&mut data, cmds, KeyKind::Button);
                         }
 
                         RegisterKeysCommands::Axis(cmds) => {
-                            manage_keys(&mut data, cmds,
-                                        |game| &mut game.control.axis_keys);
+                            manage_keys(&mut data, cmds, KeyKind::Axis);
                         }
 
                         RegisterKeysCommands::Direction(cmds) => {
-                            manage_keys(&mut data, cmds,
-                                        |game| &mut game.control.direction_keys);
+                            manage_keys(&mut data, cmds, KeyKind::Direction);
+                        }
+
+                        RegisterKeysCommands::Touchpad(cmds) => {
+                            manage_keys(&mut data, cmds, KeyKind::Touchpad);
                         }
                     }
                 }
@@ -361,11 +699,11 @@ fn main () {
         }
 
         Commands::Listen(args) => {
-            let archive = listen(&mut data, args.clone());
-            if let Some(archive) = archive {
-                if let Some(game) = data.game_data.games.get_mut(&process_id_text(args.game)) {
+            let archives = listen(&mut data, args);
+            for (id, archive) in archives {
+                if let Some(game) = data.game_data.games.get_mut(&id) {
                     game.archive = archive;
-                    info!("Game data archived.");
+                    info!("Game data \"{}\" archived.", id);
                 }
             }
         }
@@ -376,40 +714,19 @@ fn main () {
 
 fn connect(data: &mut LocalData, args: ConnectArgs) {
 
-    let mut result: Option<&Player> = None;
-    if args.account.is_some() {
-        // Account specified
-        let id = &args.account.unwrap();
-        let player = data.controller_data.players.get(id);
-
-        // Account not found
-        if player.is_none() {
-            eprintln!("Account not found: \"{}\"", id);
-            exit(1);
-        } else {
-            // Account exists
-            result = Some(player.unwrap());
+    let mut controller = ControllerData::default();
+    if args.guest {
+        let mut guest = Player::default();
+        guest.nickname(&args.nickname.unwrap_or("Guest".to_string()));
+        if let Some(color) = args.color {
+            guest.hsv(color[0].round() as i32, color[1], color[2]);
         }
+        controller.bind_guest(guest.customize.unwrap_or_default());
+        println!("Joining as a guest.");
     } else {
-        // Account not specified
-        for found in data.controller_data.players.values() {
-            // Found a replaceable account
-            result = Some(found);
-            println!("Account not specified! Using account \"{}\" instead!", found.account.id);
-            break;
-        }
-        // No replaceable account found
-        if result.is_none() {
-            eprintln!("Cannot find any replaceable account! Please ensure at least one account exists locally!");
-            exit(1);
-        }
+        bind_account(data, args.account, &mut controller);
     }
 
-    let player = result.unwrap().clone();
-
-    let mut controller = ControllerData::default();
-    controller.bind_player(player);
-
     let runtime = controller.runtime();
 
     let method = args.method.unwrap_or("tcp".to_string());
@@ -423,6 +740,9 @@ fn connect(data: &mut LocalData, args: ConnectArgs) {
             client.bind_addr(SocketAddr::from_str(&addr).unwrap_or(
                 SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT)),
             ));
+            if let Some(room) = args.room {
+                client.bind_room(room);
+            }
 
             entry = Some(client.build_entry());
         },
@@ -450,6 +770,8 @@ fn connect(data: &mut LocalData, args: ConnectArgs) {
         if args.cmd {
             println!("Enable command line.");
 
+            services.push(build_message_printer(Arc::clone(&runtime)));
+
             services.push(RuntimeConsole::build(
                 ControllerCli::command(), "ControllerCli".to_string(), Arc::clone(&runtime),
 
@@ -487,22 +809,72 @@ fn connect(data: &mut LocalData, args: ConnectArgs) {
     }
 }
 
-fn listen(data: &mut LocalData, args: ListenArgs) -> Option<GameRuntimeDataArchive> {
-    let id = process_id_text(args.game);
-    let game = data.game_data.games.get(&id);
-    if game.is_none() {
-        eprintln!("Game not found: \"{}\"", id);
-        exit(1);
+fn bind_account(data: &LocalData, account: Option<String>, controller: &mut ControllerData) {
+    let mut result: Option<&Player> = None;
+    if let Some(id) = &account {
+        // Account specified
+        let player = data.controller_data.players.get(id);
+
+        // Account not found
+        if player.is_none() {
+            eprintln!("Account not found: \"{}\"", id);
+            exit(1);
+        } else {
+            // Account exists
+            result = Some(player.unwrap());
+        }
+    } else {
+        // Account not specified
+        for found in data.controller_data.players.values() {
+            // Found a replaceable account
+            result = Some(found);
+            println!("Account not specified! Using account \"{}\" instead!", found.account.id);
+            break;
+        }
+        // No replaceable account found
+        if result.is_none() {
+            eprintln!("Cannot find any replaceable account! Please ensure at least one account exists locally, or join with --guest!");
+            exit(1);
+        }
+    }
+
+    let player = result.unwrap().clone();
+    if player.needs_migration() {
+        println!("Account \"{}\" uses outdated credentials, run \"padc account unlock {}\" to upgrade them.", player.account.id, player.account.id);
+    }
+
+    if player.is_key_account() {
+        let Some(key) = data.controller_data.keys.get(&player.account.id) else {
+            eprintln!("The key of account \"{}\" is missing!", player.account.id);
+            exit(1);
+        };
+        controller.bind_key(key.clone());
     }
+    controller.bind_player(player);
+}
 
-    let game_data = game.unwrap().clone();
+fn listen(data: &mut LocalData, args: ListenArgs) -> Vec<(String, GameRuntimeDataArchive)> {
+    let mut runtimes = Vec::new();
+    let mut hub = GameHub::new();
+    for game in args.games {
+        let id = process_id_text(game);
+        let game = data.game_data.games.get(&id);
+        if game.is_none() {
+            eprintln!("Game not found: \"{}\"", id);
+            exit(1);
+        }
 
-    let runtime = game_data.runtime();
+        let game_data = game.unwrap().clone();
+
+        let runtime = game_data.runtime();
+        hub.add_room(id.clone(), Arc::clone(&runtime));
+        runtimes.push((id, runtime));
+    }
 
     let mut services = Vec::new();
 
     if args.tcp {
-        let mut server = PadServerNetwork::build(Arc::clone(&runtime));
+        let mut server = PadServerNetwork::build_hub(hub.clone());
         if args.tcp_addr.is_some() {
             let addr = SocketAddr::from_str(&args.tcp_addr.unwrap())
                 .unwrap_or(SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT)));
@@ -522,33 +894,58 @@ fn listen(data: &mut LocalData, args: ListenArgs) -> Option<GameRuntimeDataArchi
     }
 
     if args.cmd {
+        // The command line controls the first game unless a command selects another room with --room
+        let (id, _) = &runtimes[0];
+        if runtimes.len() > 1 {
+            println!("Command line controls the game \"{}\", use --room to control the others.", id);
+        }
+
         services.push(RuntimeConsole::build(
-            GameCli::command(), "GameCli".to_string(), Arc::clone(&runtime),
+            GameCli::command(), "GameCli".to_string(), Arc::new(Mutex::new(hub.clone())),
 
             // Process command line
-            |runtime, cmd| {
-                process_game_cli(runtime, cmd)
+            |hub, cmd: GameCli| {
+                let mut runtime = None;
+                entry_mutex!(hub, |guard| {
+                    runtime = match &cmd.room {
+                        Some(room) => { guard.room(&process_id_text(room.clone())) }
+                        None => { guard.default_room() }
+                    };
+                });
+                if runtime.is_none() && let Some(room) = &cmd.room {
+                    eprintln!("Room not found: \"{}\"", room);
+                    return true;
+                }
+                let result = runtime.is_some_and(|runtime| process_game_cli(runtime, cmd));
+                if !result {
+                    entry_mutex!(hub, |guard| {
+                        guard.close_all();
+                    });
+                }
+                result
             },
 
             // Check close
-            |runtime| {
+            |hub| {
                 let mut close = false;
-                entry_mutex!(runtime, |guard| {
-                    close = guard.data.close.load(SeqCst);
+                entry_mutex!(hub, |guard| {
+                    close = guard.is_all_closed();
                 });
                 close
             },
 
             // Close
-            |runtime| {
-                entry_mutex!(runtime, |guard| {
-                    guard.close_game();
+            |hub| {
+                entry_mutex!(hub, |guard| {
+                    guard.close_all();
                 });
             },
 
         ).build_entry());
     }
 
+    drop(hub);
+
     if args.debug {
         logger_build(LevelFilter::Trace);
     } else {
@@ -557,11 +954,13 @@ fn listen(data: &mut LocalData, args: ListenArgs) -> Option<GameRuntimeDataArchi
 
     ServiceRunner::run(services);
 
-    let mut archived_data: Option<GameRuntimeDataArchive> = None;
-    if let Ok(mutex) = Arc::try_unwrap(runtime) {
-        let rt = mutex.into_inner()
-            .unwrap_or_else(|poison_error| poison_error.into_inner());
-        archived_data = Some(GameRuntimeDataArchive::from(rt.data));
+    let mut archived_data = Vec::new();
+    for (id, runtime) in runtimes {
+        if let Ok(mutex) = Arc::try_unwrap(runtime) {
+            let rt = mutex.into_inner()
+                .unwrap_or_else(|poison_error| poison_error.into_inner());
+            archived_data.push((id, GameRuntimeDataArchive::from(rt.data)));
+        }
     }
     archived_data
 }
@@ -583,14 +982,59 @@ fn add_player(data: &mut LocalData, account_args: String, password_args: Option<
         }
 
         // Create player
-        let player = Player::register(account_args, password);
+        let player = match Player::register(account_args, password) {
+            Ok(player) => { player }
+            Err(err) => {
+                eprintln!("Failed to create account: {:?}", err);
+                exit(1);
+            }
+        };
         let player_key = player.clone().account.id;
 
         data.controller_data.players.insert(player_key, player.clone());
     }
 }
 
+fn generate_player(data: &mut LocalData, args: GenerateAccountArgs) {
+    let key = match args.secret {
+        None => { PlayerKey::generate() }
+        Some(secret) => {
+            let Some(key) = PlayerKey::from_secret(&secret) else {
+                eprintln!("The secret must be 32 bytes in hex.");
+                exit(1);
+            };
+            key
+        }
+    };
+
+    let mut player = Player::register_with_key(&key);
+    let player_key = player.account.id.clone();
+    if data.controller_data.players.contains_key(&player_key) {
+        eprintln!("This account already exists. Please do not create it again.");
+        exit(1);
+    }
+    if let Some(nickname) = args.nickname {
+        player.nickname(&nickname);
+    }
+
+    data.controller_data.players.insert(player_key.clone(), player);
+    data.controller_data.keys.insert(player_key.clone(), key);
+    println!("Key account \"{}\" generated, its secret is kept in the local config.", player_key);
+}
+
 fn remove_player(data: &mut LocalData, account_args: String, password_args: Option<String>) {
+    // Key accounts have no password
+    let player_id = process_id_text(account_args.clone());
+    if data.controller_data.keys.contains_key(&player_id) {
+        if ! confirm(&format!("Remove the key account \"{}\"? Its key can't be recovered ", player_id)) {
+            exit(1);
+        }
+        data.controller_data.players.remove(&player_id);
+        data.controller_data.keys.remove(&player_id);
+        println!("The account \"{}\" has been removed!", player_id);
+        return;
+    }
+
     // Read password
     let mut password = "".to_string();
     if password_args.is_none() {
@@ -600,8 +1044,6 @@ fn remove_player(data: &mut LocalData, account_args: String, password_args: Opti
     }
 
     // Remove
-    let player_id = process_id_text(account_args.clone());
-
     let player = data.controller_data.players.get(&player_id);
     if player.is_none() {
         eprintln!("Failed to remove account \"{}\": Account not found.", account_args.clone());
@@ -617,6 +1059,41 @@ fn remove_player(data: &mut LocalData, account_args: String, password_args: Opti
     }
 }
 
+fn unlock_player(data: &mut LocalData, account_args: String, password_args: Option<String>) {
+    let player_id = process_id_text(account_args.clone());
+    let Some(player) = data.controller_data.players.get_mut(&player_id) else {
+        eprintln!("Failed to unlock account \"{}\": Account not found.", account_args);
+        exit(1);
+    };
+    if player.is_key_account() {
+        println!("The account \"{}\" is identified by its key and has no password.", player_id);
+        return;
+    }
+
+    // Read password
+    let password = match password_args {
+        None => { read_password("Enter password: ").unwrap_or("".to_string()) }
+        Some(password) => { password }
+    };
+
+    if !player.check(password.clone()) {
+        eprintln!("Failed to unlock account \"{}\": Password is incorrect!", player_id);
+        exit(1);
+    }
+
+    match player.migrate(password) {
+        Ok(true) => {
+            println!("The account \"{}\" has been unlocked, its credentials were upgraded to {:?}.", player_id, player.credential_version());
+            println!("Games keep knowing the account by its previous credentials, its bans, seats and stats are carried over.");
+        }
+        Ok(false) => { println!("The account \"{}\" has been unlocked.", player_id); }
+        Err(err) => {
+            eprintln!("Failed to upgrade the credentials of account \"{}\": {:?}", player_id, err);
+            exit(1);
+        }
+    }
+}
+
 fn edit_player(data: &mut LocalData, args: EditAccountArgs) {
     let account_id = process_id_text(args.account);
     if ! data.controller_data.players.contains_key(&account_id) {
@@ -640,6 +1117,21 @@ fn edit_player(data: &mut LocalData, args: EditAccountArgs) {
         player.hsv(hue as i32, sat, val);
         println!("Change the color of account \"{}\" to \"{}\"", account_id, hsv_to_hex(hue as i32, sat, val));
     }
+
+    for (key, value) in args.attributes {
+        player.attribute(&key, &value);
+        if value.is_empty() {
+            println!("Remove the attribute \"{}\" of account \"{}\"", key, account_id);
+        } else {
+            println!("Set the attribute \"{}\" of account \"{}\" to \"{}\"", key, account_id, value);
+        }
+    }
+
+    // Games reject invalid profiles when joining, so warn about them early
+    if let Some(mut customize) = player.customize.clone()
+        && let Err(why) = player_profile::validate(&mut customize) {
+        println!("Warning: games will not accept this profile: {:?}", why);
+    }
 }
 
 macro_rules! check_game {
@@ -656,29 +1148,213 @@ macro_rules! check_game {
     };
 }
 
-fn manage_keys(data: &mut LocalData, cmds: KeyManageCommands, get_map: fn(game: &mut GameData) -> &mut HashMap<u8, String>) {
+fn manage_keys(data: &mut LocalData, cmds: KeyManageCommands, kind: KeyKind) {
     match cmds {
         KeyManageCommands::Add(args) => {
             check_game!(data, args, |game| {
-                get_map(game).entry(args.key)
-                    .or_insert_with(|| args.key_name.clone());
+                if let Some(exists) = game.control.key(&args.key) && exists.kind != kind {
+                    println!("Key {} was registered as {:?}, replaced.", args.key, exists.kind);
+                }
+
+                let mut key = match game.control.key(&args.key) {
+                    Some(exists) if exists.kind == kind => { exists.clone() }
+                    _ => { KeyData::new(kind, args.key_name.clone()) }
+                };
+                key.name = args.key_name.clone();
+                let ranged = matches!(kind, KeyKind::Axis | KeyKind::Direction);
+                if ranged && (args.min.is_some() || args.max.is_some()) {
+                    key.range(args.min.unwrap_or(key.min), args.max.unwrap_or(key.max));
+                }
+                if let Some(default) = args.default {
+                    key.default_value(default);
+                }
+                if let Some(description) = args.description {
+                    key.description(description);
+                }
+                if ranged {
+                    let processing = &mut key.processing;
+                    if args.clamp {
+                        processing.clamp(true);
+                    }
+                    if args.normalize {
+                        processing.normalize(true);
+                    }
+                    if let Some(dead_zone) = args.dead_zone {
+                        let shape = if args.axial { DeadZoneShape::Axial } else { DeadZoneShape::Radial };
+                        processing.dead_zone(dead_zone, shape);
+                    }
+                    if let Some(smoothing) = args.smoothing {
+                        processing.smoothing(smoothing);
+                    }
+                    if let Some(exponent) = args.curve {
+                        processing.curve(ResponseCurve::Power(exponent));
+                    }
+                }
+                game.key(args.key, key);
                 println!("Registered key \"{}\"", args.key_name);
             });
         }
 
         KeyManageCommands::Remove(args) => {
             check_game!(data, args, |game| {
-                let result = get_map(game).remove(&args.key);
-                if result.is_some() {
-                    println!("Removed key \"{}\"", result.unwrap());
+                if game.control.key(&args.key).is_some_and(|key| key.kind == kind) {
+                    let result = game.control.keys.remove(&args.key);
+                    if let Some(key) = result {
+                        println!("Removed key \"{}\"", key.name);
+                    }
                 }
             });
         }
 
         KeyManageCommands::List(args) => {
             check_game!(data, args, |game| {
-                for (key, key_name) in get_map(game).iter() {
-                    println!("{} - \"{}\"", key, key_name);
+                for key in game.control.keys_of(kind) {
+                    if let Some(data) = game.control.key(&key) {
+                        match kind {
+                            KeyKind::Button | KeyKind::Touchpad => { println!("{} - \"{}\"", key, data.name); }
+                            _ => { println!("{} - \"{}\" [{}, {}] (default {})", key, data.name, data.min, data.max, data.default); }
+                        }
+                        if let Some(description) = &data.description {
+                            println!("    {}", description);
+                        }
+                        if data.processing.is_enabled() {
+                            println!("    {:?}", data.processing);
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
+
+fn manage_teams(data: &mut LocalData, cmds: TeamCommands) {
+    match cmds {
+        TeamCommands::Add(args) => {
+            check_game!(data, args, |game| {
+                let color = args.color.unwrap_or(vec![0.0, 0.0, 1.0]);
+                let hue = color[0].round().clamp(0.0, 360.0) as i32;
+                game.team(args.team.clone(), hue, color[1], color[2]);
+                println!("Added team \"{}\" ({})", args.team, hsv_to_hex(hue, color[1], color[2]));
+            });
+        }
+
+        TeamCommands::Remove(args) => {
+            check_game!(data, args, |game| {
+                let count = game.teams.len();
+                game.teams.retain(|team| team.name != args.team);
+                if game.teams.len() < count {
+                    println!("Removed team \"{}\"", args.team);
+                }
+            });
+        }
+
+        TeamCommands::List(args) => {
+            check_game!(data, args, |game| {
+                println!("Policy: {:?}", game.team_policy);
+                for team in game.teams.iter() {
+                    println!("{} - {}", team.name, hsv_to_hex(team.color_hue, team.color_saturation, team.color_value));
+                }
+            });
+        }
+
+        TeamCommands::Policy(args) => {
+            check_game!(data, args, |game| {
+                let policy = match args.policy {
+                    TeamPolicyArg::Manual => TeamPolicy::Manual,
+                    TeamPolicyArg::RoundRobin => TeamPolicy::RoundRobin,
+                    TeamPolicyArg::Balanced => TeamPolicy::Balanced,
+                };
+                game.team_policy(policy);
+                println!("Set the team policy to {:?}", policy);
+            });
+        }
+    }
+}
+
+fn manage_allow_list(data: &mut LocalData, cmds: AllowListCommands) {
+    match cmds {
+        AllowListCommands::Add(args) => {
+            check_game!(data, args, |game| {
+                let account = process_id_text(args.account);
+                game.archive.allow(account.clone());
+                println!("Allowed account \"{}\"", account);
+            });
+        }
+
+        AllowListCommands::Remove(args) => {
+            check_game!(data, args, |game| {
+                let account = process_id_text(args.account);
+                let count = game.archive.allowed.len();
+                game.archive.disallow(account.clone());
+                if game.archive.allowed.len() < count {
+                    println!("Disallowed account \"{}\"", account);
+                }
+            });
+        }
+
+        AllowListCommands::List(args) => {
+            check_game!(data, args, |game| {
+                println!("Allow list {}", if game.archive.allow_list_enabled { "enabled" } else { "disabled" });
+                for account in game.archive.allowed.iter() {
+                    println!("{}", account);
+                }
+            });
+        }
+
+        AllowListCommands::Enable(args) => {
+            check_game!(data, args, |game| {
+                game.archive.allow_list(true);
+                println!("Allow list enabled, only the allowed accounts can join.");
+            });
+        }
+
+        AllowListCommands::Disable(args) => {
+            check_game!(data, args, |game| {
+                game.archive.allow_list(false);
+                println!("Allow list disabled.");
+            });
+        }
+    }
+}
+
+fn manage_admins(data: &mut LocalData, cmds: AdminCommands) {
+    match cmds {
+        AdminCommands::Add(args) => {
+            let id = process_id_text(args.account.clone());
+            let local = data.controller_data.players.get(&id).map(|player| player.account.clone());
+            check_game!(data, args, |game| {
+                // Accounts are identified by their hashes, so they must be local or have joined the game before
+                let known = game.archive.players.iter()
+                    .find(|known| known.account.id == id)
+                    .map(|known| known.account.clone());
+                match known.or(local) {
+                    None => {
+                        eprintln!("Account not found: \"{}\", it must be a local account or have joined the game before.", id);
+                        exit(1);
+                    }
+                    Some(account) => {
+                        game.admin(account);
+                        println!("Added admin \"{}\"", id);
+                    }
+                }
+            });
+        }
+
+        AdminCommands::Remove(args) => {
+            check_game!(data, args, |game| {
+                let id = process_id_text(args.account);
+                let count = game.admins.len();
+                game.admins.retain(|admin| admin.id != id);
+                if game.admins.len() < count {
+                    println!("Removed admin \"{}\"", id);
+                }
+            });
+        }
+
+        AdminCommands::List(args) => {
+            check_game!(data, args, |game| {
+                for admin in game.admins.iter() {
+                    println!("{}", admin.id);
                 }
             });
         }
@@ -15,6 +15,11 @@ typedef enum FfiConnectionMessageTag {
   ConnectionRequestSkinPackage,
   ConnectionReady,
   ConnectionError,
+  ConnectionEnterRoom,
+  ConnectionRequestRooms,
+  ConnectionRequestKeys,
+  ConnectionSignature,
+  ConnectionJoinAsGuest,
 } FfiConnectionMessageTag;
 
 typedef enum FfiConnectionResponseMessageTag {
@@ -24,6 +29,11 @@ typedef enum FfiConnectionResponseMessageTag {
   OkResponse,
   WelcomeResponse,
   ErrorResponse,
+  RoomsResponse,
+  KeysResponse,
+  ChallengeResponse,
+  GuestWelcomeResponse,
+  JoinWelcomeResponse,
 } FfiConnectionResponseMessageTag;
 
 typedef enum FfiControlMessageTag {
@@ -35,8 +45,54 @@ typedef enum FfiControlMessageTag {
   CtrlExit,
   CtrlError,
   CtrlEnd,
+  CtrlMotion,
+  CtrlTouch,
+  CtrlPromptReply,
+  CtrlAdmin,
+  CtrlUpdateProfile,
 } FfiControlMessageTag;
 
+typedef enum FfiTouchPhase {
+  TouchBegin,
+  TouchMove,
+  TouchEnd,
+} FfiTouchPhase;
+
+typedef enum FfiHapticPatternKind {
+  PresetPattern,
+  CustomPattern,
+} FfiHapticPatternKind;
+
+typedef enum FfiHapticPreset {
+  TapHaptic,
+  DoubleTapHaptic,
+  LongHaptic,
+  HeartbeatHaptic,
+  ErrorHaptic,
+} FfiHapticPreset;
+
+typedef enum FfiTextInputKind {
+  TextInput,
+  NumberInput,
+  PasswordInput,
+  MultilineInput,
+} FfiTextInputKind;
+
+typedef enum FfiAdminResult {
+  AdminDone,
+  AdminPermissionDenied,
+  AdminPlayerNotFound,
+} FfiAdminResult;
+
+typedef enum FfiProfileErrorKind {
+  NicknameTooLongError,
+  InvalidNicknameError,
+  NicknameTakenError,
+  TooManyAttributesError,
+  InvalidAttributeKeyError,
+  InvalidAttributeValueError,
+} FfiProfileErrorKind;
+
 typedef enum FfiExitReason {
   ExitReason,
   GameOverReason,
@@ -46,21 +102,85 @@ typedef enum FfiExitReason {
   ErrorReason,
 } FfiExitReason;
 
+typedef enum FfiPromptResultKind {
+  PromptPending,
+  PromptSubmitted,
+  PromptDismissed,
+  PromptTimedOut,
+  PromptCancelled,
+  PromptDisconnected,
+} FfiPromptResultKind;
+
+typedef enum FfiAdminCommandKind {
+  AdminLock,
+  AdminUnlock,
+  AdminKick,
+  AdminBan,
+  AdminPardon,
+  AdminClose,
+} FfiAdminCommandKind;
+
 typedef enum FfiGameMessageTag {
   GameEventTrigger,
   GameMsg,
   GameLetExit,
   GameError,
   GameEnd,
+  GameTeamChanged,
+  GameInputRejected,
+  GameVibrate,
+  GameSampleRate,
+  GamePrompt,
+  GameCancelPrompt,
+  GameAdminReply,
+  GameProfileChanged,
+  GameProfileRejected,
 } FfiGameMessageTag;
 
+typedef enum FfiInputError {
+  KeyNotRegisteredError,
+  KindMismatchError,
+  ValueOutOfRangeError,
+} FfiInputError;
+
+typedef enum FfiKeyKind {
+  ButtonKey,
+  AxisKey,
+  DirectionKey,
+  TouchpadKey,
+} FfiKeyKind;
+
 typedef enum FfiJoinFailedMessage {
   ContainIdenticalPlayer,
   PlayerBanned,
   GameLocked,
   UnknownError,
+  RoomNotFound,
+  GameFull,
+  NotAllowed,
+  KickCooldown,
+  IdentityNotProven,
+  GuestsNotAllowed,
+  InvalidProfile,
 } FfiJoinFailedMessage;
 
+typedef enum FfiDeadZoneShape {
+  RadialDeadZone,
+  AxialDeadZone,
+} FfiDeadZoneShape;
+
+typedef enum FfiTeamPolicy {
+  ManualTeamPolicy,
+  RoundRobinTeamPolicy,
+  BalancedTeamPolicy,
+} FfiTeamPolicy;
+
+typedef enum FfiNicknamePolicy {
+  AnyNicknamePolicy,
+  UniqueNicknamePolicy,
+  SuffixNicknamePolicy,
+} FfiNicknamePolicy;
+
 typedef enum FfiServiceType {
   Unknown,
   TCPConnection,
@@ -68,21 +188,57 @@ typedef enum FfiServiceType {
   USB,
 } FfiServiceType;
 
+typedef enum FfiControlEventKind {
+  ControlInputEvent,
+  PlayerJoinedEvent,
+  PlayerLeftEvent,
+  PlayerTimedOutEvent,
+  PlayerKickedEvent,
+  PlayerBannedEvent,
+  ProfileUpdatedEvent,
+} FfiControlEventKind;
+
+typedef enum FfiInputKind {
+  ButtonInput,
+  AxisInput,
+  DirectionInput,
+  TouchInput,
+} FfiInputKind;
+
 typedef struct FfiAccount {
   char *id;
   char *player_hash;
 } FfiAccount;
 
+typedef struct KeyValuePair {
+  char *key;
+  char *value;
+} KeyValuePair;
+
+typedef struct FfiGameInfo {
+  struct KeyValuePair *data;
+  uintptr_t len;
+  uintptr_t cap;
+} FfiGameInfo;
+
 typedef struct FfiCustomize {
   char *nickname;
   int color_hue;
   double color_saturation;
   double color_value;
+  /**
+   * Profile attributes, e.g. a preferred language or an avatar reference
+   */
+  struct FfiGameInfo attributes;
 } FfiCustomize;
 
 typedef struct FfiPlayer {
   struct FfiAccount account;
   struct FfiCustomize *customize;
+  /**
+   * Legacy player hash kept after upgrading the credentials (Null if none)
+   */
+  char *previous_hash;
 } FfiPlayer;
 
 typedef struct FfiKeyAndAxis {
@@ -96,11 +252,76 @@ typedef struct FfiKeyAndDirection {
   double y;
 } FfiKeyAndDirection;
 
+typedef struct FfiMotionData {
+  /**
+   * Acceleration including gravity (x, y, z), in m/s²
+   */
+  double acceleration[3];
+  /**
+   * Angular velocity around each axis (x, y, z), in rad/s
+   */
+  double angular_velocity[3];
+  /**
+   * Orientation quaternion (x, y, z, w)
+   */
+  double orientation[4];
+} FfiMotionData;
+
+typedef struct FfiTouchData {
+  /**
+   * Touch id, stays the same from the begin to the end of a touch
+   */
+  uint32_t id;
+  enum FfiTouchPhase phase;
+  /**
+   * Normalized position (Range: 0 - 1)
+   */
+  double x;
+  /**
+   * Normalized position (Range: 0 - 1)
+   */
+  double y;
+  /**
+   * Pressure (Range: 0 - 1)
+   */
+  double pressure;
+} FfiTouchData;
+
+typedef struct FfiKeyAndTouch {
+  uint8_t key;
+  struct FfiTouchData touch;
+} FfiKeyAndTouch;
+
+typedef struct FfiPromptReply {
+  uint32_t id;
+  /**
+   * Replied text (Null if the player dismissed the prompt)
+   */
+  char *text;
+} FfiPromptReply;
+
+typedef struct FfiAdminCommand {
+  enum FfiAdminCommandKind kind;
+  /**
+   * Account id of the target (Null for lock, unlock and close)
+   */
+  char *account;
+  /**
+   * Reason of the ban (Null if not given)
+   */
+  char *reason;
+} FfiAdminCommand;
+
 typedef union FfiControlMessageUnion {
   char *message;
   uint8_t key;
   struct FfiKeyAndAxis key_and_axis;
   struct FfiKeyAndDirection key_and_direction;
+  struct FfiMotionData motion;
+  struct FfiKeyAndTouch key_and_touch;
+  struct FfiPromptReply prompt_reply;
+  struct FfiAdminCommand admin_command;
+  struct FfiCustomize customize;
 } FfiControlMessageUnion;
 
 typedef struct FfiControlMessage {
@@ -108,9 +329,80 @@ typedef struct FfiControlMessage {
   union FfiControlMessageUnion data;
 } FfiControlMessage;
 
+typedef struct FfiTeamChanged {
+  /**
+   * Team name (Null if unassigned)
+   */
+  char *team;
+  struct FfiCustomize customize;
+} FfiTeamChanged;
+
+typedef struct FfiInputRejected {
+  uint8_t key;
+  enum FfiInputError error;
+} FfiInputRejected;
+
+typedef struct FfiHapticPulse {
+  /**
+   * Duration in milliseconds
+   */
+  uint32_t duration_ms;
+  /**
+   * Intensity (Range: 0 - 1)
+   */
+  float intensity;
+} FfiHapticPulse;
+
+typedef struct FfiHapticPattern {
+  enum FfiHapticPatternKind kind;
+  /**
+   * Preset of the pattern (Ignored for custom patterns)
+   */
+  enum FfiHapticPreset preset;
+  /**
+   * Pulses of the pattern, presets are expanded
+   */
+  struct FfiHapticPulse *pulses;
+  uintptr_t len;
+  uintptr_t cap;
+} FfiHapticPattern;
+
+typedef struct FfiTextPrompt {
+  uint32_t id;
+  char *title;
+  char *placeholder;
+  /**
+   * Maximum count of characters (0 if unlimited)
+   */
+  uint32_t max_length;
+  enum FfiTextInputKind kind;
+} FfiTextPrompt;
+
+typedef struct FfiAdminReply {
+  struct FfiAdminCommand command;
+  enum FfiAdminResult result;
+} FfiAdminReply;
+
+typedef struct FfiProfileError {
+  enum FfiProfileErrorKind kind;
+  /**
+   * Key of the invalid attribute (Null unless the error is about an attribute)
+   */
+  char *attribute;
+} FfiProfileError;
+
 typedef union FfiGameMessageUnion {
   uint8_t key;
   char *message;
+  struct FfiTeamChanged team_changed;
+  struct FfiInputRejected input_rejected;
+  struct FfiHapticPattern haptic;
+  uint16_t sample_rate;
+  struct FfiTextPrompt prompt;
+  uint32_t prompt_id;
+  struct FfiAdminReply admin_reply;
+  struct FfiCustomize customize;
+  struct FfiProfileError profile_error;
   enum FfiExitReason exit_reason;
 } FfiGameMessageUnion;
 
@@ -119,8 +411,19 @@ typedef struct FfiGameMessage {
   union FfiGameMessageUnion data;
 } FfiGameMessage;
 
+typedef struct FfiPromptResult {
+  enum FfiPromptResultKind kind;
+  /**
+   * Submitted text (Null unless submitted)
+   */
+  char *text;
+} FfiPromptResult;
+
 typedef union FfiConnectionMessageUnion {
   struct FfiPlayer player;
+  char *room;
+  uint8_t signature[64];
+  struct FfiCustomize customize;
 } FfiConnectionMessageUnion;
 
 typedef struct FfiConnectionMessage {
@@ -128,20 +431,71 @@ typedef struct FfiConnectionMessage {
   union FfiConnectionMessageUnion data;
 } FfiConnectionMessage;
 
-typedef struct KeyValuePair {
-  char *key;
-  char *value;
-} KeyValuePair;
+typedef struct FfiRoomInfo {
+  char *name;
+  struct FfiGameInfo info;
+} FfiRoomInfo;
 
-typedef struct FfiGameInfo {
-  struct KeyValuePair *data;
+typedef struct FfiRoomList {
+  struct FfiRoomInfo *data;
   uintptr_t len;
   uintptr_t cap;
-} FfiGameInfo;
+} FfiRoomList;
+
+typedef struct FfiKeyInfo {
+  uint8_t key;
+  enum FfiKeyKind kind;
+  char *name;
+  double min;
+  double max;
+  double default_value;
+  /**
+   * Description of the key (Null if not described)
+   */
+  char *description;
+} FfiKeyInfo;
+
+typedef struct FfiKeyList {
+  struct FfiKeyInfo *data;
+  uintptr_t len;
+  uintptr_t cap;
+} FfiKeyList;
+
+typedef struct FfiJoinFailed {
+  enum FfiJoinFailedMessage kind;
+  /**
+   * Reason of the ban, or the key of the invalid attribute (Null otherwise)
+   */
+  char *reason;
+  /**
+   * Seconds until the ban is lifted or the kick cooldown ends (0 if permanent or not limited)
+   */
+  uint64_t seconds;
+  /**
+   * Why the custom information was not accepted (Only valid for InvalidProfile)
+   */
+  enum FfiProfileErrorKind profile_error;
+} FfiJoinFailed;
+
+typedef struct FfiJoinWelcome {
+  /**
+   * Account generated for the guest (Null fields unless welcomed as a guest)
+   */
+  struct FfiAccount account;
+  /**
+   * Team name (Null if unassigned)
+   */
+  char *team;
+  struct FfiCustomize customize;
+} FfiJoinWelcome;
 
 typedef union FfiConnectionResponseMessageUnion {
   struct FfiGameInfo game_info;
-  enum FfiJoinFailedMessage failed_message;
+  struct FfiRoomList rooms;
+  struct FfiKeyList keys;
+  struct FfiJoinFailed failed_message;
+  uint8_t challenge[32];
+  struct FfiJoinWelcome welcome;
 } FfiConnectionResponseMessageUnion;
 
 typedef struct FfiConnectionResponseMessage {
@@ -171,9 +525,70 @@ typedef struct FfiGameRuntime {
   void (*drop_fn)(void*);
 } FfiGameRuntime;
 
+typedef struct FfiBanRecord {
+  struct FfiPlayer player;
+  /**
+   * Reason of the ban (Null if not given)
+   */
+  char *reason;
+  /**
+   * Seconds since the Unix epoch (0 if unknown)
+   */
+  uint64_t banned_at;
+  /**
+   * When the ban is lifted, seconds since the Unix epoch (0 if permanent)
+   */
+  uint64_t expires_at;
+} FfiBanRecord;
+
+typedef struct FfiBanList {
+  struct FfiBanRecord *bans;
+  uintptr_t len;
+  uintptr_t cap;
+} FfiBanList;
+
+typedef struct FfiKnownPlayer {
+  /**
+   * Account with its last custom information
+   */
+  struct FfiPlayer player;
+  /**
+   * Seat taken by the player (-1 if none)
+   */
+  int seat;
+} FfiKnownPlayer;
+
+typedef struct FfiKnownPlayerList {
+  struct FfiKnownPlayer *players;
+  uintptr_t len;
+  uintptr_t cap;
+} FfiKnownPlayerList;
+
+typedef struct FfiPlayerList {
+  struct FfiPlayer *players;
+  uintptr_t len;
+  uintptr_t cap;
+} FfiPlayerList;
+
 typedef struct FfiControlEvent {
+  enum FfiControlEventKind kind;
   struct FfiPlayer player;
+  /**
+   * The account has a seat, guests that left and unseated accounts have none
+   */
+  bool has_seat;
+  /**
+   * Seat of the account (Only valid if has_seat)
+   */
+  uint8_t seat;
+  /**
+   * Control message (Only valid for ControlInputEvent)
+   */
   struct FfiControlMessage message;
+  /**
+   * Exit reason (Only valid when the player left)
+   */
+  enum FfiExitReason exit_reason;
 } FfiControlEvent;
 
 typedef struct FfiButtonStatus {
@@ -182,6 +597,54 @@ typedef struct FfiButtonStatus {
   bool released;
 } FfiButtonStatus;
 
+/**
+ * A single input of a snapshot, players are identified by their seats
+ */
+typedef struct FfiInputEntry {
+  /**
+   * The player has a seat
+   */
+  bool has_seat;
+  /**
+   * Seat of the player (Only valid if has_seat)
+   */
+  uint8_t seat;
+  enum FfiInputKind kind;
+  uint8_t key;
+  /**
+   * Button level (ButtonInput)
+   */
+  bool pressed;
+  /**
+   * Pressed since the last snapshot (ButtonInput)
+   */
+  bool just_pressed;
+  /**
+   * Released since the last snapshot (ButtonInput)
+   */
+  bool just_released;
+  /**
+   * Count of presses since the last snapshot (ButtonInput)
+   */
+  uint32_t press_count;
+  /**
+   * Axis value, or the x of the direction or the touch
+   */
+  double x;
+  /**
+   * The y of the direction or the touch
+   */
+  double y;
+  /**
+   * Touch id of the finger (TouchInput)
+   */
+  uint32_t touch_id;
+  /**
+   * Pressure of the finger (TouchInput)
+   */
+  double pressure;
+} FfiInputEntry;
+
 typedef struct FfiAxis {
   bool found;
   double axis;
@@ -193,16 +656,106 @@ typedef struct FfiDirection {
   double y;
 } FfiDirection;
 
+typedef struct FfiTouchPoint {
+  uint32_t id;
+  double x;
+  double y;
+  double pressure;
+} FfiTouchPoint;
+
+typedef struct FfiMotion {
+  bool found;
+  struct FfiMotionData motion;
+} FfiMotion;
+
 typedef struct FfiBooleanResult {
   bool found;
   bool result;
 } FfiBooleanResult;
 
-typedef struct FfiPlayerList {
-  struct FfiPlayer *players;
+typedef struct FfiSeat {
+  bool found;
+  uint8_t seat;
+} FfiSeat;
+
+typedef struct FfiPlayerStats {
+  /**
+   * False if the player never joined the game
+   */
+  bool found;
+  uintptr_t session_count;
+  /**
+   * Seconds
+   */
+  uint64_t total_play_time;
+  uint64_t total_inputs;
+  uint64_t total_presses;
+  /**
+   * Seconds since the Unix epoch (0 if never joined)
+   */
+  uint64_t first_joined_at;
+  /**
+   * Seconds since the Unix epoch, now if online (0 if never joined)
+   */
+  uint64_t last_seen_at;
+} FfiPlayerStats;
+
+typedef struct FfiSession {
+  /**
+   * Seconds since the Unix epoch
+   */
+  uint64_t joined_at;
+  /**
+   * Seconds since the Unix epoch (0 if open)
+   */
+  uint64_t left_at;
+  /**
+   * True while the player is online
+   */
+  bool open;
+  /**
+   * Why the player left (Ignored if open)
+   */
+  enum FfiExitReason exit_reason;
+  uint64_t inputs;
+  uint64_t presses;
+} FfiSession;
+
+typedef struct FfiSessionList {
+  struct FfiSession *sessions;
   uintptr_t len;
   uintptr_t cap;
-} FfiPlayerList;
+} FfiSessionList;
+
+/**
+ * Callback of button events: (player, key, user_data)
+ */
+typedef void (*FfiKeyCallback)(const struct FfiPlayer*, uint8_t, void*);
+
+/**
+ * Callback of axis events: (player, key, axis, user_data)
+ */
+typedef void (*FfiAxisCallback)(const struct FfiPlayer*, uint8_t, double, void*);
+
+/**
+ * Callback of direction events: (player, key, x, y, user_data)
+ */
+typedef void (*FfiDirectionCallback)(const struct FfiPlayer*, uint8_t, double, double, void*);
+
+/**
+ * Callback of touch events: (player, key, touch, user_data)
+ */
+typedef void (*FfiTouchCallback)(const struct FfiPlayer*, uint8_t, struct FfiTouchData, void*);
+
+/**
+ * Callback of plain messages: (player, message, user_data)
+ */
+typedef void (*FfiMessageCallback)(const struct FfiPlayer*, const char*, void*);
+
+/**
+ * Callback of player events: (player, user_data)
+ */
+typedef void (*FfiPlayerCallback)(const struct FfiPlayer*, void*);
 
 typedef struct FfiTcpClientService {
   void *_0;
@@ -220,14 +773,43 @@ void free_c_string(char *ptr);
 
 /**
  * Register a player
+ * Returns null if the credentials cannot be derived, such as when the pepper is not accepted
  */
 struct FfiPlayer *player_register(const char *id, const char *password);
 
+/**
+ * Restore a registered player from the player hash saved at registration
+ * Returns null if the password does not match the hash
+ */
+struct FfiPlayer *player_login(const char *id, const char *password, const char *hash);
+
 /**
  * Register a player from hash
  */
 struct FfiPlayer *player_from_hash(const char *hash);
 
+/**
+ * Generate a new key for a key account
+ * Returns the secret key in hex, keep it safe and free it with free_c_string
+ */
+char *player_generate_key(void);
+
+/**
+ * Register a key account from the secret key in hex
+ * Returns null if the secret is invalid
+ */
+struct FfiPlayer *player_register_with_key(const char *secret);
+
+/**
+ * Check if the player is identified by a public key
+ */
+bool player_is_key_account(const struct FfiPlayer *player);
+
+/**
+ * Check if the player is an ephemeral guest
+ */
+bool player_is_guest(const struct FfiPlayer *player);
+
 /**
  * Get a hash from player
  */
@@ -238,6 +820,17 @@ const char *player_get_hash(struct FfiPlayer *player);
  */
 bool player_check(const struct FfiPlayer *player, const char *password);
 
+/**
+ * Check if the player hash is in an outdated credential format
+ */
+bool player_needs_migration(const struct FfiPlayer *player);
+
+/**
+ * Check the password and upgrade outdated credentials of the player
+ * Returns true if the player hash was replaced
+ */
+bool player_migrate(struct FfiPlayer *player, const char *password);
+
 /**
  * Set the player's nickname
  */
@@ -253,6 +846,17 @@ void player_set_hue(struct FfiPlayer *player, int hue);
  */
 void player_set_hsv(struct FfiPlayer *player, int hue, double saturation, double value);
 
+/**
+ * Set a profile attribute of the player, an empty value removes it
+ */
+void player_set_attribute(struct FfiPlayer *player, const char *key, const char *value);
+
+/**
+ * Get a profile attribute of the player
+ * Returns null if the attribute is not set, free the value with free_c_string
+ */
+char *player_get_attribute(const struct FfiPlayer *player, const char *key);
+
 /**
  * Free the player
  */
@@ -268,6 +872,21 @@ void free_control_message(struct FfiControlMessage *msg);
  */
 void free_game_message(struct FfiGameMessage *msg);
 
+/**
+ * Free HapticPattern
+ */
+void free_haptic_pattern(struct FfiHapticPattern *pattern);
+
+/**
+ * Free TextPrompt
+ */
+void free_text_prompt(struct FfiTextPrompt *prompt);
+
+/**
+ * Free PromptResult
+ */
+void free_prompt_result(struct FfiPromptResult *result);
+
 /**
  * Free ExitReason
  */
@@ -288,6 +907,10 @@ void free_connection_response_message(struct FfiConnectionResponseMessage *msg);
  */
 void free_join_failed_message(enum FfiJoinFailedMessage *msg);
 
+void free_key_list(struct FfiKeyList list);
+
+void free_room_list(struct FfiRoomList list);
+
 void free_game_info(struct FfiGameInfo map);
 
 /**
@@ -301,6 +924,21 @@ struct FfiControllerData *controller_data_new(void);
 void controller_data_bind_player(struct FfiControllerData *controller,
                                  struct FfiPlayer *ffi_player);
 
+/**
+ * Join as a guest with the nickname and color instead of an account
+ */
+void controller_data_bind_guest(struct FfiControllerData *controller,
+                                const char *nickname,
+                                int hue,
+                                double saturation,
+                                double value);
+
+/**
+ * Bind the secret key (hex) of a key account to controller
+ * Returns false if the secret is invalid
+ */
+bool controller_data_bind_key(struct FfiControllerData *controller, const char *secret);
+
 /**
  * Build runtime
  */
@@ -351,11 +989,119 @@ void controller_runtime_change_direction(struct FfiControllerRuntime *runtime,
                                          double x,
                                          double y);
 
+/**
+ * Touch a touchpad
+ */
+void controller_runtime_touch(struct FfiControllerRuntime *runtime,
+                              uint8_t key,
+                              struct FfiTouchData touch);
+
+/**
+ * Touch a touchpad by its name, returns false if the name is not found
+ */
+bool controller_runtime_touch_by_name(struct FfiControllerRuntime *runtime,
+                                      const char *name,
+                                      struct FfiTouchData touch);
+
+/**
+ * Send a motion sample, ignored if the game does not enable motion sensors
+ */
+void controller_runtime_send_motion(struct FfiControllerRuntime *runtime,
+                                    struct FfiMotionData motion);
+
+/**
+ * Samples per second requested by the game (0 if motion sensors are not enabled)
+ */
+uint16_t controller_runtime_get_sample_rate(struct FfiControllerRuntime *runtime);
+
+/**
+ * Find the key ID by its name using the keys downloaded from the game
+ * Returns -1 if the name is not found
+ */
+int controller_runtime_find_key(struct FfiControllerRuntime *runtime,
+                                enum FfiKeyKind kind,
+                                const char *name);
+
+/**
+ * Press a button by its name, returns false if the name is not found
+ */
+bool controller_runtime_press_button_by_name(struct FfiControllerRuntime *runtime,
+                                             const char *name);
+
+/**
+ * Release a button by its name, returns false if the name is not found
+ */
+bool controller_runtime_release_button_by_name(struct FfiControllerRuntime *runtime,
+                                               const char *name);
+
+/**
+ * Change axis value by the key name, returns false if the name is not found
+ */
+bool controller_runtime_change_axis_by_name(struct FfiControllerRuntime *runtime,
+                                            const char *name,
+                                            double axis);
+
+/**
+ * Change direction value by the key name, returns false if the name is not found
+ */
+bool controller_runtime_change_direction_by_name(struct FfiControllerRuntime *runtime,
+                                                 const char *name,
+                                                 double x,
+                                                 double y);
+
+/**
+ * Get the keys downloaded from the game, free it with free_key_list
+ */
+struct FfiKeyList controller_runtime_get_keys(struct FfiControllerRuntime *runtime);
+
 /**
  * Pop a message from the queue
  */
 struct FfiGameMessage *controller_runtime_pop(struct FfiControllerRuntime *runtime);
 
+/**
+ * Take the latest vibration requested by the game (Null if none)
+ * Free it with free_haptic_pattern
+ */
+struct FfiHapticPattern *controller_runtime_take_haptic(struct FfiControllerRuntime *runtime);
+
+/**
+ * Get the oldest prompt waiting for the reply of the player (Null if none)
+ * Free it with free_text_prompt
+ */
+struct FfiTextPrompt *controller_runtime_get_pending_prompt(struct FfiControllerRuntime *runtime);
+
+/**
+ * Reply to the prompt with the text, returns false if the prompt is not pending
+ */
+bool controller_runtime_reply_prompt(struct FfiControllerRuntime *runtime,
+                                     uint32_t id,
+                                     const char *text);
+
+/**
+ * Dismiss the prompt without replying text, returns false if the prompt is not pending
+ */
+bool controller_runtime_dismiss_prompt(struct FfiControllerRuntime *runtime, uint32_t id);
+
+/**
+ * Send an admin command, the game replies with GameAdminReply
+ */
+void controller_runtime_admin(struct FfiControllerRuntime *runtime,
+                              const struct FfiAdminCommand *command);
+
+/**
+ * Ask the game to replace the custom information of the player
+ * The game replies with GameProfileChanged, or GameProfileRejected if it is not accepted
+ * Returns false if the custom information is invalid
+ */
+bool controller_runtime_update_profile(struct FfiControllerRuntime *runtime,
+                                       const struct FfiCustomize *customize);
+
+/**
+ * Get team assigned by the game (Null if unassigned)
+ */
+char *controller_runtime_get_team(struct FfiControllerRuntime *runtime);
+
 /**
  * Free runtime memory
  */
@@ -383,6 +1129,83 @@ struct FfiGameData *game_data_set_name_info(struct FfiGameData *data, const char
  */
 struct FfiGameData *game_data_set_version_info(struct FfiGameData *data, const char *version);
 
+/**
+ * Set max players
+ */
+struct FfiGameData *game_data_set_max_players(struct FfiGameData *data, uint8_t max_players);
+
+/**
+ * Register or modify a key
+ * The description is optional (Nullable), the range is ignored for buttons and touchpads
+ */
+struct FfiGameData *game_data_register_key(struct FfiGameData *data,
+                                           uint8_t key,
+                                           enum FfiKeyKind kind,
+                                           const char *name,
+                                           double min,
+                                           double max,
+                                           double default_value,
+                                           const char *description);
+
+/**
+ * Set the input processing of a registered key
+ * A curve exponent of 1 keeps the response linear
+ */
+struct FfiGameData *game_data_set_key_processing(struct FfiGameData *data,
+                                                 uint8_t key,
+                                                 bool clamp,
+                                                 bool normalize,
+                                                 double dead_zone,
+                                                 enum FfiDeadZoneShape dead_zone_shape,
+                                                 double smoothing,
+                                                 double curve_exponent);
+
+/**
+ * Set a custom response curve of a registered key
+ * The curve is interpolated between the points (inputs[i], outputs[i])
+ */
+struct FfiGameData *game_data_set_key_curve_points(struct FfiGameData *data,
+                                                   uint8_t key,
+                                                   const double *inputs,
+                                                   const double *outputs,
+                                                   uintptr_t len);
+
+/**
+ * Accept motion samples from the controllers at the sample rate (Hz)
+ */
+struct FfiGameData *game_data_enable_motion(struct FfiGameData *data, uint16_t sample_rate);
+
+/**
+ * Add or modify a team
+ */
+struct FfiGameData *game_data_add_team(struct FfiGameData *data,
+                                       const char *name,
+                                       int hue,
+                                       double saturation,
+                                       double value);
+
+/**
+ * Set team policy
+ */
+struct FfiGameData *game_data_set_team_policy(struct FfiGameData *data, enum FfiTeamPolicy policy);
+
+/**
+ * Let the player send admin commands from its controller
+ */
+struct FfiGameData *game_data_add_admin(struct FfiGameData *data,
+                                        const struct FfiPlayer *ffi_player);
+
+/**
+ * Let players join as guests without an account
+ */
+struct FfiGameData *game_data_allow_guests(struct FfiGameData *data, bool allow);
+
+/**
+ * Set how the nicknames of online players are kept apart
+ */
+struct FfiGameData *game_data_set_nickname_policy(struct FfiGameData *data,
+                                                  enum FfiNicknamePolicy policy);
+
 /**
  * Load data archive
  */
@@ -410,6 +1233,49 @@ struct FfiGameRuntimeArchive *game_archive_data_new(void);
 struct FfiGameRuntimeArchive *game_archive_data_add_ban_player(struct FfiGameRuntimeArchive *data,
                                                                struct FfiPlayer *ffi_player);
 
+/**
+ * Add a ban with its reason (Nullable), time and expiry (Seconds since the Unix epoch, 0 if permanent)
+ */
+struct FfiGameRuntimeArchive *game_archive_data_add_ban(struct FfiGameRuntimeArchive *data,
+                                                        const struct FfiPlayer *ffi_player,
+                                                        const char *reason,
+                                                        uint64_t banned_at,
+                                                        uint64_t expires_at);
+
+/**
+ * Put the account id on the allow list
+ */
+struct FfiGameRuntimeArchive *game_archive_data_allow(struct FfiGameRuntimeArchive *data,
+                                                      const char *id);
+
+/**
+ * Enable or disable the allow list
+ */
+struct FfiGameRuntimeArchive *game_archive_data_set_allow_list(struct FfiGameRuntimeArchive *data,
+                                                               bool enabled);
+
+/**
+ * Add a known player with its custom information and seat (-1 if none)
+ */
+struct FfiGameRuntimeArchive *game_archive_data_add_known_player(struct FfiGameRuntimeArchive *data,
+                                                                 const struct FfiPlayer *ffi_player,
+                                                                 int seat);
+
+/**
+ * Get the version of the archive
+ */
+uint32_t game_archive_data_get_version(const struct FfiGameRuntimeArchive *data);
+
+/**
+ * Get the bans of the archive
+ */
+struct FfiBanList game_archive_data_get_bans(const struct FfiGameRuntimeArchive *data);
+
+/**
+ * Get the known players of the archive
+ */
+struct FfiKnownPlayerList game_archive_data_get_known_players(const struct FfiGameRuntimeArchive *data);
+
 /**
  * Free data
  */
@@ -439,6 +1305,80 @@ void game_runtime_send_event(struct FfiGameRuntime *runtime,
                              enum FfiServiceType service_type,
                              uint8_t key);
 
+/**
+ * Vibrate the controller of the online player, returns false if the player is offline
+ * The pattern is not freed
+ */
+bool game_runtime_vibrate(struct FfiGameRuntime *runtime,
+                          const struct FfiPlayer *player,
+                          const struct FfiHapticPattern *pattern);
+
+/**
+ * Vibrate the controller of the online player with a preset, returns false if the player is offline
+ */
+bool game_runtime_vibrate_preset(struct FfiGameRuntime *runtime,
+                                 const struct FfiPlayer *player,
+                                 enum FfiHapticPreset preset);
+
+/**
+ * Send a message to all online members of the team
+ */
+void game_runtime_send_message_to_team(struct FfiGameRuntime *runtime,
+                                       const char *team,
+                                       struct FfiGameMessage *message);
+
+/**
+ * Send a message to all online players, returns the count of players reached
+ */
+uintptr_t game_runtime_broadcast(struct FfiGameRuntime *runtime, struct FfiGameMessage *message);
+
+/**
+ * Send a message to all online players except the player, returns the count of players reached
+ */
+uintptr_t game_runtime_broadcast_except(struct FfiGameRuntime *runtime,
+                                        const struct FfiPlayer *player,
+                                        struct FfiGameMessage *message);
+
+/**
+ * Send a message to the players, offline players are skipped
+ * Returns the count of players reached
+ */
+uintptr_t game_runtime_multicast(struct FfiGameRuntime *runtime,
+                                 const struct FfiPlayer *players,
+                                 uintptr_t len,
+                                 struct FfiGameMessage *message);
+
+/**
+ * Assign a player to a team (Remove from its team if the team is null)
+ */
+bool game_runtime_assign_team(struct FfiGameRuntime *runtime,
+                              const struct FfiPlayer *player,
+                              const char *team);
+
+/**
+ * Get team of player (Null if unassigned)
+ */
+char *game_runtime_get_player_team(struct FfiGameRuntime *runtime, const struct FfiPlayer *player);
+
+/**
+ * Get the online player with its current custom information (Null if not online)
+ * Free it with free_player
+ */
+struct FfiPlayer *game_runtime_get_player_profile(struct FfiGameRuntime *runtime,
+                                                  const struct FfiPlayer *player);
+
+/**
+ * Replace the custom information of the online player with the one of the player
+ * Returns false if the player is not online or the custom information is not accepted
+ */
+bool game_runtime_change_profile(struct FfiGameRuntime *runtime, const struct FfiPlayer *player);
+
+/**
+ * Get online members of team
+ */
+struct FfiPlayerList game_runtime_get_team_members(struct FfiGameRuntime *runtime,
+                                                   const char *team);
+
 /**
  * Pop a control event
  */
@@ -466,6 +1406,23 @@ void game_runtime_ban_player(struct FfiGameRuntime *runtime,
                              const struct FfiPlayer *player,
                              enum FfiServiceType service_type);
 
+/**
+ * Kick a player, it can't rejoin before the cooldown ends (Seconds, 0 for none)
+ */
+void game_runtime_kick_player_with_cooldown(struct FfiGameRuntime *runtime,
+                                            const struct FfiPlayer *player,
+                                            uint64_t cooldown_secs,
+                                            enum FfiServiceType service_type);
+
+/**
+ * Ban a player (And kick) with the reason (Nullable) for the duration (Seconds, 0 if permanent)
+ */
+void game_runtime_ban_player_for(struct FfiGameRuntime *runtime,
+                                 const struct FfiPlayer *player,
+                                 const char *reason,
+                                 uint64_t duration_secs,
+                                 enum FfiServiceType service_type);
+
 /**
  * Pardon a player
  */
@@ -491,6 +1448,57 @@ void game_runtime_unlock(struct FfiGameRuntime *runtime);
  */
 bool game_runtime_get_lock_status(struct FfiGameRuntime *runtime);
 
+/**
+ * Let only the accounts on the allow list join
+ */
+void game_runtime_enable_allow_list(struct FfiGameRuntime *runtime);
+
+/**
+ * Let every account join again
+ */
+void game_runtime_disable_allow_list(struct FfiGameRuntime *runtime);
+
+/**
+ * Get allow list status
+ */
+bool game_runtime_is_allow_list_enabled(struct FfiGameRuntime *runtime);
+
+/**
+ * Let guests join the game
+ */
+void game_runtime_allow_guests(struct FfiGameRuntime *runtime);
+
+/**
+ * Stop guests from joining the game
+ */
+void game_runtime_deny_guests(struct FfiGameRuntime *runtime);
+
+/**
+ * Check if guests can join the game
+ */
+bool game_runtime_are_guests_allowed(struct FfiGameRuntime *runtime);
+
+/**
+ * Put the account id on the allow list
+ */
+void game_runtime_allow_account(struct FfiGameRuntime *runtime, const char *id);
+
+/**
+ * Take the account id off the allow list
+ */
+void game_runtime_disallow_account(struct FfiGameRuntime *runtime, const char *id);
+
+/**
+ * Check if the player can send admin commands
+ */
+bool game_runtime_is_admin(struct FfiGameRuntime *runtime, const struct FfiPlayer *player);
+
+/**
+ * Check if the player can pass the allow list, every player can while it is disabled
+ */
+bool game_runtime_is_account_allowed(struct FfiGameRuntime *runtime,
+                                     const struct FfiPlayer *player);
+
 /**
  * Get button status of player
  */
@@ -498,6 +1506,20 @@ struct FfiButtonStatus game_runtime_get_button_status(struct FfiGameRuntime *run
                                                       const struct FfiPlayer *player,
                                                       uint8_t key);
 
+/**
+ * Count the entries of the next snapshot, used to allocate the buffer
+ */
+uintptr_t game_runtime_snapshot_len(struct FfiGameRuntime *runtime);
+
+/**
+ * Take a snapshot of all online players' inputs and begin a new frame
+ * Fills at most capacity entries into the buffer, returns the count of entries of the snapshot
+ * Entries beyond the capacity are dropped, use game_runtime_snapshot_len to size the buffer
+ */
+uintptr_t game_runtime_snapshot(struct FfiGameRuntime *runtime,
+                                struct FfiInputEntry *buffer,
+                                uintptr_t capacity);
+
 /**
  * Get axis value of player
  */
@@ -512,6 +1534,72 @@ struct FfiDirection game_runtime_get_direction(struct FfiGameRuntime *runtime,
                                                const struct FfiPlayer *player,
                                                uint8_t key);
 
+/**
+ * Get the fingers of player on the touchpad, ordered by touch ids
+ * Fills at most capacity points into the buffer, returns the count of fingers
+ */
+uintptr_t game_runtime_get_touches(struct FfiGameRuntime *runtime,
+                                   const struct FfiPlayer *player,
+                                   uint8_t key,
+                                   struct FfiTouchPoint *buffer,
+                                   uintptr_t capacity);
+
+/**
+ * Get the latest motion sample of player
+ */
+struct FfiMotion game_runtime_get_motion(struct FfiGameRuntime *runtime,
+                                         const struct FfiPlayer *player);
+
+/**
+ * Ask the controller of the online player to send motion samples at the rate (Hz)
+ * Returns false if the player is offline or motion sensors are not enabled
+ */
+bool game_runtime_request_sample_rate(struct FfiGameRuntime *runtime,
+                                      const struct FfiPlayer *player,
+                                      uint16_t sample_rate);
+
+/**
+ * Ask the online player to type a text, the id of the prompt is ignored
+ * Returns the request id (0 if the player is offline), the prompt never times out if the timeout is 0
+ */
+uint32_t game_runtime_prompt(struct FfiGameRuntime *runtime,
+                             const struct FfiPlayer *player,
+                             const struct FfiTextPrompt *prompt,
+                             uint64_t timeout_ms);
+
+/**
+ * Cancel the pending prompt, returns false if it is not pending
+ */
+bool game_runtime_cancel_prompt(struct FfiGameRuntime *runtime, uint32_t id);
+
+/**
+ * Take the result of the prompt, the kind is PromptPending while no result is available
+ * Free it with free_prompt_result
+ */
+struct FfiPromptResult *game_runtime_take_prompt_result(struct FfiGameRuntime *runtime,
+                                                        uint32_t id);
+
+/**
+ * Get button status of player by the key name
+ */
+struct FfiButtonStatus game_runtime_get_button_status_by_name(struct FfiGameRuntime *runtime,
+                                                              const struct FfiPlayer *player,
+                                                              const char *name);
+
+/**
+ * Get axis value of player by the key name
+ */
+struct FfiAxis game_runtime_get_axis_by_name(struct FfiGameRuntime *runtime,
+                                             const struct FfiPlayer *player,
+                                             const char *name);
+
+/**
+ * Get direction value of player by the key name
+ */
+struct FfiDirection game_runtime_get_direction_by_name(struct FfiGameRuntime *runtime,
+                                                       const struct FfiPlayer *player,
+                                                       const char *name);
+
 /**
  * Get service type of player
  */
@@ -530,11 +1618,41 @@ struct FfiBooleanResult game_runtime_is_player_banned(struct FfiGameRuntime *run
 struct FfiBooleanResult game_runtime_is_player_online(struct FfiGameRuntime *runtime,
                                                       const struct FfiPlayer *player);
 
+/**
+ * Get seat of player
+ */
+struct FfiSeat game_runtime_get_player_seat(struct FfiGameRuntime *runtime,
+                                            const struct FfiPlayer *player);
+
+/**
+ * Get the player sitting at the seat (Null if the seat is empty)
+ */
+struct FfiPlayer *game_runtime_get_player_at_seat(struct FfiGameRuntime *runtime, uint8_t seat);
+
 /**
  * Get online list
  */
 struct FfiPlayerList game_runtime_get_online_list(struct FfiGameRuntime *runtime);
 
+/**
+ * Archive the runtime data, the runtime keeps running
+ * Free it with free_game_archive_data
+ */
+struct FfiGameRuntimeArchive *game_runtime_archive(struct FfiGameRuntime *runtime);
+
+/**
+ * Get the statistics of the player
+ */
+struct FfiPlayerStats game_runtime_get_stats(struct FfiGameRuntime *runtime,
+                                             const struct FfiPlayer *player);
+
+/**
+ * Get the session history of the player, ordered by join time
+ * Free it with free_session_list
+ */
+struct FfiSessionList game_runtime_get_sessions(struct FfiGameRuntime *runtime,
+                                                const struct FfiPlayer *player);
+
 /**
  * Get banned list
  */
@@ -555,6 +1673,97 @@ void free_control_event(struct FfiControlEvent *event);
  */
 void free_player_list(struct FfiPlayerList list);
 
+/**
+ * Free session list
+ */
+void free_session_list(struct FfiSessionList list);
+
+/**
+ * Free ban list
+ */
+void free_ban_list(struct FfiBanList list);
+
+/**
+ * Free known player list
+ */
+void free_known_player_list(struct FfiKnownPlayerList list);
+
+/**
+ * Register a callback invoked when a button is pressed
+ */
+void game_runtime_on_pressed(struct FfiGameRuntime *runtime,
+                             FfiKeyCallback callback,
+                             void *user_data);
+
+/**
+ * Register a callback invoked when a button is released
+ */
+void game_runtime_on_released(struct FfiGameRuntime *runtime,
+                              FfiKeyCallback callback,
+                              void *user_data);
+
+/**
+ * Register a callback invoked when the value of an axis is changed
+ */
+void game_runtime_on_axis_changed(struct FfiGameRuntime *runtime,
+                                  FfiAxisCallback callback,
+                                  void *user_data);
+
+/**
+ * Register a callback invoked when the value of a direction is changed
+ */
+void game_runtime_on_direction_changed(struct FfiGameRuntime *runtime,
+                                       FfiDirectionCallback callback,
+                                       void *user_data);
+
+/**
+ * Register a callback invoked when a finger touched, moved on or left a touchpad
+ */
+void game_runtime_on_touch(struct FfiGameRuntime *runtime,
+                           FfiTouchCallback callback,
+                           void *user_data);
+
+/**
+ * Register a callback invoked when a player sends a plain message
+ */
+void game_runtime_on_message(struct FfiGameRuntime *runtime,
+                             FfiMessageCallback callback,
+                             void *user_data);
+
+/**
+ * Register a callback invoked when a player joined the game
+ */
+void game_runtime_on_player_joined(struct FfiGameRuntime *runtime,
+                                   FfiPlayerCallback callback,
+                                   void *user_data);
+
+/**
+ * Register a callback invoked when a player left the game
+ */
+void game_runtime_on_player_left(struct FfiGameRuntime *runtime,
+                                 FfiPlayerCallback callback,
+                                 void *user_data);
+
+/**
+ * Register a callback invoked when a player changed its custom information
+ * The player passed to the callback carries the profile accepted by the game
+ */
+void game_runtime_on_profile_updated(struct FfiGameRuntime *runtime,
+                                     FfiPlayerCallback callback,
+                                     void *user_data);
+
+/**
+ * Remove all registered callbacks
+ */
+void game_runtime_clear_callbacks(struct FfiGameRuntime *runtime);
+
+/**
+ * Invoke the registered callbacks for all events received since the last dispatch
+ * The runtime is unlocked while the callbacks run, so they may call other runtime functions
+ * Returns the count of dispatched events
+ */
+uintptr_t game_runtime_dispatch_events(struct FfiGameRuntime *runtime);
+
 /**
  * Free service type tag
  */
@@ -601,6 +1810,11 @@ bool tcp_client_bind_address_v6(struct FfiTcpClientService *service,
                                 const char *ip_str,
                                 uint16_t port);
 
+/**
+ * Bind the room to join
+ */
+void tcp_client_bind_room(struct FfiTcpClientService *service, const char *room);
+
 /**
  * Connect
  */
@@ -652,6 +1866,13 @@ bool tcp_server_bind_address_v6(struct FfiTcpServerService *service,
                                 const char *ip_str,
                                 uint16_t port);
 
+/**
+ * Host another runtime in the specified room
+ */
+void tcp_server_add_room(struct FfiTcpServerService *service,
+                         const char *name,
+                         struct FfiGameRuntime *runtime);
+
 /**
  * Start listening
  */
@@ -2,4 +2,5 @@ pub mod ngpd_player;
 pub mod ngpd_message;
 pub mod ngpd_game_info;
 pub mod ngpd_controller;
-pub mod ngpd_game;
\ No newline at end of file
+pub mod ngpd_game;
+pub mod ngpd_game_observer;
\ No newline at end of file
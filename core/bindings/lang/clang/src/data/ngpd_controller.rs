@@ -1,11 +1,19 @@
-use crate::data::ngpd_message::{FfiControlMessage, FfiGameMessage};
-use crate::data::ngpd_player::FfiPlayer;
+use crate::converter::string_converter::str_rs_to_c;
+use crate::data::ngpd_game::FfiKeyKind;
+use crate::data::ngpd_game_info::FfiKeyList;
+use crate::data::ngpd_message::{FfiAdminCommand, FfiControlMessage, FfiGameMessage, FfiHapticPattern, FfiMotionData, FfiTextPrompt, FfiTouchData};
+use crate::data::ngpd_player::{FfiCustomize, FfiPlayer};
 use nogamepads::entry_mutex;
 use nogamepads_core::data::controller::controller_data::ControllerData;
 use nogamepads_core::data::controller::controller_runtime::ControllerRuntime;
+use nogamepads_core::data::game::game_data::KeyKind;
+use nogamepads_core::data::message::message_admin::AdminCommand;
 use nogamepads_core::data::message::message_enums::ControlMessage;
-use nogamepads_core::data::player::player_data::Player;
-use std::ffi::{c_char, c_double, c_void, CStr};
+use nogamepads_core::data::message::message_sensors::MotionData;
+use nogamepads_core::data::message::message_touch::TouchData;
+use nogamepads_core::data::player::player_data::{Customize, Player};
+use nogamepads_core::data::player::player_identity::PlayerKey;
+use std::ffi::{c_char, c_double, c_int, c_void, CStr};
 use std::ptr::null_mut;
 use std::sync::{Arc, Mutex, MutexGuard};
 
@@ -49,6 +57,49 @@ impl FfiControllerData {
         drop(unsafe { Box::from_raw(ffi_player) });
     }
 
+    /// Join as a guest with the nickname and color instead of an account
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_data_bind_guest(
+        controller: *mut FfiControllerData,
+        nickname: *const c_char,
+        hue: c_int,
+        saturation: c_double,
+        value: c_double
+    ) {
+        if controller.is_null() || nickname.is_null() { return; }
+
+        let nickname = unsafe { CStr::from_ptr(nickname) }.to_string_lossy().into_owned();
+        let customize = Customize {
+            nickname,
+            color_hue: hue.clamp(0, 360),
+            color_saturation: saturation.clamp(0.0, 1.0),
+            color_value: value.clamp(0.0, 1.0),
+            ..Default::default()
+        };
+
+        let controller_inner = unsafe { &mut *((*controller).0 as *mut ControllerData) };
+        controller_inner.bind_guest(customize);
+    }
+
+    /// Bind the secret key (hex) of a key account to controller
+    /// Returns false if the secret is invalid
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_data_bind_key(
+        controller: *mut FfiControllerData,
+        secret: *const c_char
+    ) -> bool {
+        if controller.is_null() || secret.is_null() { return false; }
+
+        let secret = unsafe { CStr::from_ptr(secret) }.to_string_lossy().into_owned();
+        let Some(key) = PlayerKey::from_secret(&secret) else {
+            return false;
+        };
+
+        let controller_inner = unsafe { &mut *((*controller).0 as *mut ControllerData) };
+        controller_inner.bind_key(key);
+        true
+    }
+
     /// Build runtime
     #[unsafe(no_mangle)]
     pub extern "C" fn controller_data_build_runtime(
@@ -267,6 +318,160 @@ impl FfiControllerRuntime {
         )
     }
 
+    /// Touch a touchpad
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_touch(
+        runtime: *mut FfiControllerRuntime,
+        key: u8,
+        touch: FfiTouchData
+    ) {
+        if runtime.is_null() {
+            return;
+        }
+
+        Self::operate_controller_runtime_with_return(runtime, (key, TouchData::from(&touch)), |guard, (key, touch)| {
+            guard.touch(key, touch);
+            Some(())
+        });
+    }
+
+    /// Touch a touchpad by its name, returns false if the name is not found
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_touch_by_name(
+        runtime: *mut FfiControllerRuntime,
+        name: *const c_char,
+        touch: FfiTouchData
+    ) -> bool {
+        let key = Self::controller_runtime_find_key(runtime, FfiKeyKind::TouchpadKey, name);
+        if key < 0 { return false; }
+
+        Self::controller_runtime_touch(runtime, key as u8, touch);
+        true
+    }
+
+    /// Send a motion sample, ignored if the game does not enable motion sensors
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_send_motion(
+        runtime: *mut FfiControllerRuntime,
+        motion: FfiMotionData
+    ) {
+        if runtime.is_null() {
+            return;
+        }
+
+        Self::operate_controller_runtime_with_return(runtime, MotionData::from(&motion), |guard, motion| {
+            guard.send_motion(motion);
+            Some(())
+        });
+    }
+
+    /// Samples per second requested by the game (0 if motion sensors are not enabled)
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_get_sample_rate(
+        runtime: *mut FfiControllerRuntime
+    ) -> u16 {
+        if runtime.is_null() {
+            return 0;
+        }
+
+        Self::operate_controller_runtime_with_return(runtime, (), |guard, _| {
+            guard.motion_sample_rate()
+        }).unwrap_or(0)
+    }
+
+    /// Find the key ID by its name using the keys downloaded from the game
+    /// Returns -1 if the name is not found
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_find_key(
+        runtime: *mut FfiControllerRuntime,
+        kind: FfiKeyKind,
+        name: *const c_char
+    ) -> c_int {
+        if runtime.is_null() || name.is_null() {
+            return -1;
+        }
+
+        let name = unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned();
+        let key = Self::operate_controller_runtime_with_return(
+            runtime, (KeyKind::from(&kind), name), |guard, (kind, name)| {
+                guard.find_key(kind, &name)
+            }
+        );
+
+        key.map(c_int::from).unwrap_or(-1)
+    }
+
+    /// Press a button by its name, returns false if the name is not found
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_press_button_by_name(
+        runtime: *mut FfiControllerRuntime,
+        name: *const c_char
+    ) -> bool {
+        let key = Self::controller_runtime_find_key(runtime, FfiKeyKind::ButtonKey, name);
+        if key < 0 { return false; }
+
+        Self::controller_runtime_press_a_button(runtime, key as u8);
+        true
+    }
+
+    /// Release a button by its name, returns false if the name is not found
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_release_button_by_name(
+        runtime: *mut FfiControllerRuntime,
+        name: *const c_char
+    ) -> bool {
+        let key = Self::controller_runtime_find_key(runtime, FfiKeyKind::ButtonKey, name);
+        if key < 0 { return false; }
+
+        Self::controller_runtime_release_a_button(runtime, key as u8);
+        true
+    }
+
+    /// Change axis value by the key name, returns false if the name is not found
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_change_axis_by_name(
+        runtime: *mut FfiControllerRuntime,
+        name: *const c_char,
+        axis: c_double
+    ) -> bool {
+        let key = Self::controller_runtime_find_key(runtime, FfiKeyKind::AxisKey, name);
+        if key < 0 { return false; }
+
+        Self::controller_runtime_change_axis(runtime, key as u8, axis);
+        true
+    }
+
+    /// Change direction value by the key name, returns false if the name is not found
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_change_direction_by_name(
+        runtime: *mut FfiControllerRuntime,
+        name: *const c_char,
+        x: c_double,
+        y: c_double
+    ) -> bool {
+        let key = Self::controller_runtime_find_key(runtime, FfiKeyKind::DirectionKey, name);
+        if key < 0 { return false; }
+
+        Self::controller_runtime_change_direction(runtime, key as u8, x, y);
+        true
+    }
+
+    /// Get the keys downloaded from the game, free it with free_key_list
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_get_keys(
+        runtime: *mut FfiControllerRuntime
+    ) -> FfiKeyList {
+        let keys = if runtime.is_null() {
+            None
+        } else {
+            Self::operate_controller_runtime_with_return(runtime, (), |guard, _| {
+                Some(guard.keys().clone())
+            })
+        };
+
+        FfiKeyList::from(&keys.unwrap_or_default())
+    }
+
     /// Pop a message from the queue
     #[unsafe(no_mangle)]
     pub extern "C" fn controller_runtime_pop(
@@ -300,6 +505,135 @@ impl FfiControllerRuntime {
         }
     }
 
+    /// Take the latest vibration requested by the game (Null if none)
+    /// Free it with free_haptic_pattern
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_take_haptic(
+        runtime: *mut FfiControllerRuntime
+    ) -> *mut FfiHapticPattern {
+        if runtime.is_null() {
+            return null_mut();
+        }
+
+        let pattern = Self::operate_controller_runtime_with_return(runtime, (), |guard, _| {
+            guard.take_haptic()
+        });
+
+        match pattern {
+            None => { null_mut() }
+            Some(pattern) => { Box::into_raw(Box::new(FfiHapticPattern::from(&pattern))) }
+        }
+    }
+
+    /// Get the oldest prompt waiting for the reply of the player (Null if none)
+    /// Free it with free_text_prompt
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_get_pending_prompt(
+        runtime: *mut FfiControllerRuntime
+    ) -> *mut FfiTextPrompt {
+        if runtime.is_null() {
+            return null_mut();
+        }
+
+        let prompt = Self::operate_controller_runtime_with_return(runtime, (), |guard, _| {
+            guard.pending_prompts().first().cloned()
+        });
+
+        match prompt {
+            None => { null_mut() }
+            Some(prompt) => { Box::into_raw(Box::new(FfiTextPrompt::from(&prompt))) }
+        }
+    }
+
+    /// Reply to the prompt with the text, returns false if the prompt is not pending
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_reply_prompt(
+        runtime: *mut FfiControllerRuntime,
+        id: u32,
+        text: *const c_char
+    ) -> bool {
+        if runtime.is_null() || text.is_null() {
+            return false;
+        }
+
+        let text = unsafe { CStr::from_ptr(text) }.to_string_lossy().into_owned();
+        Self::operate_controller_runtime_with_return(runtime, (id, text), |guard, (id, text)| {
+            Some(guard.reply_prompt(id, text))
+        }).unwrap_or(false)
+    }
+
+    /// Dismiss the prompt without replying text, returns false if the prompt is not pending
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_dismiss_prompt(
+        runtime: *mut FfiControllerRuntime,
+        id: u32
+    ) -> bool {
+        if runtime.is_null() {
+            return false;
+        }
+
+        Self::operate_controller_runtime_with_return(runtime, id, |guard, id| {
+            Some(guard.dismiss_prompt(id))
+        }).unwrap_or(false)
+    }
+
+    /// Send an admin command, the game replies with GameAdminReply
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_admin(
+        runtime: *mut FfiControllerRuntime,
+        command: *const FfiAdminCommand
+    ) {
+        if runtime.is_null() || command.is_null() {
+            return;
+        }
+
+        let command = AdminCommand::from(unsafe { &*command });
+        Self::operate_controller_runtime_with_return(runtime, command, |guard, command| {
+            guard.admin(command);
+            Some(())
+        });
+    }
+
+    /// Ask the game to replace the custom information of the player
+    /// The game replies with GameProfileChanged, or GameProfileRejected if it is not accepted
+    /// Returns false if the custom information is invalid
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_update_profile(
+        runtime: *mut FfiControllerRuntime,
+        customize: *const FfiCustomize
+    ) -> bool {
+        if runtime.is_null() || customize.is_null() {
+            return false;
+        }
+
+        let Ok(customize) = Customize::try_from(unsafe { &*customize }) else {
+            return false;
+        };
+        Self::operate_controller_runtime_with_return(runtime, customize, |guard, customize| {
+            guard.update_profile(customize);
+            Some(true)
+        }).unwrap_or(false)
+    }
+
+    /// Get team assigned by the game (Null if unassigned)
+    #[unsafe(no_mangle)]
+    pub extern "C" fn controller_runtime_get_team(
+        runtime: *mut FfiControllerRuntime
+    ) -> *mut c_char {
+        if runtime.is_null() {
+            return null_mut();
+        }
+
+        let team = Self::operate_controller_runtime_with_return(runtime, (), |guard, _| {
+            guard.team()
+        });
+
+        match team {
+            None => { null_mut() }
+            Some(team) => { unsafe { str_rs_to_c(team) } }
+        }
+    }
+
     /// Free runtime memory
     #[unsafe(no_mangle)]
     pub extern "C" fn free_controller_runtime(runtime: *mut FfiControllerRuntime) {
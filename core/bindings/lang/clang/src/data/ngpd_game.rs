@@ -1,14 +1,22 @@
-use crate::data::ngpd_message::{free_control_message, FfiControlMessage, FfiExitReason, FfiGameMessage};
+use crate::converter::string_converter::str_rs_to_c;
+use crate::data::ngpd_message::{free_control_message, FfiControlMessage, FfiExitReason, FfiGameMessage, FfiHapticPattern, FfiHapticPreset, FfiMotionData, FfiPromptResult, FfiTextPrompt, FfiTouchPoint};
 use crate::data::ngpd_player::{free_player, FfiPlayer};
 use crate::service::ngpd_service_types::FfiServiceType;
 use nogamepads::entry_mutex;
-use nogamepads_core::data::game::game_data::{GameData, GameRuntimeDataArchive};
-use nogamepads_core::data::game::game_runtime::GameRuntime;
-use nogamepads_core::data::message::message_enums::{ExitReason, GameMessage};
+use nogamepads_core::data::game::game_data::{BanRecord, GameData, GameRuntimeDataArchive, KeyData, KeyKind, KnownPlayer, NicknamePolicy, TeamPolicy};
+use nogamepads_core::data::game::game_input::{DeadZoneShape, ResponseCurve};
+use nogamepads_core::data::game::game_runtime::{ControlEvent, GameRuntime};
+use nogamepads_core::data::game::game_snapshot::InputSnapshot;
+use nogamepads_core::data::game::game_stats::PlayerStats;
+use nogamepads_core::data::message::message_enums::{ControlMessage, ExitReason, GameMessage};
+use nogamepads_core::data::message::message_haptics::HapticPattern;
+use nogamepads_core::data::message::message_prompt::TextPrompt;
+use nogamepads_core::data::message::message_sensors::MotionData;
 use nogamepads_core::data::player::player_data::Player;
 use nogamepads_core::service::service_types::ServiceType;
-use std::ffi::{c_char, c_double, c_void, CStr};
+use std::ffi::{c_char, c_double, c_int, c_void, CStr, CString};
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 #[repr(C)]
 pub struct FfiGameData(*mut c_void);
@@ -16,10 +24,29 @@ pub struct FfiGameData(*mut c_void);
 #[repr(C)]
 pub struct FfiGameRuntimeArchive(*mut c_void);
 
+#[repr(C)]
+pub enum FfiControlEventKind {
+    ControlInputEvent,
+    PlayerJoinedEvent,
+    PlayerLeftEvent,
+    PlayerTimedOutEvent,
+    PlayerKickedEvent,
+    PlayerBannedEvent,
+    ProfileUpdatedEvent
+}
+
 #[repr(C)]
 pub struct FfiControlEvent {
+    kind: FfiControlEventKind,
     player: FfiPlayer,
-    message: FfiControlMessage
+    /// The account has a seat, guests that left and unseated accounts have none
+    has_seat: bool,
+    /// Seat of the account (Only valid if has_seat)
+    seat: u8,
+    /// Control message (Only valid for ControlInputEvent)
+    message: FfiControlMessage,
+    /// Exit reason (Only valid when the player left)
+    exit_reason: FfiExitReason
 }
 
 #[repr(C)]
@@ -28,6 +55,43 @@ pub struct FfiGameRuntime {
     drop_fn: extern "C" fn(*mut c_void),
 }
 
+#[repr(C)]
+#[derive(Clone)]
+pub enum FfiInputKind {
+    ButtonInput,
+    AxisInput,
+    DirectionInput,
+    TouchInput
+}
+
+/// A single input of a snapshot, players are identified by their seats
+#[repr(C)]
+#[derive(Clone)]
+pub struct FfiInputEntry {
+    /// The player has a seat
+    has_seat: bool,
+    /// Seat of the player (Only valid if has_seat)
+    seat: u8,
+    kind: FfiInputKind,
+    key: u8,
+    /// Button level (ButtonInput)
+    pressed: bool,
+    /// Pressed since the last snapshot (ButtonInput)
+    just_pressed: bool,
+    /// Released since the last snapshot (ButtonInput)
+    just_released: bool,
+    /// Count of presses since the last snapshot (ButtonInput)
+    press_count: u32,
+    /// Axis value, or the x of the direction or the touch
+    x: c_double,
+    /// The y of the direction or the touch
+    y: c_double,
+    /// Touch id of the finger (TouchInput)
+    touch_id: u32,
+    /// Pressure of the finger (TouchInput)
+    pressure: c_double
+}
+
 #[repr(C)]
 pub struct FfiButtonStatus {
     found: bool,
@@ -48,12 +112,74 @@ pub struct FfiDirection {
     y: c_double,
 }
 
+#[repr(C)]
+pub struct FfiMotion {
+    found: bool,
+    motion: FfiMotionData,
+}
+
+#[repr(C)]
+pub struct FfiSeat {
+    found: bool,
+    seat: u8
+}
+
 #[repr(C)]
 pub struct FfiBooleanResult {
     found: bool,
     result: bool
 }
 
+#[repr(C)]
+pub enum FfiKeyKind {
+    ButtonKey,
+    AxisKey,
+    DirectionKey,
+    TouchpadKey
+}
+
+impl From<&KeyKind> for FfiKeyKind {
+    fn from(value: &KeyKind) -> Self {
+        match value {
+            KeyKind::Button => { FfiKeyKind::ButtonKey }
+            KeyKind::Axis => { FfiKeyKind::AxisKey }
+            KeyKind::Direction => { FfiKeyKind::DirectionKey }
+            KeyKind::Touchpad => { FfiKeyKind::TouchpadKey }
+        }
+    }
+}
+
+impl From<&FfiKeyKind> for KeyKind {
+    fn from(value: &FfiKeyKind) -> Self {
+        match value {
+            FfiKeyKind::ButtonKey => { KeyKind::Button }
+            FfiKeyKind::AxisKey => { KeyKind::Axis }
+            FfiKeyKind::DirectionKey => { KeyKind::Direction }
+            FfiKeyKind::TouchpadKey => { KeyKind::Touchpad }
+        }
+    }
+}
+
+#[repr(C)]
+pub enum FfiDeadZoneShape {
+    RadialDeadZone,
+    AxialDeadZone
+}
+
+#[repr(C)]
+pub enum FfiTeamPolicy {
+    ManualTeamPolicy,
+    RoundRobinTeamPolicy,
+    BalancedTeamPolicy
+}
+
+#[repr(C)]
+pub enum FfiNicknamePolicy {
+    AnyNicknamePolicy,
+    UniqueNicknamePolicy,
+    SuffixNicknamePolicy
+}
+
 #[repr(C)]
 pub struct FfiPlayerList {
     players: *mut FfiPlayer,
@@ -61,6 +187,75 @@ pub struct FfiPlayerList {
     cap: usize,
 }
 
+#[repr(C)]
+pub struct FfiBanRecord {
+    pub player: FfiPlayer,
+    /// Reason of the ban (Null if not given)
+    pub reason: *mut c_char,
+    /// Seconds since the Unix epoch (0 if unknown)
+    pub banned_at: u64,
+    /// When the ban is lifted, seconds since the Unix epoch (0 if permanent)
+    pub expires_at: u64
+}
+
+#[repr(C)]
+pub struct FfiBanList {
+    bans: *mut FfiBanRecord,
+    len: usize,
+    cap: usize,
+}
+
+#[repr(C)]
+pub struct FfiKnownPlayer {
+    /// Account with its last custom information
+    pub player: FfiPlayer,
+    /// Seat taken by the player (-1 if none)
+    pub seat: c_int
+}
+
+#[repr(C)]
+pub struct FfiKnownPlayerList {
+    players: *mut FfiKnownPlayer,
+    len: usize,
+    cap: usize,
+}
+
+#[repr(C)]
+pub struct FfiPlayerStats {
+    /// False if the player never joined the game
+    pub found: bool,
+    pub session_count: usize,
+    /// Seconds
+    pub total_play_time: u64,
+    pub total_inputs: u64,
+    pub total_presses: u64,
+    /// Seconds since the Unix epoch (0 if never joined)
+    pub first_joined_at: u64,
+    /// Seconds since the Unix epoch, now if online (0 if never joined)
+    pub last_seen_at: u64
+}
+
+#[repr(C)]
+pub struct FfiSession {
+    /// Seconds since the Unix epoch
+    pub joined_at: u64,
+    /// Seconds since the Unix epoch (0 if open)
+    pub left_at: u64,
+    /// True while the player is online
+    pub open: bool,
+    /// Why the player left (Ignored if open)
+    pub exit_reason: FfiExitReason,
+    pub inputs: u64,
+    pub presses: u64
+}
+
+#[repr(C)]
+pub struct FfiSessionList {
+    sessions: *mut FfiSession,
+    len: usize,
+    cap: usize,
+}
+
 impl FfiGameData {
 
     /// Create game data
@@ -133,6 +328,243 @@ impl FfiGameData {
         raw
     }
 
+    /// Set max players
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_set_max_players(
+        data: *mut FfiGameData,
+        max_players: u8,
+    ) -> *mut FfiGameData {
+
+        if data.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        data_inner.max_players(max_players);
+
+        let raw = Box::into_raw(Box::new(FfiGameData(Box::into_raw(Box::new(data)) as *mut _)));
+        raw
+    }
+
+    /// Register or modify a key
+    /// The description is optional (Nullable), the range is ignored for buttons and touchpads
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_register_key(
+        data: *mut FfiGameData,
+        key: u8,
+        kind: FfiKeyKind,
+        name: *const c_char,
+        min: c_double,
+        max: c_double,
+        default_value: c_double,
+        description: *const c_char
+    ) -> *mut FfiGameData {
+
+        if data.is_null() || name.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let name_str = unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned();
+        let kind = KeyKind::from(&kind);
+
+        let mut key_data = KeyData::new(kind, name_str);
+        if matches!(kind, KeyKind::Axis | KeyKind::Direction) {
+            key_data.range(min, max);
+        }
+        key_data.default_value(default_value);
+        if !description.is_null() {
+            key_data.description(unsafe { CStr::from_ptr(description) }.to_string_lossy().into_owned());
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        data_inner.key(key, key_data);
+
+        let raw = Box::into_raw(Box::new(FfiGameData(Box::into_raw(Box::new(data)) as *mut _)));
+        raw
+    }
+
+    /// Set the input processing of a registered key
+    /// A curve exponent of 1 keeps the response linear
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_set_key_processing(
+        data: *mut FfiGameData,
+        key: u8,
+        clamp: bool,
+        normalize: bool,
+        dead_zone: c_double,
+        dead_zone_shape: FfiDeadZoneShape,
+        smoothing: c_double,
+        curve_exponent: c_double
+    ) -> *mut FfiGameData {
+
+        if data.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        if let Some(key_data) = data_inner.control.keys.get_mut(&key) {
+            let shape = match dead_zone_shape {
+                FfiDeadZoneShape::RadialDeadZone => { DeadZoneShape::Radial }
+                FfiDeadZoneShape::AxialDeadZone => { DeadZoneShape::Axial }
+            };
+            let curve = if curve_exponent == 1.0 { ResponseCurve::Linear } else { ResponseCurve::Power(curve_exponent) };
+            key_data.processing
+                .clamp(clamp)
+                .normalize(normalize)
+                .dead_zone(dead_zone, shape)
+                .smoothing(smoothing)
+                .curve(curve);
+        }
+
+        let raw = Box::into_raw(Box::new(FfiGameData(Box::into_raw(Box::new(data)) as *mut _)));
+        raw
+    }
+
+    /// Set a custom response curve of a registered key
+    /// The curve is interpolated between the points (inputs[i], outputs[i])
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_set_key_curve_points(
+        data: *mut FfiGameData,
+        key: u8,
+        inputs: *const c_double,
+        outputs: *const c_double,
+        len: usize
+    ) -> *mut FfiGameData {
+
+        if data.is_null() || (len > 0 && (inputs.is_null() || outputs.is_null())) {
+            return std::ptr::null_mut();
+        }
+
+        let points = if len == 0 {
+            Vec::new()
+        } else {
+            let inputs = unsafe { std::slice::from_raw_parts(inputs, len) };
+            let outputs = unsafe { std::slice::from_raw_parts(outputs, len) };
+            inputs.iter().copied().zip(outputs.iter().copied()).collect()
+        };
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        if let Some(key_data) = data_inner.control.keys.get_mut(&key) {
+            key_data.processing.curve(ResponseCurve::Points(points));
+        }
+
+        let raw = Box::into_raw(Box::new(FfiGameData(Box::into_raw(Box::new(data)) as *mut _)));
+        raw
+    }
+
+    /// Accept motion samples from the controllers at the sample rate (Hz)
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_enable_motion(
+        data: *mut FfiGameData,
+        sample_rate: u16
+    ) -> *mut FfiGameData {
+
+        if data.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        data_inner.motion(sample_rate);
+
+        let raw = Box::into_raw(Box::new(FfiGameData(Box::into_raw(Box::new(data)) as *mut _)));
+        raw
+    }
+
+    /// Add or modify a team
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_add_team(
+        data: *mut FfiGameData,
+        name: *const c_char,
+        hue: c_int,
+        saturation: c_double,
+        value: c_double
+    ) -> *mut FfiGameData {
+
+        if data.is_null() || name.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let name_str = unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned();
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        data_inner.team(name_str, hue, saturation, value);
+
+        let raw = Box::into_raw(Box::new(FfiGameData(Box::into_raw(Box::new(data)) as *mut _)));
+        raw
+    }
+
+    /// Set team policy
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_set_team_policy(
+        data: *mut FfiGameData,
+        policy: FfiTeamPolicy
+    ) -> *mut FfiGameData {
+
+        if data.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        data_inner.team_policy(match policy {
+            FfiTeamPolicy::ManualTeamPolicy => { TeamPolicy::Manual }
+            FfiTeamPolicy::RoundRobinTeamPolicy => { TeamPolicy::RoundRobin }
+            FfiTeamPolicy::BalancedTeamPolicy => { TeamPolicy::Balanced }
+        });
+
+        let raw = Box::into_raw(Box::new(FfiGameData(Box::into_raw(Box::new(data)) as *mut _)));
+        raw
+    }
+
+    /// Let the player send admin commands from its controller
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_add_admin(
+        data: *mut FfiGameData,
+        ffi_player: *const FfiPlayer
+    ) -> *mut FfiGameData {
+        if data.is_null() || ffi_player.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        let player = Player::try_from(unsafe { &*ffi_player }).unwrap_or_default();
+        data_inner.admin(player.account);
+        data
+    }
+
+    /// Let players join as guests without an account
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_allow_guests(
+        data: *mut FfiGameData,
+        allow: bool
+    ) -> *mut FfiGameData {
+        if data.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        data_inner.allow_guests(allow);
+        data
+    }
+
+    /// Set how the nicknames of online players are kept apart
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_data_set_nickname_policy(
+        data: *mut FfiGameData,
+        policy: FfiNicknamePolicy
+    ) -> *mut FfiGameData {
+        if data.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameData) };
+        data_inner.nickname_policy(match policy {
+            FfiNicknamePolicy::AnyNicknamePolicy => { NicknamePolicy::Any }
+            FfiNicknamePolicy::UniqueNicknamePolicy => { NicknamePolicy::Unique }
+            FfiNicknamePolicy::SuffixNicknamePolicy => { NicknamePolicy::Suffix }
+        });
+        data
+    }
+
     /// Load data archive
     #[unsafe(no_mangle)]
     pub extern "C" fn game_data_load_archive(
@@ -226,11 +658,146 @@ impl FfiGameRuntimeArchive {
         let ffi_player_ref = unsafe { &*ffi_player };
         let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
 
-        data_inner.banned.push(player.account);
+        data_inner.ban(player.account, None);
 
         Box::into_raw(Box::new(FfiGameRuntimeArchive(Box::into_raw(Box::new(data_inner)) as *mut _)))
     }
 
+    /// Add a ban with its reason (Nullable), time and expiry (Seconds since the Unix epoch, 0 if permanent)
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_archive_data_add_ban(
+        data: *mut FfiGameRuntimeArchive,
+        ffi_player: *const FfiPlayer,
+        reason: *const c_char,
+        banned_at: u64,
+        expires_at: u64
+    ) -> *mut FfiGameRuntimeArchive {
+        if data.is_null() || ffi_player.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameRuntimeDataArchive) };
+        let player = Player::try_from(unsafe { &*ffi_player }).unwrap_or_default();
+        let reason = if reason.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(reason) }.to_string_lossy().into_owned())
+        };
+
+        data_inner.bans.retain(|ban| ban.account != player.account);
+        let expires_at = if expires_at == 0 { None } else { Some(expires_at) };
+        data_inner.bans.push(BanRecord { account: player.account, reason, banned_at, expires_at });
+        data
+    }
+
+    /// Put the account id on the allow list
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_archive_data_allow(
+        data: *mut FfiGameRuntimeArchive,
+        id: *const c_char
+    ) -> *mut FfiGameRuntimeArchive {
+        if data.is_null() || id.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameRuntimeDataArchive) };
+        let id = unsafe { CStr::from_ptr(id) }.to_string_lossy().into_owned();
+        data_inner.allow(id);
+        data
+    }
+
+    /// Enable or disable the allow list
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_archive_data_set_allow_list(
+        data: *mut FfiGameRuntimeArchive,
+        enabled: bool
+    ) -> *mut FfiGameRuntimeArchive {
+        if data.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameRuntimeDataArchive) };
+        data_inner.allow_list(enabled);
+        data
+    }
+
+    /// Add a known player with its custom information and seat (-1 if none)
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_archive_data_add_known_player(
+        data: *mut FfiGameRuntimeArchive,
+        ffi_player: *const FfiPlayer,
+        seat: c_int
+    ) -> *mut FfiGameRuntimeArchive {
+        if data.is_null() || ffi_player.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let data_inner = unsafe { &mut *((*data).0 as *mut GameRuntimeDataArchive) };
+        let player = Player::try_from(unsafe { &*ffi_player }).unwrap_or_default();
+
+        data_inner.remember(KnownPlayer {
+            account: player.account,
+            customize: player.customize,
+            seat: u8::try_from(seat).ok()
+        });
+        data
+    }
+
+    /// Get the version of the archive
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_archive_data_get_version(data: *const FfiGameRuntimeArchive) -> u32 {
+        if data.is_null() {
+            return 0;
+        }
+        let data_inner = unsafe { &*((*data).0 as *const GameRuntimeDataArchive) };
+        data_inner.version
+    }
+
+    /// Get the bans of the archive
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_archive_data_get_bans(data: *const FfiGameRuntimeArchive) -> FfiBanList {
+        let mut result: Vec<FfiBanRecord> = vec![];
+        if !data.is_null() {
+            let data_inner = unsafe { &*((*data).0 as *const GameRuntimeDataArchive) };
+            for ban in &data_inner.bans {
+                result.push(FfiBanRecord {
+                    player: FfiPlayer::from(&Player::from(ban.account.clone())),
+                    reason: ban.reason.clone().map(|reason| unsafe { str_rs_to_c(reason) }).unwrap_or(std::ptr::null_mut()),
+                    banned_at: ban.banned_at,
+                    expires_at: ban.expires_at.unwrap_or(0)
+                });
+            }
+        }
+
+        let len = result.len();
+        let cap = result.capacity();
+        let ptr = result.as_mut_ptr();
+        std::mem::forget(result);
+        FfiBanList { bans: ptr, len, cap }
+    }
+
+    /// Get the known players of the archive
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_archive_data_get_known_players(data: *const FfiGameRuntimeArchive) -> FfiKnownPlayerList {
+        let mut result: Vec<FfiKnownPlayer> = vec![];
+        if !data.is_null() {
+            let data_inner = unsafe { &*((*data).0 as *const GameRuntimeDataArchive) };
+            for known in &data_inner.players {
+                let player = Player { account: known.account.clone(), customize: known.customize.clone(), previous_hash: None };
+                result.push(FfiKnownPlayer {
+                    player: FfiPlayer::from(&player),
+                    seat: known.seat.map(c_int::from).unwrap_or(-1)
+                });
+            }
+        }
+
+        let len = result.len();
+        let cap = result.capacity();
+        let ptr = result.as_mut_ptr();
+        std::mem::forget(result);
+        FfiKnownPlayerList { players: ptr, len, cap }
+    }
+
     /// Free data
     #[unsafe(no_mangle)]
     pub extern "C" fn free_game_archive_data(data: *mut FfiGameRuntimeArchive) {
@@ -262,7 +829,16 @@ impl FfiGameRuntime {
         }
     }
 
-    fn operate_game_runtime_with_return<Input, Result>(
+    /// Clone the runtime behind the pointer without locking it
+    pub(crate) fn clone_game_runtime(runtime: *mut FfiGameRuntime) -> Arc<Mutex<GameRuntime>> {
+        unsafe {
+            let ffi_runtime = &*runtime;
+            Arc::increment_strong_count(ffi_runtime.inner);
+            Arc::<Mutex<GameRuntime>>::from_raw(ffi_runtime.inner as *mut _)
+        }
+    }
+
+    pub(crate) fn operate_game_runtime_with_return<Input, Result>(
         runtime: *mut FfiGameRuntime,
         input: Input,
         callback: fn(guard: &mut MutexGuard<GameRuntime>, input: Input) -> Option<Result>
@@ -340,6 +916,251 @@ impl FfiGameRuntime {
         Self::send_message_to(runtime, player, service_type, GameMessage::EventTrigger(key));
     }
 
+    /// Vibrate the controller of the online player, returns false if the player is offline
+    /// The pattern is not freed
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_vibrate(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        pattern: *const FfiHapticPattern
+    ) -> bool {
+        if runtime.is_null() || player.is_null() || pattern.is_null() { return false; }
+
+        let pattern = HapticPattern::from(unsafe { &*pattern });
+        Self::vibrate(runtime, player, pattern)
+    }
+
+    /// Vibrate the controller of the online player with a preset, returns false if the player is offline
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_vibrate_preset(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        preset: FfiHapticPreset
+    ) -> bool {
+        if runtime.is_null() || player.is_null() { return false; }
+
+        Self::vibrate(runtime, player, HapticPattern::Preset((&preset).into()))
+    }
+
+    fn vibrate(runtime: *mut FfiGameRuntime, player: *const FfiPlayer, pattern: HapticPattern) -> bool {
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+
+        Self::operate_game_runtime_with_return(
+            runtime, (player.account, pattern), |guard, (account, pattern)| {
+                Some(guard.vibrate(&account, pattern))
+            }
+        ).unwrap_or(false)
+    }
+
+    /// Send a message to all online members of the team
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_send_message_to_team(
+        runtime: *mut FfiGameRuntime,
+        team: *const c_char,
+        message: *mut FfiGameMessage
+    ) {
+        if runtime.is_null() || team.is_null() || message.is_null() { return; }
+
+        let team_str = unsafe { CStr::from_ptr(team) }.to_string_lossy().into_owned();
+        let msg = unsafe { GameMessage::from(message.read()) };
+
+        Self::operate_game_runtime_with_return(
+            runtime, (team_str, msg),
+            |guard, (team, message)| {
+                guard.send_team_message(&team, message);
+                Some(())
+            }
+        );
+    }
+
+    /// Send a message to all online players, returns the count of players reached
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_broadcast(
+        runtime: *mut FfiGameRuntime,
+        message: *mut FfiGameMessage
+    ) -> usize {
+        if runtime.is_null() || message.is_null() { return 0; }
+
+        let msg = unsafe { GameMessage::from(message.read()) };
+
+        Self::operate_game_runtime_with_return(runtime, msg, |guard, message| {
+            Some(guard.broadcast(message))
+        }).unwrap_or(0)
+    }
+
+    /// Send a message to all online players except the player, returns the count of players reached
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_broadcast_except(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        message: *mut FfiGameMessage
+    ) -> usize {
+        if runtime.is_null() || player.is_null() || message.is_null() { return 0; }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+        let msg = unsafe { GameMessage::from(message.read()) };
+
+        Self::operate_game_runtime_with_return(
+            runtime, (player.account, msg), |guard, (account, message)| {
+                Some(guard.broadcast_except(&account, message))
+            }
+        ).unwrap_or(0)
+    }
+
+    /// Send a message to the players, offline players are skipped
+    /// Returns the count of players reached
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_multicast(
+        runtime: *mut FfiGameRuntime,
+        players: *const FfiPlayer,
+        len: usize,
+        message: *mut FfiGameMessage
+    ) -> usize {
+        if runtime.is_null() || message.is_null() || (len > 0 && players.is_null()) { return 0; }
+
+        let accounts: Vec<_> = if len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(players, len) }.iter()
+                .map(|player| Player::try_from(player).unwrap_or_default().account)
+                .collect()
+        };
+        let msg = unsafe { GameMessage::from(message.read()) };
+
+        Self::operate_game_runtime_with_return(
+            runtime, (accounts, msg), |guard, (accounts, message)| {
+                Some(guard.multicast(&accounts, message))
+            }
+        ).unwrap_or(0)
+    }
+
+    /// Assign a player to a team (Remove from its team if the team is null)
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_assign_team(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        team: *const c_char
+    ) -> bool {
+        if runtime.is_null() || player.is_null() { return false; }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+        let team = if team.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(team) }.to_string_lossy().into_owned())
+        };
+
+        let result = Self::operate_game_runtime_with_return(
+            runtime, (player.account, team),
+            |guard, (account, team)| {
+                Some(guard.assign_team(&account, team))
+            }
+        );
+        result.unwrap_or(false)
+    }
+
+    /// Get team of player (Null if unassigned)
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_player_team(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer
+    ) -> *mut c_char {
+        if runtime.is_null() || player.is_null() { return std::ptr::null_mut(); }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+
+        let team = Self::operate_game_runtime_with_return(
+            runtime, player.account, |guard, account| {
+                guard.data.team_of(&account)
+            }
+        );
+
+        match team {
+            None => { std::ptr::null_mut() }
+            Some(team) => { unsafe { str_rs_to_c(team) } }
+        }
+    }
+
+    /// Get the online player with its current custom information (Null if not online)
+    /// Free it with free_player
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_player_profile(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer
+    ) -> *mut FfiPlayer {
+        if runtime.is_null() || player.is_null() { return std::ptr::null_mut(); }
+
+        let player = Player::try_from(unsafe { &*player }).unwrap_or_default();
+        let profile = Self::operate_game_runtime_with_return(
+            runtime, player.account, |guard, account| {
+                guard.data.is_account_online(&account).then(|| {
+                    let customize = guard.data.customize_of(&account);
+                    Player { account, customize, previous_hash: None }
+                })
+            }
+        );
+
+        match profile {
+            None => { std::ptr::null_mut() }
+            Some(profile) => { Box::into_raw(Box::new(FfiPlayer::from(&profile))) }
+        }
+    }
+
+    /// Replace the custom information of the online player with the one of the player
+    /// Returns false if the player is not online or the custom information is not accepted
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_change_profile(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer
+    ) -> bool {
+        if runtime.is_null() || player.is_null() { return false; }
+
+        let Ok(player) = Player::try_from(unsafe { &*player }) else {
+            return false;
+        };
+        Self::operate_game_runtime_with_return(
+            runtime, player, |guard, player| {
+                Some(guard.change_profile(&player.account, player.customize.unwrap_or_default()))
+            }
+        ).unwrap_or(false)
+    }
+
+    /// Get online members of team
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_team_members(
+        runtime: *mut FfiGameRuntime,
+        team: *const c_char
+    ) -> FfiPlayerList {
+
+        let mut result : Vec<FfiPlayer> = vec![];
+        if !runtime.is_null() && !team.is_null() {
+            let team_str = unsafe { CStr::from_ptr(team) }.to_string_lossy().into_owned();
+            let members = Self::operate_game_runtime_with_return(
+                runtime, team_str, |guard, team| {
+                    Some(guard.data.team_members(&team))
+                }
+            );
+
+            for member in members.unwrap_or_default() {
+                result.push(FfiPlayer::from(&Player::register_from_hash(member.player_hash)));
+            }
+        }
+
+        let len = result.len();
+        let cap = result.capacity();
+        let ptr = result.as_mut_ptr();
+        std::mem::forget(result);
+        FfiPlayerList {
+            players: ptr,
+            len,
+            cap,
+        }
+    }
+
     /// Pop a control event
     #[unsafe(no_mangle)]
     pub extern "C" fn game_runtime_pop_control_event(runtime: *mut FfiGameRuntime) -> *mut FfiControlEvent {
@@ -349,26 +1170,83 @@ impl FfiGameRuntime {
             runtime,
             (), |guard, _| {
                 guard.pop_control_event()
+                    .map(|(account, message)| (guard.data.seat_of(&account), account, message))
             });
 
         match control_event {
             None => { std::ptr::null_mut() }
-            Some((account, message)) => {
+            Some((seat, account, event)) => {
                 let hash = account.player_hash;
-                let player = FfiPlayer::from(&Player::register_from_hash(hash));
+                let mut player = Player::register_from_hash(hash);
+                let (kind, message, reason) = match event {
+                    ControlEvent::Control(message) => { (FfiControlEventKind::ControlInputEvent, message, ExitReason::default()) }
+                    ControlEvent::Joined => { (FfiControlEventKind::PlayerJoinedEvent, ControlMessage::Err, ExitReason::default()) }
+                    ControlEvent::Left(reason) => { (FfiControlEventKind::PlayerLeftEvent, ControlMessage::Err, reason) }
+                    ControlEvent::TimedOut => { (FfiControlEventKind::PlayerTimedOutEvent, ControlMessage::Err, ExitReason::Err) }
+                    ControlEvent::Kicked => { (FfiControlEventKind::PlayerKickedEvent, ControlMessage::Err, ExitReason::YouAreKicked) }
+                    ControlEvent::Banned => { (FfiControlEventKind::PlayerBannedEvent, ControlMessage::Err, ExitReason::YouAreBanned) }
+                    ControlEvent::ProfileUpdated(customize) => {
+                        player.customize = Some(customize);
+                        (FfiControlEventKind::ProfileUpdatedEvent, ControlMessage::Err, ExitReason::default())
+                    }
+                };
+                let player = FfiPlayer::from(&player);
                 let message = FfiControlMessage::from(message);
-                Box::into_raw(Box::new(FfiControlEvent { player, message }))
+                let exit_reason = FfiExitReason::from(&reason);
+                Box::into_raw(Box::new(FfiControlEvent { kind, player, has_seat: seat.is_some(), seat: seat.unwrap_or_default(), message, exit_reason }))
             }
         }
     }
 
-    /// Let player exit
+    /// Let player exit
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_let_exit(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        service_type: FfiServiceType,
+        reason: FfiExitReason
+    ) {
+        if runtime.is_null() || player.is_null() { return; }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+
+        Self::operate_game_runtime_with_return(
+            runtime, (&player.account, ExitReason::from(&reason), ServiceType::from(&service_type)),
+            |guard, (account, reason, service)| {
+                guard.let_account_exit(account, reason, service);
+                Some(())
+            }
+        );
+    }
+
+    /// Kick a player
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_kick_player(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        service_type: FfiServiceType
+    ) {
+        if runtime.is_null() || player.is_null() { return; }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+
+        Self::operate_game_runtime_with_return(
+            runtime, (&player, ServiceType::from(&service_type)),
+            |guard, (player, service)| {
+                guard.kick_player(player, service);
+                Some(())
+            }
+        );
+    }
+
+    /// Ban a player (And kick)
     #[unsafe(no_mangle)]
-    pub extern "C" fn game_runtime_let_exit(
+    pub extern "C" fn game_runtime_ban_player(
         runtime: *mut FfiGameRuntime,
         player: *const FfiPlayer,
-        service_type: FfiServiceType,
-        reason: FfiExitReason
+        service_type: FfiServiceType
     ) {
         if runtime.is_null() || player.is_null() { return; }
 
@@ -376,51 +1254,59 @@ impl FfiGameRuntime {
         let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
 
         Self::operate_game_runtime_with_return(
-            runtime, (&player.account, ExitReason::from(&reason), ServiceType::from(&service_type)),
-            |guard, (account, reason, service)| {
-                guard.let_account_exit(account, reason, service);
+            runtime, (&player, ServiceType::from(&service_type)),
+            |guard, (player, service)| {
+                guard.ban_player(player, service);
                 Some(())
             }
         );
     }
 
-    /// Kick a player
+    /// Kick a player, it can't rejoin before the cooldown ends (Seconds, 0 for none)
     #[unsafe(no_mangle)]
-    pub extern "C" fn game_runtime_kick_player(
+    pub extern "C" fn game_runtime_kick_player_with_cooldown(
         runtime: *mut FfiGameRuntime,
         player: *const FfiPlayer,
+        cooldown_secs: u64,
         service_type: FfiServiceType
     ) {
         if runtime.is_null() || player.is_null() { return; }
 
-        let ffi_player_ref = unsafe { &*player };
-        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+        let player = Player::try_from(unsafe { &*player }).unwrap_or_default();
+        let cooldown = if cooldown_secs == 0 { None } else { Some(Duration::from_secs(cooldown_secs)) };
 
         Self::operate_game_runtime_with_return(
-            runtime, (&player, ServiceType::from(&service_type)),
-            |guard, (player, service)| {
-                guard.kick_player(player, service);
+            runtime, (&player, cooldown, ServiceType::from(&service_type)),
+            |guard, (player, cooldown, service)| {
+                guard.kick_player_with_cooldown(player, cooldown, service);
                 Some(())
             }
         );
     }
 
-    /// Ban a player (And kick)
+    /// Ban a player (And kick) with the reason (Nullable) for the duration (Seconds, 0 if permanent)
     #[unsafe(no_mangle)]
-    pub extern "C" fn game_runtime_ban_player(
+    pub extern "C" fn game_runtime_ban_player_for(
         runtime: *mut FfiGameRuntime,
         player: *const FfiPlayer,
+        reason: *const c_char,
+        duration_secs: u64,
         service_type: FfiServiceType
     ) {
         if runtime.is_null() || player.is_null() { return; }
 
-        let ffi_player_ref = unsafe { &*player };
-        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+        let player = Player::try_from(unsafe { &*player }).unwrap_or_default();
+        let reason = if reason.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(reason) }.to_string_lossy().into_owned())
+        };
+        let duration = if duration_secs == 0 { None } else { Some(Duration::from_secs(duration_secs)) };
 
         Self::operate_game_runtime_with_return(
-            runtime, (&player, ServiceType::from(&service_type)),
-            |guard, (player, service)| {
-                guard.ban_player(player, service);
+            runtime, (&player, reason, duration, ServiceType::from(&service_type)),
+            |guard, (player, reason, duration, service)| {
+                guard.ban_player_for(player, reason, duration, service);
                 Some(())
             }
         );
@@ -485,6 +1371,102 @@ impl FfiGameRuntime {
         result.unwrap_or(false)
     }
 
+    /// Let only the accounts on the allow list join
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_enable_allow_list(runtime: *mut FfiGameRuntime) {
+        if runtime.is_null() { return; }
+        Self::operate_game_runtime(runtime, |guard| {
+            guard.enable_allow_list();
+        })
+    }
+
+    /// Let every account join again
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_disable_allow_list(runtime: *mut FfiGameRuntime) {
+        if runtime.is_null() { return; }
+        Self::operate_game_runtime(runtime, |guard| {
+            guard.disable_allow_list();
+        })
+    }
+
+    /// Get allow list status
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_is_allow_list_enabled(runtime: *mut FfiGameRuntime) -> bool {
+        if runtime.is_null() { return false; }
+        Self::operate_game_runtime_with_return(runtime, (), |guard, _| {
+            Some(guard.is_allow_list_enabled())
+        }).unwrap_or(false)
+    }
+
+    /// Let guests join the game
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_allow_guests(runtime: *mut FfiGameRuntime) {
+        if runtime.is_null() { return; }
+        Self::operate_game_runtime(runtime, |guard| {
+            guard.allow_guests();
+        })
+    }
+
+    /// Stop guests from joining the game
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_deny_guests(runtime: *mut FfiGameRuntime) {
+        if runtime.is_null() { return; }
+        Self::operate_game_runtime(runtime, |guard| {
+            guard.deny_guests();
+        })
+    }
+
+    /// Check if guests can join the game
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_are_guests_allowed(runtime: *mut FfiGameRuntime) -> bool {
+        if runtime.is_null() { return false; }
+        Self::operate_game_runtime_with_return(runtime, (), |guard, _| {
+            Some(guard.are_guests_allowed())
+        }).unwrap_or(false)
+    }
+
+    /// Put the account id on the allow list
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_allow_account(runtime: *mut FfiGameRuntime, id: *const c_char) {
+        if runtime.is_null() || id.is_null() { return; }
+        let id = unsafe { CStr::from_ptr(id) }.to_string_lossy().into_owned();
+        Self::operate_game_runtime_with_return(runtime, id, |guard, id| {
+            guard.allow_account(id);
+            Some(())
+        });
+    }
+
+    /// Take the account id off the allow list
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_disallow_account(runtime: *mut FfiGameRuntime, id: *const c_char) {
+        if runtime.is_null() || id.is_null() { return; }
+        let id = unsafe { CStr::from_ptr(id) }.to_string_lossy().into_owned();
+        Self::operate_game_runtime_with_return(runtime, id, |guard, id| {
+            guard.disallow_account(id);
+            Some(())
+        });
+    }
+
+    /// Check if the player can send admin commands
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_is_admin(runtime: *mut FfiGameRuntime, player: *const FfiPlayer) -> bool {
+        if runtime.is_null() || player.is_null() { return false; }
+        let player = Player::try_from(unsafe { &*player }).unwrap_or_default();
+        Self::operate_game_runtime_with_return(runtime, player.account, |guard, account| {
+            Some(guard.data.is_admin(&account))
+        }).unwrap_or(false)
+    }
+
+    /// Check if the player can pass the allow list, every player can while it is disabled
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_is_account_allowed(runtime: *mut FfiGameRuntime, player: *const FfiPlayer) -> bool {
+        if runtime.is_null() || player.is_null() { return false; }
+        let player = Player::try_from(unsafe { &*player }).unwrap_or_default();
+        Self::operate_game_runtime_with_return(runtime, player.account, |guard, account| {
+            Some(guard.data.is_account_allowed(&account))
+        }).unwrap_or(false)
+    }
+
     /// Get button status of player
     #[unsafe(no_mangle)]
     pub extern "C" fn game_runtime_get_button_status(
@@ -511,6 +1493,39 @@ impl FfiGameRuntime {
         }
     }
 
+    /// Count the entries of the next snapshot, used to allocate the buffer
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_snapshot_len(runtime: *mut FfiGameRuntime) -> usize {
+        if runtime.is_null() { return 0; }
+
+        Self::operate_game_runtime_with_return(runtime, (), |guard, _| {
+            Some(FfiInputEntry::collect(guard.peek_snapshot()).len())
+        }).unwrap_or(0)
+    }
+
+    /// Take a snapshot of all online players' inputs and begin a new frame
+    /// Fills at most capacity entries into the buffer, returns the count of entries of the snapshot
+    /// Entries beyond the capacity are dropped, use game_runtime_snapshot_len to size the buffer
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_snapshot(
+        runtime: *mut FfiGameRuntime,
+        buffer: *mut FfiInputEntry,
+        capacity: usize
+    ) -> usize {
+        if runtime.is_null() { return 0; }
+
+        let entries = Self::operate_game_runtime_with_return(runtime, (), |guard, _| {
+            Some(FfiInputEntry::collect(guard.snapshot()))
+        }).unwrap_or_default();
+
+        if !buffer.is_null() {
+            for (i, entry) in entries.iter().take(capacity).enumerate() {
+                unsafe { buffer.add(i).write(entry.clone()) };
+            }
+        }
+        entries.len()
+    }
+
     /// Get axis value of player
     #[unsafe(no_mangle)]
     pub extern "C" fn game_runtime_get_axis(
@@ -563,6 +1578,187 @@ impl FfiGameRuntime {
         }
     }
 
+    /// Get the fingers of player on the touchpad, ordered by touch ids
+    /// Fills at most capacity points into the buffer, returns the count of fingers
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_touches(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        key: u8,
+        buffer: *mut FfiTouchPoint,
+        capacity: usize
+    ) -> usize {
+        if runtime.is_null() || player.is_null() { return 0; }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+
+        let points = Self::operate_game_runtime_with_return(
+            runtime, (player.account, key), |guard, (account, key)| {
+                Some(guard.control.get_touches(&account, &key))
+            }
+        ).unwrap_or_default();
+
+        if !buffer.is_null() {
+            for (i, point) in points.iter().take(capacity).enumerate() {
+                unsafe { buffer.add(i).write(FfiTouchPoint::from(point)) };
+            }
+        }
+        points.len()
+    }
+
+    /// Get the latest motion sample of player
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_motion(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer
+    ) -> FfiMotion {
+        let not_found = FfiMotion { found: false, motion: FfiMotionData::from(&MotionData::default()) };
+        if runtime.is_null() || player.is_null() { return not_found; }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+
+        let motion = Self::operate_game_runtime_with_return(
+            runtime, player.account, |guard, account| {
+                guard.control.get_motion(&account)
+            }
+        );
+
+        match motion {
+            None => { not_found }
+            Some(motion) => { FfiMotion { found: true, motion: FfiMotionData::from(&motion) } }
+        }
+    }
+
+    /// Ask the controller of the online player to send motion samples at the rate (Hz)
+    /// Returns false if the player is offline or motion sensors are not enabled
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_request_sample_rate(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        sample_rate: u16
+    ) -> bool {
+        if runtime.is_null() || player.is_null() { return false; }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+
+        Self::operate_game_runtime_with_return(
+            runtime, (player.account, sample_rate), |guard, (account, sample_rate)| {
+                Some(guard.request_sample_rate(&account, sample_rate))
+            }
+        ).unwrap_or(false)
+    }
+
+    /// Ask the online player to type a text, the id of the prompt is ignored
+    /// Returns the request id (0 if the player is offline), the prompt never times out if the timeout is 0
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_prompt(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        prompt: *const FfiTextPrompt,
+        timeout_ms: u64
+    ) -> u32 {
+        if runtime.is_null() || player.is_null() || prompt.is_null() { return 0; }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+        let prompt = TextPrompt::from(unsafe { &*prompt });
+        let timeout = if timeout_ms == 0 { None } else { Some(Duration::from_millis(timeout_ms)) };
+
+        Self::operate_game_runtime_with_return(
+            runtime, (player.account, prompt, timeout), |guard, (account, prompt, timeout)| {
+                guard.prompt(&account, prompt, timeout)
+            }
+        ).unwrap_or(0)
+    }
+
+    /// Cancel the pending prompt, returns false if it is not pending
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_cancel_prompt(
+        runtime: *mut FfiGameRuntime,
+        id: u32
+    ) -> bool {
+        if runtime.is_null() { return false; }
+
+        Self::operate_game_runtime_with_return(runtime, id, |guard, id| {
+            Some(guard.cancel_prompt(id))
+        }).unwrap_or(false)
+    }
+
+    /// Take the result of the prompt, the kind is PromptPending while no result is available
+    /// Free it with free_prompt_result
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_take_prompt_result(
+        runtime: *mut FfiGameRuntime,
+        id: u32
+    ) -> *mut FfiPromptResult {
+        if runtime.is_null() { return std::ptr::null_mut(); }
+
+        let result = Self::operate_game_runtime_with_return(runtime, id, |guard, id| {
+            guard.take_prompt_result(id)
+        });
+        Box::into_raw(Box::new(FfiPromptResult::from(result)))
+    }
+
+    /// Get button status of player by the key name
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_button_status_by_name(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        name: *const c_char
+    ) -> FfiButtonStatus {
+        let not_found = FfiButtonStatus { found: false, pressed: false, released: false };
+        if runtime.is_null() || player.is_null() || name.is_null() { return not_found; }
+
+        match Self::find_key(runtime, KeyKind::Button, name) {
+            None => { not_found }
+            Some(key) => { Self::game_runtime_get_button_status(runtime, player, key) }
+        }
+    }
+
+    /// Get axis value of player by the key name
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_axis_by_name(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        name: *const c_char
+    ) -> FfiAxis {
+        let not_found = FfiAxis { found: false, axis: 0.0 };
+        if runtime.is_null() || player.is_null() || name.is_null() { return not_found; }
+
+        match Self::find_key(runtime, KeyKind::Axis, name) {
+            None => { not_found }
+            Some(key) => { Self::game_runtime_get_axis(runtime, player, key) }
+        }
+    }
+
+    /// Get direction value of player by the key name
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_direction_by_name(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer,
+        name: *const c_char
+    ) -> FfiDirection {
+        let not_found = FfiDirection { found: false, x: 0.0, y: 0.0 };
+        if runtime.is_null() || player.is_null() || name.is_null() { return not_found; }
+
+        match Self::find_key(runtime, KeyKind::Direction, name) {
+            None => { not_found }
+            Some(key) => { Self::game_runtime_get_direction(runtime, player, key) }
+        }
+    }
+
+    fn find_key(runtime: *mut FfiGameRuntime, kind: KeyKind, name: *const c_char) -> Option<u8> {
+        let name = unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned();
+        Self::operate_game_runtime_with_return(
+            runtime, (kind, name), |guard, (kind, name)| {
+                guard.control.keys().find_key(kind, &name)
+            }
+        )
+    }
+
     /// Get service type of player
     #[unsafe(no_mangle)]
     pub extern "C" fn game_runtime_get_service_type(
@@ -638,6 +1834,53 @@ impl FfiGameRuntime {
         }
     }
 
+    /// Get seat of player
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_player_seat(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer
+    ) -> FfiSeat {
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+        let account = player.account;
+
+        let seat = Self::operate_game_runtime_with_return(
+            runtime, account, |guard, account| {
+                guard.data.seat_of(&account)
+            }
+        );
+
+        match seat {
+            None => { FfiSeat { found: false, seat: 0 } }
+            Some(seat) => {
+                FfiSeat { found: true, seat }
+            }
+        }
+    }
+
+    /// Get the player sitting at the seat (Null if the seat is empty)
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_player_at_seat(
+        runtime: *mut FfiGameRuntime,
+        seat: u8
+    ) -> *mut FfiPlayer {
+        if runtime.is_null() { return std::ptr::null_mut(); }
+
+        let account = Self::operate_game_runtime_with_return(
+            runtime, seat, |guard, seat| {
+                guard.data.account_at_seat(seat)
+            }
+        );
+
+        match account {
+            None => { std::ptr::null_mut() }
+            Some(account) => {
+                Box::into_raw(Box::new(FfiPlayer::from(&Player::register_from_hash(account.player_hash))))
+            }
+        }
+    }
+
     /// Get online list
     #[unsafe(no_mangle)]
     pub extern "C" fn game_runtime_get_online_list(runtime: *mut FfiGameRuntime) -> FfiPlayerList {
@@ -665,6 +1908,76 @@ impl FfiGameRuntime {
         }
     }
 
+    /// Archive the runtime data, the runtime keeps running
+    /// Free it with free_game_archive_data
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_archive(runtime: *mut FfiGameRuntime) -> *mut FfiGameRuntimeArchive {
+        if runtime.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let archive = Self::operate_game_runtime_with_return(runtime, (), |guard, _| {
+            Some(guard.data.archive())
+        }).unwrap_or_default();
+        Box::into_raw(Box::new(FfiGameRuntimeArchive(Box::into_raw(Box::new(archive)) as *mut _)))
+    }
+
+    /// Get the statistics of the player
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_stats(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer
+    ) -> FfiPlayerStats {
+        let stats = Self::stats_of(runtime, player);
+        FfiPlayerStats {
+            found: stats.is_some(),
+            session_count: stats.as_ref().map(PlayerStats::session_count).unwrap_or(0),
+            total_play_time: stats.as_ref().map(PlayerStats::total_play_time).unwrap_or(0),
+            total_inputs: stats.as_ref().map(PlayerStats::total_inputs).unwrap_or(0),
+            total_presses: stats.as_ref().map(PlayerStats::total_presses).unwrap_or(0),
+            first_joined_at: stats.as_ref().and_then(PlayerStats::first_joined_at).unwrap_or(0),
+            last_seen_at: stats.as_ref().and_then(PlayerStats::last_seen_at).unwrap_or(0),
+        }
+    }
+
+    /// Get the session history of the player, ordered by join time
+    /// Free it with free_session_list
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_get_sessions(
+        runtime: *mut FfiGameRuntime,
+        player: *const FfiPlayer
+    ) -> FfiSessionList {
+        let mut result: Vec<FfiSession> = vec![];
+        if let Some(stats) = Self::stats_of(runtime, player) {
+            for session in stats.sessions {
+                result.push(FfiSession {
+                    joined_at: session.joined_at,
+                    left_at: session.left_at.unwrap_or(0),
+                    open: session.is_open(),
+                    exit_reason: FfiExitReason::from(&session.exit_reason.unwrap_or(ExitReason::Exit)),
+                    inputs: session.inputs,
+                    presses: session.presses,
+                });
+            }
+        }
+
+        let len = result.len();
+        let cap = result.capacity();
+        let ptr = result.as_mut_ptr();
+        std::mem::forget(result);
+        FfiSessionList { sessions: ptr, len, cap }
+    }
+
+    fn stats_of(runtime: *mut FfiGameRuntime, player: *const FfiPlayer) -> Option<PlayerStats> {
+        if runtime.is_null() || player.is_null() { return None; }
+
+        let ffi_player_ref = unsafe { &*player };
+        let player = Player::try_from(&*ffi_player_ref).unwrap_or_default();
+        Self::operate_game_runtime_with_return(runtime, player.account, |guard, account| {
+            guard.data.stats_of(&account)
+        })
+    }
+
     /// Get banned list
     #[unsafe(no_mangle)]
     pub extern "C" fn game_runtime_get_banned_list(runtime: *mut FfiGameRuntime) -> FfiPlayerList {
@@ -724,6 +2037,49 @@ impl FfiControlEvent {
     }
 }
 
+impl FfiInputEntry {
+    fn collect(snapshot: InputSnapshot) -> Vec<FfiInputEntry> {
+        let mut entries = Vec::new();
+        for player in snapshot.players {
+            let (has_seat, seat) = (player.seat.is_some(), player.seat.unwrap_or_default());
+            for button in player.buttons {
+                entries.push(FfiInputEntry {
+                    has_seat, seat, kind: FfiInputKind::ButtonInput, key: button.key,
+                    pressed: button.pressed,
+                    just_pressed: button.just_pressed,
+                    just_released: button.just_released,
+                    press_count: button.press_count,
+                    x: 0.0, y: 0.0, touch_id: 0, pressure: 0.0
+                });
+            }
+            for (key, axis) in player.axes {
+                entries.push(FfiInputEntry {
+                    has_seat, seat, kind: FfiInputKind::AxisInput, key,
+                    pressed: false, just_pressed: false, just_released: false, press_count: 0,
+                    x: axis, y: 0.0, touch_id: 0, pressure: 0.0
+                });
+            }
+            for (key, (x, y)) in player.directions {
+                entries.push(FfiInputEntry {
+                    has_seat, seat, kind: FfiInputKind::DirectionInput, key,
+                    pressed: false, just_pressed: false, just_released: false, press_count: 0,
+                    x, y, touch_id: 0, pressure: 0.0
+                });
+            }
+            for (key, points) in player.touches {
+                for point in points {
+                    entries.push(FfiInputEntry {
+                        has_seat, seat, kind: FfiInputKind::TouchInput, key,
+                        pressed: true, just_pressed: false, just_released: false, press_count: 0,
+                        x: point.x, y: point.y, touch_id: point.id, pressure: point.pressure
+                    });
+                }
+            }
+        }
+        entries
+    }
+}
+
 impl FfiPlayerList {
 
     /// Free player list
@@ -739,4 +2095,43 @@ impl FfiPlayerList {
             };
         }
     }
+}
+
+impl FfiSessionList {
+
+    /// Free session list
+    #[unsafe(no_mangle)]
+    pub extern "C" fn free_session_list(list: FfiSessionList) {
+        if list.sessions.is_null() { return; }
+        drop(unsafe { Vec::from_raw_parts(list.sessions, list.len, list.cap) });
+    }
+}
+
+impl FfiBanList {
+
+    /// Free ban list
+    #[unsafe(no_mangle)]
+    pub extern "C" fn free_ban_list(list: FfiBanList) {
+        if list.bans.is_null() { return; }
+        let bans = unsafe { Vec::from_raw_parts(list.bans, list.len, list.cap) };
+        for ban in bans {
+            if !ban.reason.is_null() {
+                drop(unsafe { CString::from_raw(ban.reason) });
+            }
+            free_player(Box::into_raw(Box::new(ban.player)));
+        }
+    }
+}
+
+impl FfiKnownPlayerList {
+
+    /// Free known player list
+    #[unsafe(no_mangle)]
+    pub extern "C" fn free_known_player_list(list: FfiKnownPlayerList) {
+        if list.players.is_null() { return; }
+        let players = unsafe { Vec::from_raw_parts(list.players, list.len, list.cap) };
+        for known in players {
+            free_player(Box::into_raw(Box::new(known.player)));
+        }
+    }
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
+use crate::data::ngpd_game::FfiKeyKind;
+use nogamepads_core::data::game::game_data::{GameControlData, KeyData};
 use std::collections::HashMap;
-use std::ffi::{c_char, CStr, CString};
+use std::ffi::{c_char, c_double, CStr, CString};
 
 #[repr(C)]
 pub struct KeyValuePair {
@@ -14,6 +16,38 @@ pub struct FfiGameInfo {
     cap: usize,
 }
 
+#[repr(C)]
+pub struct FfiRoomInfo {
+    name: *mut c_char,
+    info: FfiGameInfo,
+}
+
+#[repr(C)]
+pub struct FfiRoomList {
+    data: *mut FfiRoomInfo,
+    len: usize,
+    cap: usize,
+}
+
+#[repr(C)]
+pub struct FfiKeyInfo {
+    key: u8,
+    kind: FfiKeyKind,
+    name: *mut c_char,
+    min: c_double,
+    max: c_double,
+    default_value: c_double,
+    /// Description of the key (Null if not described)
+    description: *mut c_char,
+}
+
+#[repr(C)]
+pub struct FfiKeyList {
+    data: *mut FfiKeyInfo,
+    len: usize,
+    cap: usize,
+}
+
 impl From<&HashMap<String, String>> for FfiGameInfo {
     fn from(map: &HashMap<String, String>) -> Self {
         let mut kv_pairs: Vec<KeyValuePair> = map
@@ -95,6 +129,187 @@ impl From<&FfiGameInfo> for Option<HashMap<String, String>> {
     }
 }
 
+impl From<&HashMap<String, HashMap<String, String>>> for FfiRoomList {
+    fn from(rooms: &HashMap<String, HashMap<String, String>>) -> Self {
+        let mut room_infos: Vec<FfiRoomInfo> = rooms
+            .iter()
+            .map(|(name, info)| {
+                FfiRoomInfo {
+                    name: CString::new(name.clone()).unwrap().into_raw(),
+                    info: FfiGameInfo::from(info),
+                }
+            })
+            .collect();
+
+        let cap = room_infos.capacity();
+        let len = room_infos.len();
+        let data_ptr = room_infos.as_mut_ptr();
+
+        std::mem::forget(room_infos);
+
+        FfiRoomList {
+            data: data_ptr,
+            len,
+            cap,
+        }
+    }
+}
+
+impl TryFrom<&FfiRoomList> for HashMap<String, HashMap<String, String>> {
+    type Error = String;
+
+    fn try_from(ffi_rooms: &FfiRoomList) -> Result<Self, Self::Error> {
+        if ffi_rooms.len == 0 {
+            return Ok(HashMap::new());
+        }
+
+        if ffi_rooms.data.is_null() {
+            return Err("FfiRoomList data pointer is null".to_string());
+        }
+
+        let room_slice = unsafe {
+            std::slice::from_raw_parts(ffi_rooms.data, ffi_rooms.len)
+        };
+
+        let mut rooms = HashMap::with_capacity(ffi_rooms.len);
+
+        for (i, room) in room_slice.iter().enumerate() {
+            if room.name.is_null() {
+                return Err(format!("Room name pointer is null at index {}", i));
+            }
+
+            let name_str = unsafe {
+                CStr::from_ptr(room.name)
+                    .to_str()
+                    .map_err(|_| "Invalid UTF-8 in room name")?
+            };
+
+            rooms.insert(name_str.to_owned(), HashMap::try_from(&room.info)?);
+        }
+
+        Ok(rooms)
+    }
+}
+
+impl From<&GameControlData> for FfiKeyList {
+    fn from(keys: &GameControlData) -> Self {
+        let mut key_infos: Vec<FfiKeyInfo> = keys.keys
+            .iter()
+            .map(|(key, data)| {
+                FfiKeyInfo {
+                    key: *key,
+                    kind: (&data.kind).into(),
+                    name: CString::new(data.name.clone()).unwrap().into_raw(),
+                    min: data.min,
+                    max: data.max,
+                    default_value: data.default,
+                    description: match &data.description {
+                        None => { std::ptr::null_mut() }
+                        Some(description) => { CString::new(description.clone()).unwrap().into_raw() }
+                    },
+                }
+            })
+            .collect();
+
+        key_infos.sort_by_key(|info| info.key);
+
+        let cap = key_infos.capacity();
+        let len = key_infos.len();
+        let data_ptr = key_infos.as_mut_ptr();
+
+        std::mem::forget(key_infos);
+
+        FfiKeyList {
+            data: data_ptr,
+            len,
+            cap,
+        }
+    }
+}
+
+impl TryFrom<&FfiKeyList> for GameControlData {
+    type Error = String;
+
+    fn try_from(ffi_keys: &FfiKeyList) -> Result<Self, Self::Error> {
+        if ffi_keys.len == 0 {
+            return Ok(GameControlData::default());
+        }
+
+        if ffi_keys.data.is_null() {
+            return Err("FfiKeyList data pointer is null".to_string());
+        }
+
+        let key_slice = unsafe {
+            std::slice::from_raw_parts(ffi_keys.data, ffi_keys.len)
+        };
+
+        let mut keys = GameControlData::default();
+
+        for (i, info) in key_slice.iter().enumerate() {
+            if info.name.is_null() {
+                return Err(format!("Key name pointer is null at index {}", i));
+            }
+
+            let name_str = unsafe {
+                CStr::from_ptr(info.name)
+                    .to_str()
+                    .map_err(|_| "Invalid UTF-8 in key name")?
+            };
+
+            let mut data = KeyData::new((&info.kind).into(), name_str.to_owned());
+            data.min = info.min;
+            data.max = info.max;
+            data.default = info.default_value;
+            if !info.description.is_null() {
+                let description = unsafe {
+                    CStr::from_ptr(info.description)
+                        .to_str()
+                        .map_err(|_| "Invalid UTF-8 in key description")?
+                };
+                data.description = Some(description.to_owned());
+            }
+
+            keys.keys.insert(info.key, data);
+        }
+
+        Ok(keys)
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn free_key_list(list: FfiKeyList) {
+    if list.data.is_null() {
+        return;
+    }
+
+    let key_infos = unsafe { Vec::from_raw_parts(list.data, list.len, list.cap) };
+
+    for info in key_infos {
+        unsafe {
+            let _ = CString::from_raw(info.name);
+            if !info.description.is_null() {
+                let _ = CString::from_raw(info.description);
+            }
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn free_room_list(list: FfiRoomList) {
+    if list.data.is_null() {
+        return;
+    }
+
+    let room_infos = unsafe { Vec::from_raw_parts(list.data, list.len, list.cap) };
+
+    for room in room_infos {
+        unsafe {
+            let _ = CString::from_raw(room.name);
+        }
+        free_game_info(room.info);
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn free_game_info(map: FfiGameInfo) {
     let kv_pairs = unsafe { Vec::from_raw_parts(map.data, map.len, map.cap) };
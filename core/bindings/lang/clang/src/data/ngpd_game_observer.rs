@@ -0,0 +1,256 @@
+use crate::data::ngpd_game::FfiGameRuntime;
+use crate::data::ngpd_message::FfiTouchData;
+use crate::data::ngpd_player::{free_player, FfiPlayer};
+use nogamepads_core::data::game::game_runtime::GameRuntime;
+use nogamepads_core::data::player::player_data::{Account, Customize, Player};
+use std::ffi::{c_char, c_double, c_void, CString};
+
+/// Callback of button events: (player, key, user_data)
+pub type FfiKeyCallback = extern "C" fn(*const FfiPlayer, u8, *mut c_void);
+
+/// Callback of axis events: (player, key, axis, user_data)
+pub type FfiAxisCallback = extern "C" fn(*const FfiPlayer, u8, c_double, *mut c_void);
+
+/// Callback of direction events: (player, key, x, y, user_data)
+pub type FfiDirectionCallback = extern "C" fn(*const FfiPlayer, u8, c_double, c_double, *mut c_void);
+
+/// Callback of touch events: (player, key, touch, user_data)
+pub type FfiTouchCallback = extern "C" fn(*const FfiPlayer, u8, FfiTouchData, *mut c_void);
+
+/// Callback of plain messages: (player, message, user_data)
+pub type FfiMessageCallback = extern "C" fn(*const FfiPlayer, *const c_char, *mut c_void);
+
+/// Callback of player events: (player, user_data)
+pub type FfiPlayerCallback = extern "C" fn(*const FfiPlayer, *mut c_void);
+
+/// User data passed back to the callbacks untouched
+struct FfiUserData(*mut c_void);
+
+// The pointer is only handed back to the caller, which is responsible for its thread safety
+unsafe impl Send for FfiUserData {}
+
+impl FfiUserData {
+    fn get(&self) -> *mut c_void {
+        self.0
+    }
+}
+
+/// Call the callback with a temporary player, the player is freed after the call
+fn with_player(account: &Account, callback: impl FnOnce(*const FfiPlayer)) {
+    with_profile(account, None, callback);
+}
+
+/// Call the callback with a temporary player carrying the custom information, the player is freed after the call
+fn with_profile(account: &Account, customize: Option<&Customize>, callback: impl FnOnce(*const FfiPlayer)) {
+    let player = Player { account: account.clone(), customize: customize.cloned(), previous_hash: None };
+    let player = Box::into_raw(Box::new(FfiPlayer::from(&player)));
+    callback(player);
+    free_player(player);
+}
+
+impl FfiGameRuntime {
+
+    /// Register a callback invoked when a button is pressed
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_on_pressed(
+        runtime: *mut FfiGameRuntime,
+        callback: FfiKeyCallback,
+        user_data: *mut c_void
+    ) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(
+            runtime, (callback, FfiUserData(user_data)),
+            |guard, (callback, user_data)| {
+                guard.observer().on_pressed(move |account, key| {
+                    with_player(account, |player| callback(player, key, user_data.get()));
+                });
+                Some(())
+            }
+        );
+    }
+
+    /// Register a callback invoked when a button is released
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_on_released(
+        runtime: *mut FfiGameRuntime,
+        callback: FfiKeyCallback,
+        user_data: *mut c_void
+    ) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(
+            runtime, (callback, FfiUserData(user_data)),
+            |guard, (callback, user_data)| {
+                guard.observer().on_released(move |account, key| {
+                    with_player(account, |player| callback(player, key, user_data.get()));
+                });
+                Some(())
+            }
+        );
+    }
+
+    /// Register a callback invoked when the value of an axis is changed
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_on_axis_changed(
+        runtime: *mut FfiGameRuntime,
+        callback: FfiAxisCallback,
+        user_data: *mut c_void
+    ) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(
+            runtime, (callback, FfiUserData(user_data)),
+            |guard, (callback, user_data)| {
+                guard.observer().on_axis_changed(move |account, key, axis| {
+                    with_player(account, |player| callback(player, key, axis, user_data.get()));
+                });
+                Some(())
+            }
+        );
+    }
+
+    /// Register a callback invoked when the value of a direction is changed
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_on_direction_changed(
+        runtime: *mut FfiGameRuntime,
+        callback: FfiDirectionCallback,
+        user_data: *mut c_void
+    ) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(
+            runtime, (callback, FfiUserData(user_data)),
+            |guard, (callback, user_data)| {
+                guard.observer().on_direction_changed(move |account, key, (x, y)| {
+                    with_player(account, |player| callback(player, key, x, y, user_data.get()));
+                });
+                Some(())
+            }
+        );
+    }
+
+    /// Register a callback invoked when a finger touched, moved on or left a touchpad
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_on_touch(
+        runtime: *mut FfiGameRuntime,
+        callback: FfiTouchCallback,
+        user_data: *mut c_void
+    ) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(
+            runtime, (callback, FfiUserData(user_data)),
+            |guard, (callback, user_data)| {
+                guard.observer().on_touch(move |account, key, touch| {
+                    let touch = FfiTouchData::from(touch);
+                    with_player(account, |player| callback(player, key, touch, user_data.get()));
+                });
+                Some(())
+            }
+        );
+    }
+
+    /// Register a callback invoked when a player sends a plain message
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_on_message(
+        runtime: *mut FfiGameRuntime,
+        callback: FfiMessageCallback,
+        user_data: *mut c_void
+    ) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(
+            runtime, (callback, FfiUserData(user_data)),
+            |guard, (callback, user_data)| {
+                guard.observer().on_message(move |account, msg| {
+                    let msg = CString::new(msg).unwrap_or_default();
+                    with_player(account, |player| callback(player, msg.as_ptr(), user_data.get()));
+                });
+                Some(())
+            }
+        );
+    }
+
+    /// Register a callback invoked when a player joined the game
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_on_player_joined(
+        runtime: *mut FfiGameRuntime,
+        callback: FfiPlayerCallback,
+        user_data: *mut c_void
+    ) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(
+            runtime, (callback, FfiUserData(user_data)),
+            |guard, (callback, user_data)| {
+                guard.observer().on_player_joined(move |account| {
+                    with_player(account, |player| callback(player, user_data.get()));
+                });
+                Some(())
+            }
+        );
+    }
+
+    /// Register a callback invoked when a player left the game
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_on_player_left(
+        runtime: *mut FfiGameRuntime,
+        callback: FfiPlayerCallback,
+        user_data: *mut c_void
+    ) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(
+            runtime, (callback, FfiUserData(user_data)),
+            |guard, (callback, user_data)| {
+                guard.observer().on_player_left(move |account| {
+                    with_player(account, |player| callback(player, user_data.get()));
+                });
+                Some(())
+            }
+        );
+    }
+
+    /// Register a callback invoked when a player changed its custom information
+    /// The player passed to the callback carries the profile accepted by the game
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_on_profile_updated(
+        runtime: *mut FfiGameRuntime,
+        callback: FfiPlayerCallback,
+        user_data: *mut c_void
+    ) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(
+            runtime, (callback, FfiUserData(user_data)),
+            |guard, (callback, user_data)| {
+                guard.observer().on_profile_updated(move |account, customize| {
+                    with_profile(account, Some(customize), |player| callback(player, user_data.get()));
+                });
+                Some(())
+            }
+        );
+    }
+
+    /// Remove all registered callbacks
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_clear_callbacks(runtime: *mut FfiGameRuntime) {
+        if runtime.is_null() { return; }
+
+        Self::operate_game_runtime_with_return(runtime, (), |guard, _| {
+            guard.observer().clear();
+            Some(())
+        });
+    }
+
+    /// Invoke the registered callbacks for all events received since the last dispatch
+    /// The runtime is unlocked while the callbacks run, so they may call other runtime functions
+    /// Returns the count of dispatched events
+    #[unsafe(no_mangle)]
+    pub extern "C" fn game_runtime_dispatch_events(runtime: *mut FfiGameRuntime) -> usize {
+        if runtime.is_null() { return 0; }
+
+        GameRuntime::dispatch_events(&Self::clone_game_runtime(runtime))
+    }
+}
\ No newline at end of file
@@ -1,8 +1,14 @@
-use std::ffi::{c_char, c_double, CString};
+use std::ffi::{c_char, c_double, CStr, CString};
 use crate::converter::string_converter::{str_c_to_rs, str_rs_to_c};
-use crate::data::ngpd_game_info::{free_game_info, FfiGameInfo};
-use crate::data::ngpd_player::{free_player, FfiPlayer};
-use nogamepads_core::data::message::message_enums::{ConnectionMessage, ConnectionResponseMessage, ControlMessage, ExitReason, GameMessage, JoinFailedMessage};
+use crate::data::ngpd_game_info::{free_game_info, free_key_list, free_room_list, FfiGameInfo, FfiKeyList, FfiRoomList};
+use crate::data::ngpd_player::{free_customize_strings, free_player, FfiAccount, FfiCustomize, FfiPlayer};
+use nogamepads_core::data::message::message_admin::{AdminCommand, AdminResult};
+use nogamepads_core::data::message::message_enums::{ConnectionMessage, ConnectionResponseMessage, ControlMessage, ExitReason, GameMessage, InputError, JoinFailedMessage};
+use nogamepads_core::data::message::message_haptics::{HapticPattern, HapticPreset, HapticPulse};
+use nogamepads_core::data::message::message_prompt::{PromptResult, TextInputKind, TextPrompt};
+use nogamepads_core::data::message::message_sensors::MotionData;
+use nogamepads_core::data::message::message_touch::{TouchData, TouchPhase, TouchPoint};
+use nogamepads_core::data::player::player_profile::ProfileError;
 use std::mem::ManuallyDrop;
 use std::ops::Deref;
 
@@ -21,7 +27,12 @@ pub enum FfiControlMessageTag {
     CtrlDir,
     CtrlExit,
     CtrlError,
-    CtrlEnd
+    CtrlEnd,
+    CtrlMotion,
+    CtrlTouch,
+    CtrlPromptReply,
+    CtrlAdmin,
+    CtrlUpdateProfile
 }
 
 #[repr(C)]
@@ -31,6 +42,11 @@ pub union FfiControlMessageUnion {
     pub key: u8,
     pub key_and_axis: ManuallyDrop<FfiKeyAndAxis>,
     pub key_and_direction: ManuallyDrop<FfiKeyAndDirection>,
+    pub motion: FfiMotionData,
+    pub key_and_touch: FfiKeyAndTouch,
+    pub prompt_reply: ManuallyDrop<FfiPromptReply>,
+    pub admin_command: ManuallyDrop<FfiAdminCommand>,
+    pub customize: ManuallyDrop<FfiCustomize>,
 }
 
 #[repr(C)]
@@ -46,6 +62,94 @@ pub struct FfiKeyAndDirection {
     pub y: c_double
 }
 
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct FfiKeyAndTouch {
+    pub key: u8,
+    pub touch: FfiTouchData
+}
+
+#[repr(C)]
+pub struct FfiPromptReply {
+    pub id: u32,
+    /// Replied text (Null if the player dismissed the prompt)
+    pub text: *mut c_char
+}
+
+#[repr(C)]
+pub struct FfiAdminCommand {
+    pub kind: FfiAdminCommandKind,
+    /// Account id of the target (Null for lock, unlock and close)
+    pub account: *mut c_char,
+    /// Reason of the ban (Null if not given)
+    pub reason: *mut c_char
+}
+
+#[repr(C)]
+pub enum FfiAdminCommandKind {
+    AdminLock,
+    AdminUnlock,
+    AdminKick,
+    AdminBan,
+    AdminPardon,
+    AdminClose
+}
+
+#[repr(C)]
+pub struct FfiAdminReply {
+    pub command: FfiAdminCommand,
+    pub result: FfiAdminResult
+}
+
+#[repr(C)]
+pub enum FfiAdminResult {
+    AdminDone,
+    AdminPermissionDenied,
+    AdminPlayerNotFound
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct FfiTouchData {
+    /// Touch id, stays the same from the begin to the end of a touch
+    pub id: u32,
+    pub phase: FfiTouchPhase,
+    /// Normalized position (Range: 0 - 1)
+    pub x: c_double,
+    /// Normalized position (Range: 0 - 1)
+    pub y: c_double,
+    /// Pressure (Range: 0 - 1)
+    pub pressure: c_double
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum FfiTouchPhase {
+    TouchBegin,
+    TouchMove,
+    TouchEnd
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct FfiTouchPoint {
+    pub id: u32,
+    pub x: c_double,
+    pub y: c_double,
+    pub pressure: c_double
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct FfiMotionData {
+    /// Acceleration including gravity (x, y, z), in m/s²
+    pub acceleration: [c_double; 3],
+    /// Angular velocity around each axis (x, y, z), in rad/s
+    pub angular_velocity: [c_double; 3],
+    /// Orientation quaternion (x, y, z, w)
+    pub orientation: [c_double; 4]
+}
+
 #[repr(C)]
 pub struct FfiGameMessage {
     pub tag: FfiGameMessageTag,
@@ -58,7 +162,16 @@ pub enum FfiGameMessageTag {
     GameMsg,
     GameLetExit,
     GameError,
-    GameEnd
+    GameEnd,
+    GameTeamChanged,
+    GameInputRejected,
+    GameVibrate,
+    GameSampleRate,
+    GamePrompt,
+    GameCancelPrompt,
+    GameAdminReply,
+    GameProfileChanged,
+    GameProfileRejected
 }
 
 #[repr(C)]
@@ -66,9 +179,125 @@ pub union FfiGameMessageUnion {
     pub none: (),
     pub key: u8,
     pub message: *mut c_char,
+    pub team_changed: ManuallyDrop<FfiTeamChanged>,
+    pub input_rejected: ManuallyDrop<FfiInputRejected>,
+    pub haptic: ManuallyDrop<FfiHapticPattern>,
+    pub sample_rate: u16,
+    pub prompt: ManuallyDrop<FfiTextPrompt>,
+    pub prompt_id: u32,
+    pub admin_reply: ManuallyDrop<FfiAdminReply>,
+    pub customize: ManuallyDrop<FfiCustomize>,
+    pub profile_error: ManuallyDrop<FfiProfileError>,
     pub exit_reason: ManuallyDrop<FfiExitReason>,
 }
 
+#[repr(C)]
+pub struct FfiTeamChanged {
+    /// Team name (Null if unassigned)
+    pub team: *mut c_char,
+    pub customize: FfiCustomize
+}
+
+#[repr(C)]
+pub struct FfiProfileError {
+    pub kind: FfiProfileErrorKind,
+    /// Key of the invalid attribute (Null unless the error is about an attribute)
+    pub attribute: *mut c_char
+}
+
+#[repr(C)]
+pub enum FfiProfileErrorKind {
+    NicknameTooLongError,
+    InvalidNicknameError,
+    NicknameTakenError,
+    TooManyAttributesError,
+    InvalidAttributeKeyError,
+    InvalidAttributeValueError
+}
+
+#[repr(C)]
+pub struct FfiInputRejected {
+    pub key: u8,
+    pub error: FfiInputError
+}
+
+#[repr(C)]
+pub enum FfiInputError {
+    KeyNotRegisteredError,
+    KindMismatchError,
+    ValueOutOfRangeError
+}
+
+#[repr(C)]
+pub struct FfiHapticPattern {
+    pub kind: FfiHapticPatternKind,
+    /// Preset of the pattern (Ignored for custom patterns)
+    pub preset: FfiHapticPreset,
+    /// Pulses of the pattern, presets are expanded
+    pub pulses: *mut FfiHapticPulse,
+    pub len: usize,
+    pub cap: usize
+}
+
+#[repr(C)]
+pub enum FfiHapticPatternKind {
+    PresetPattern,
+    CustomPattern
+}
+
+#[repr(C)]
+pub enum FfiHapticPreset {
+    TapHaptic,
+    DoubleTapHaptic,
+    LongHaptic,
+    HeartbeatHaptic,
+    ErrorHaptic
+}
+
+#[repr(C)]
+pub struct FfiHapticPulse {
+    /// Duration in milliseconds
+    pub duration_ms: u32,
+    /// Intensity (Range: 0 - 1)
+    pub intensity: f32
+}
+
+#[repr(C)]
+pub struct FfiTextPrompt {
+    pub id: u32,
+    pub title: *mut c_char,
+    pub placeholder: *mut c_char,
+    /// Maximum count of characters (0 if unlimited)
+    pub max_length: u32,
+    pub kind: FfiTextInputKind
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum FfiTextInputKind {
+    TextInput,
+    NumberInput,
+    PasswordInput,
+    MultilineInput
+}
+
+#[repr(C)]
+pub struct FfiPromptResult {
+    pub kind: FfiPromptResultKind,
+    /// Submitted text (Null unless submitted)
+    pub text: *mut c_char
+}
+
+#[repr(C)]
+pub enum FfiPromptResultKind {
+    PromptPending,
+    PromptSubmitted,
+    PromptDismissed,
+    PromptTimedOut,
+    PromptCancelled,
+    PromptDisconnected
+}
+
 #[repr(C)]
 pub enum FfiExitReason {
     ExitReason,
@@ -92,13 +321,21 @@ pub enum FfiConnectionMessageTag {
     ConnectionRequestLayoutConfigure,
     ConnectionRequestSkinPackage,
     ConnectionReady,
-    ConnectionError
+    ConnectionError,
+    ConnectionEnterRoom,
+    ConnectionRequestRooms,
+    ConnectionRequestKeys,
+    ConnectionSignature,
+    ConnectionJoinAsGuest
 }
 
 #[repr(C)]
 pub union FfiConnectionMessageUnion {
     pub none: (),
-    pub player: ManuallyDrop<FfiPlayer>
+    pub player: ManuallyDrop<FfiPlayer>,
+    pub room: *mut c_char,
+    pub signature: [u8; 64],
+    pub customize: ManuallyDrop<FfiCustomize>
 }
 
 #[repr(C)]
@@ -114,19 +351,48 @@ pub enum FfiConnectionResponseMessageTag {
     FailResponse,
     OkResponse,
     WelcomeResponse,
-    ErrorResponse
+    ErrorResponse,
+    RoomsResponse,
+    KeysResponse,
+    ChallengeResponse,
+    GuestWelcomeResponse,
+    JoinWelcomeResponse
 }
 
 #[repr(C)]
 pub union FfiConnectionResponseMessageUnion {
     pub none: (),
     pub game_info: ManuallyDrop<FfiGameInfo>,
-    pub failed_message: ManuallyDrop<FfiJoinFailedMessage>
+    pub rooms: ManuallyDrop<FfiRoomList>,
+    pub keys: ManuallyDrop<FfiKeyList>,
+    pub failed_message: ManuallyDrop<FfiJoinFailed>,
+    pub challenge: [u8; 32],
+    pub welcome: ManuallyDrop<FfiJoinWelcome>
+}
+
+#[repr(C)]
+pub struct FfiJoinWelcome {
+    /// Account generated for the guest (Null fields unless welcomed as a guest)
+    pub account: FfiAccount,
+    /// Team name (Null if unassigned)
+    pub team: *mut c_char,
+    pub customize: FfiCustomize
+}
+
+#[repr(C)]
+pub struct FfiJoinFailed {
+    pub kind: FfiJoinFailedMessage,
+    /// Reason of the ban, or the key of the invalid attribute (Null otherwise)
+    pub reason: *mut c_char,
+    /// Seconds until the ban is lifted or the kick cooldown ends (0 if permanent or not limited)
+    pub seconds: u64,
+    /// Why the custom information was not accepted (Only valid for InvalidProfile)
+    pub profile_error: FfiProfileErrorKind
 }
 
 #[repr(C)]
 pub enum FfiJoinFailedMessage {
-    ContainIdenticalPlayer, PlayerBanned, GameLocked, UnknownError
+    ContainIdenticalPlayer, PlayerBanned, GameLocked, UnknownError, RoomNotFound, GameFull, NotAllowed, KickCooldown, IdentityNotProven, GuestsNotAllowed, InvalidProfile
 }
 
 impl From<ControlMessage> for FfiControlMessage {
@@ -178,6 +444,49 @@ impl From<ControlMessage> for FfiControlMessage {
                     }
                 }
             }
+            ControlMessage::Motion(motion) => {
+                FfiControlMessage {
+                    tag: FfiControlMessageTag::CtrlMotion,
+                    data: FfiControlMessageUnion {
+                        motion: FfiMotionData::from(&motion)
+                    }
+                }
+            }
+            ControlMessage::Touch(key, touch) => {
+                FfiControlMessage {
+                    tag: FfiControlMessageTag::CtrlTouch,
+                    data: FfiControlMessageUnion {
+                        key_and_touch: FfiKeyAndTouch { key, touch: FfiTouchData::from(&touch) }
+                    }
+                }
+            }
+            ControlMessage::PromptReply(id, text) => unsafe {
+                FfiControlMessage {
+                    tag: FfiControlMessageTag::CtrlPromptReply,
+                    data: FfiControlMessageUnion {
+                        prompt_reply: ManuallyDrop::new(FfiPromptReply {
+                            id,
+                            text: text.map(|text| str_rs_to_c(text)).unwrap_or(std::ptr::null_mut())
+                        })
+                    }
+                }
+            }
+            ControlMessage::Admin(command) => {
+                FfiControlMessage {
+                    tag: FfiControlMessageTag::CtrlAdmin,
+                    data: FfiControlMessageUnion {
+                        admin_command: ManuallyDrop::new(FfiAdminCommand::from(&command))
+                    }
+                }
+            }
+            ControlMessage::UpdateProfile(customize) => {
+                FfiControlMessage {
+                    tag: FfiControlMessageTag::CtrlUpdateProfile,
+                    data: FfiControlMessageUnion {
+                        customize: ManuallyDrop::new(FfiCustomize::from(customize))
+                    }
+                }
+            }
             ControlMessage::Exit => {
                 FfiControlMessage {
                     tag: FfiControlMessageTag::CtrlAxis,
@@ -220,6 +529,24 @@ impl From<FfiControlMessage> for ControlMessage {
                 let value = unsafe { value.data.key_and_direction };
                 ControlMessage::Dir(value.key.into(), (value.x.into(), value.y.into()))
             }
+            FfiControlMessageTag::CtrlMotion => unsafe {
+                ControlMessage::Motion((&value.data.motion).into())
+            }
+            FfiControlMessageTag::CtrlTouch => unsafe {
+                let value = value.data.key_and_touch;
+                ControlMessage::Touch(value.key, (&value.touch).into())
+            }
+            FfiControlMessageTag::CtrlPromptReply => unsafe {
+                let reply = value.data.prompt_reply.deref();
+                let text = if reply.text.is_null() { None } else { Some(str_c_to_rs(reply.text)) };
+                ControlMessage::PromptReply(reply.id, text)
+            }
+            FfiControlMessageTag::CtrlAdmin => unsafe {
+                ControlMessage::Admin(value.data.admin_command.deref().into())
+            }
+            FfiControlMessageTag::CtrlUpdateProfile => unsafe {
+                ControlMessage::UpdateProfile(value.data.customize.deref().try_into().unwrap_or_default())
+            }
             FfiControlMessageTag::CtrlExit => {
                 ControlMessage::Exit
             }
@@ -252,6 +579,83 @@ impl From<GameMessage> for FfiGameMessage {
                     }
                 }
             }
+            GameMessage::TeamChanged(team, customize) => unsafe {
+                FfiGameMessage {
+                    tag: FfiGameMessageTag::GameTeamChanged,
+                    data: FfiGameMessageUnion {
+                        team_changed: ManuallyDrop::new(FfiTeamChanged {
+                            team: team.map(|team| str_rs_to_c(team)).unwrap_or(std::ptr::null_mut()),
+                            customize: FfiCustomize::from(customize)
+                        })
+                    }
+                }
+            }
+            GameMessage::InputRejected(key, error) => {
+                FfiGameMessage {
+                    tag: FfiGameMessageTag::GameInputRejected,
+                    data: FfiGameMessageUnion {
+                        input_rejected: ManuallyDrop::new(FfiInputRejected {
+                            key,
+                            error: FfiInputError::from(&error)
+                        })
+                    }
+                }
+            }
+            GameMessage::Vibrate(pattern) => {
+                FfiGameMessage {
+                    tag: FfiGameMessageTag::GameVibrate,
+                    data: FfiGameMessageUnion {
+                        haptic: ManuallyDrop::new(FfiHapticPattern::from(&pattern))
+                    }
+                }
+            }
+            GameMessage::SampleRate(sample_rate) => {
+                FfiGameMessage {
+                    tag: FfiGameMessageTag::GameSampleRate,
+                    data: FfiGameMessageUnion { sample_rate }
+                }
+            }
+            GameMessage::Prompt(prompt) => {
+                FfiGameMessage {
+                    tag: FfiGameMessageTag::GamePrompt,
+                    data: FfiGameMessageUnion {
+                        prompt: ManuallyDrop::new(FfiTextPrompt::from(&prompt))
+                    }
+                }
+            }
+            GameMessage::CancelPrompt(prompt_id) => {
+                FfiGameMessage {
+                    tag: FfiGameMessageTag::GameCancelPrompt,
+                    data: FfiGameMessageUnion { prompt_id }
+                }
+            }
+            GameMessage::AdminReply(command, result) => {
+                FfiGameMessage {
+                    tag: FfiGameMessageTag::GameAdminReply,
+                    data: FfiGameMessageUnion {
+                        admin_reply: ManuallyDrop::new(FfiAdminReply {
+                            command: FfiAdminCommand::from(&command),
+                            result: FfiAdminResult::from(&result)
+                        })
+                    }
+                }
+            }
+            GameMessage::ProfileChanged(customize) => {
+                FfiGameMessage {
+                    tag: FfiGameMessageTag::GameProfileChanged,
+                    data: FfiGameMessageUnion {
+                        customize: ManuallyDrop::new(FfiCustomize::from(customize))
+                    }
+                }
+            }
+            GameMessage::ProfileRejected(error) => {
+                FfiGameMessage {
+                    tag: FfiGameMessageTag::GameProfileRejected,
+                    data: FfiGameMessageUnion {
+                        profile_error: ManuallyDrop::new(FfiProfileError::from(&error))
+                    }
+                }
+            }
             GameMessage::LetExit(reason) => {
                 FfiGameMessage {
                     tag: FfiGameMessageTag::GameLetExit,
@@ -285,6 +689,37 @@ impl From<FfiGameMessage> for GameMessage {
             FfiGameMessageTag::GameMsg => unsafe {
                 GameMessage::Msg(str_c_to_rs(value.data.message))
             }
+            FfiGameMessageTag::GameTeamChanged => unsafe {
+                let team_changed = value.data.team_changed.deref();
+                let team = if team_changed.team.is_null() { None } else { Some(str_c_to_rs(team_changed.team)) };
+                GameMessage::TeamChanged(team, (&team_changed.customize).try_into().unwrap_or_default())
+            }
+            FfiGameMessageTag::GameInputRejected => unsafe {
+                let input_rejected = value.data.input_rejected.deref();
+                GameMessage::InputRejected(input_rejected.key, (&input_rejected.error).into())
+            }
+            FfiGameMessageTag::GameVibrate => unsafe {
+                GameMessage::Vibrate(value.data.haptic.deref().into())
+            }
+            FfiGameMessageTag::GameSampleRate => unsafe {
+                GameMessage::SampleRate(value.data.sample_rate)
+            }
+            FfiGameMessageTag::GamePrompt => unsafe {
+                GameMessage::Prompt(value.data.prompt.deref().into())
+            }
+            FfiGameMessageTag::GameCancelPrompt => unsafe {
+                GameMessage::CancelPrompt(value.data.prompt_id)
+            }
+            FfiGameMessageTag::GameAdminReply => unsafe {
+                let reply = value.data.admin_reply.deref();
+                GameMessage::AdminReply((&reply.command).into(), (&reply.result).into())
+            }
+            FfiGameMessageTag::GameProfileChanged => unsafe {
+                GameMessage::ProfileChanged(value.data.customize.deref().try_into().unwrap_or_default())
+            }
+            FfiGameMessageTag::GameProfileRejected => unsafe {
+                GameMessage::ProfileRejected(value.data.profile_error.deref().into())
+            }
             FfiGameMessageTag::GameLetExit => unsafe {
                 GameMessage::LetExit(value.data.exit_reason.deref().into())
             }
@@ -298,6 +733,296 @@ impl From<FfiGameMessage> for GameMessage {
     }
 }
 
+impl From<&AdminCommand> for FfiAdminCommand {
+    fn from(value: &AdminCommand) -> Self {
+        let (kind, account, reason) = match value {
+            AdminCommand::Lock => { (FfiAdminCommandKind::AdminLock, None, None) }
+            AdminCommand::Unlock => { (FfiAdminCommandKind::AdminUnlock, None, None) }
+            AdminCommand::Kick(account) => { (FfiAdminCommandKind::AdminKick, Some(account.clone()), None) }
+            AdminCommand::Ban(account, reason) => { (FfiAdminCommandKind::AdminBan, Some(account.clone()), reason.clone()) }
+            AdminCommand::Pardon(account) => { (FfiAdminCommandKind::AdminPardon, Some(account.clone()), None) }
+            AdminCommand::Close => { (FfiAdminCommandKind::AdminClose, None, None) }
+        };
+        FfiAdminCommand {
+            kind,
+            account: account.map(|account| unsafe { str_rs_to_c(account) }).unwrap_or(std::ptr::null_mut()),
+            reason: reason.map(|reason| unsafe { str_rs_to_c(reason) }).unwrap_or(std::ptr::null_mut())
+        }
+    }
+}
+
+impl From<&FfiAdminCommand> for AdminCommand {
+    fn from(value: &FfiAdminCommand) -> Self {
+        let text = |ptr: *mut c_char| {
+            if ptr.is_null() { None } else { Some(unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned()) }
+        };
+        let account = text(value.account).unwrap_or_default();
+        match value.kind {
+            FfiAdminCommandKind::AdminLock => { AdminCommand::Lock }
+            FfiAdminCommandKind::AdminUnlock => { AdminCommand::Unlock }
+            FfiAdminCommandKind::AdminKick => { AdminCommand::Kick(account) }
+            FfiAdminCommandKind::AdminBan => { AdminCommand::Ban(account, text(value.reason)) }
+            FfiAdminCommandKind::AdminPardon => { AdminCommand::Pardon(account) }
+            FfiAdminCommandKind::AdminClose => { AdminCommand::Close }
+        }
+    }
+}
+
+impl From<&ProfileError> for FfiProfileError {
+    fn from(value: &ProfileError) -> Self {
+        let (kind, attribute) = profile_error_parts(value);
+        FfiProfileError {
+            kind,
+            attribute: attribute.map(|key| unsafe { str_rs_to_c(key) }).unwrap_or(std::ptr::null_mut())
+        }
+    }
+}
+
+impl From<&FfiProfileError> for ProfileError {
+    fn from(value: &FfiProfileError) -> Self {
+        let attribute = if value.attribute.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(value.attribute) }.to_string_lossy().into_owned()
+        };
+        profile_error(&value.kind, attribute)
+    }
+}
+
+fn profile_error_parts(value: &ProfileError) -> (FfiProfileErrorKind, Option<String>) {
+    match value {
+        ProfileError::NicknameTooLong => { (FfiProfileErrorKind::NicknameTooLongError, None) }
+        ProfileError::InvalidNickname => { (FfiProfileErrorKind::InvalidNicknameError, None) }
+        ProfileError::NicknameTaken => { (FfiProfileErrorKind::NicknameTakenError, None) }
+        ProfileError::TooManyAttributes => { (FfiProfileErrorKind::TooManyAttributesError, None) }
+        ProfileError::InvalidAttributeKey(key) => { (FfiProfileErrorKind::InvalidAttributeKeyError, Some(key.clone())) }
+        ProfileError::InvalidAttributeValue(key) => { (FfiProfileErrorKind::InvalidAttributeValueError, Some(key.clone())) }
+    }
+}
+
+fn profile_error(kind: &FfiProfileErrorKind, attribute: String) -> ProfileError {
+    match kind {
+        FfiProfileErrorKind::NicknameTooLongError => { ProfileError::NicknameTooLong }
+        FfiProfileErrorKind::InvalidNicknameError => { ProfileError::InvalidNickname }
+        FfiProfileErrorKind::NicknameTakenError => { ProfileError::NicknameTaken }
+        FfiProfileErrorKind::TooManyAttributesError => { ProfileError::TooManyAttributes }
+        FfiProfileErrorKind::InvalidAttributeKeyError => { ProfileError::InvalidAttributeKey(attribute) }
+        FfiProfileErrorKind::InvalidAttributeValueError => { ProfileError::InvalidAttributeValue(attribute) }
+    }
+}
+
+impl From<&AdminResult> for FfiAdminResult {
+    fn from(value: &AdminResult) -> Self {
+        match value {
+            AdminResult::Done => { FfiAdminResult::AdminDone }
+            AdminResult::PermissionDenied => { FfiAdminResult::AdminPermissionDenied }
+            AdminResult::PlayerNotFound => { FfiAdminResult::AdminPlayerNotFound }
+        }
+    }
+}
+
+impl From<&FfiAdminResult> for AdminResult {
+    fn from(value: &FfiAdminResult) -> Self {
+        match value {
+            FfiAdminResult::AdminDone => { AdminResult::Done }
+            FfiAdminResult::AdminPermissionDenied => { AdminResult::PermissionDenied }
+            FfiAdminResult::AdminPlayerNotFound => { AdminResult::PlayerNotFound }
+        }
+    }
+}
+
+impl From<&InputError> for FfiInputError {
+    fn from(value: &InputError) -> Self {
+        match value {
+            InputError::KeyNotRegistered => { FfiInputError::KeyNotRegisteredError }
+            InputError::KindMismatch => { FfiInputError::KindMismatchError }
+            InputError::ValueOutOfRange => { FfiInputError::ValueOutOfRangeError }
+        }
+    }
+}
+
+impl From<&FfiInputError> for InputError {
+    fn from(value: &FfiInputError) -> Self {
+        match value {
+            FfiInputError::KeyNotRegisteredError => { InputError::KeyNotRegistered }
+            FfiInputError::KindMismatchError => { InputError::KindMismatch }
+            FfiInputError::ValueOutOfRangeError => { InputError::ValueOutOfRange }
+        }
+    }
+}
+
+impl From<&TouchData> for FfiTouchData {
+    fn from(value: &TouchData) -> Self {
+        let phase = match value.phase {
+            TouchPhase::Begin => { FfiTouchPhase::TouchBegin }
+            TouchPhase::Move => { FfiTouchPhase::TouchMove }
+            TouchPhase::End => { FfiTouchPhase::TouchEnd }
+        };
+        FfiTouchData { id: value.id, phase, x: value.x, y: value.y, pressure: value.pressure }
+    }
+}
+
+impl From<&FfiTouchData> for TouchData {
+    fn from(value: &FfiTouchData) -> Self {
+        let phase = match value.phase {
+            FfiTouchPhase::TouchBegin => { TouchPhase::Begin }
+            FfiTouchPhase::TouchMove => { TouchPhase::Move }
+            FfiTouchPhase::TouchEnd => { TouchPhase::End }
+        };
+        TouchData::new(value.id, phase, value.x, value.y, value.pressure)
+    }
+}
+
+impl From<&TouchPoint> for FfiTouchPoint {
+    fn from(value: &TouchPoint) -> Self {
+        FfiTouchPoint { id: value.id, x: value.x, y: value.y, pressure: value.pressure }
+    }
+}
+
+impl From<&MotionData> for FfiMotionData {
+    fn from(value: &MotionData) -> Self {
+        FfiMotionData {
+            acceleration: value.acceleration,
+            angular_velocity: value.angular_velocity,
+            orientation: value.orientation
+        }
+    }
+}
+
+impl From<&FfiMotionData> for MotionData {
+    fn from(value: &FfiMotionData) -> Self {
+        MotionData::new(value.acceleration, value.angular_velocity, value.orientation)
+    }
+}
+
+impl From<&HapticPattern> for FfiHapticPattern {
+    fn from(value: &HapticPattern) -> Self {
+        let (kind, preset) = match value {
+            HapticPattern::Preset(preset) => { (FfiHapticPatternKind::PresetPattern, preset.into()) }
+            HapticPattern::Custom(_) => { (FfiHapticPatternKind::CustomPattern, FfiHapticPreset::TapHaptic) }
+        };
+
+        let mut pulses: Vec<FfiHapticPulse> = value.pulses().iter()
+            .map(|pulse| FfiHapticPulse { duration_ms: pulse.duration_ms, intensity: pulse.intensity })
+            .collect();
+
+        let cap = pulses.capacity();
+        let len = pulses.len();
+        let data_ptr = pulses.as_mut_ptr();
+
+        std::mem::forget(pulses);
+
+        FfiHapticPattern { kind, preset, pulses: data_ptr, len, cap }
+    }
+}
+
+impl From<&FfiHapticPattern> for HapticPattern {
+    fn from(value: &FfiHapticPattern) -> Self {
+        match value.kind {
+            FfiHapticPatternKind::PresetPattern => { HapticPattern::Preset((&value.preset).into()) }
+            FfiHapticPatternKind::CustomPattern => {
+                if value.len == 0 || value.pulses.is_null() {
+                    return HapticPattern::Custom(Vec::new());
+                }
+                let pulses = unsafe { std::slice::from_raw_parts(value.pulses, value.len) };
+                HapticPattern::Custom(pulses.iter()
+                    .map(|pulse| HapticPulse::new(pulse.duration_ms, pulse.intensity))
+                    .collect())
+            }
+        }
+    }
+}
+
+impl From<&HapticPreset> for FfiHapticPreset {
+    fn from(value: &HapticPreset) -> Self {
+        match value {
+            HapticPreset::Tap => { FfiHapticPreset::TapHaptic }
+            HapticPreset::DoubleTap => { FfiHapticPreset::DoubleTapHaptic }
+            HapticPreset::Long => { FfiHapticPreset::LongHaptic }
+            HapticPreset::Heartbeat => { FfiHapticPreset::HeartbeatHaptic }
+            HapticPreset::Error => { FfiHapticPreset::ErrorHaptic }
+        }
+    }
+}
+
+impl From<&FfiHapticPreset> for HapticPreset {
+    fn from(value: &FfiHapticPreset) -> Self {
+        match value {
+            FfiHapticPreset::TapHaptic => { HapticPreset::Tap }
+            FfiHapticPreset::DoubleTapHaptic => { HapticPreset::DoubleTap }
+            FfiHapticPreset::LongHaptic => { HapticPreset::Long }
+            FfiHapticPreset::HeartbeatHaptic => { HapticPreset::Heartbeat }
+            FfiHapticPreset::ErrorHaptic => { HapticPreset::Error }
+        }
+    }
+}
+
+impl From<&TextPrompt> for FfiTextPrompt {
+    fn from(value: &TextPrompt) -> Self {
+        FfiTextPrompt {
+            id: value.id,
+            title: unsafe { str_rs_to_c(value.title.clone()) },
+            placeholder: unsafe { str_rs_to_c(value.placeholder.clone()) },
+            max_length: value.max_length.unwrap_or(0),
+            kind: (&value.kind).into()
+        }
+    }
+}
+
+impl From<&FfiTextPrompt> for TextPrompt {
+    fn from(value: &FfiTextPrompt) -> Self {
+        let mut prompt = TextPrompt::new(if value.title.is_null() { String::new() } else { unsafe { str_c_to_rs(value.title) } });
+        if !value.placeholder.is_null() {
+            prompt.placeholder(unsafe { str_c_to_rs(value.placeholder) });
+        }
+        if value.max_length > 0 {
+            prompt.max_length(value.max_length);
+        }
+        prompt.kind((&value.kind).into());
+        prompt.id = value.id;
+        prompt
+    }
+}
+
+impl From<&TextInputKind> for FfiTextInputKind {
+    fn from(value: &TextInputKind) -> Self {
+        match value {
+            TextInputKind::Text => { FfiTextInputKind::TextInput }
+            TextInputKind::Number => { FfiTextInputKind::NumberInput }
+            TextInputKind::Password => { FfiTextInputKind::PasswordInput }
+            TextInputKind::Multiline => { FfiTextInputKind::MultilineInput }
+        }
+    }
+}
+
+impl From<&FfiTextInputKind> for TextInputKind {
+    fn from(value: &FfiTextInputKind) -> Self {
+        match value {
+            FfiTextInputKind::TextInput => { TextInputKind::Text }
+            FfiTextInputKind::NumberInput => { TextInputKind::Number }
+            FfiTextInputKind::PasswordInput => { TextInputKind::Password }
+            FfiTextInputKind::MultilineInput => { TextInputKind::Multiline }
+        }
+    }
+}
+
+impl From<Option<PromptResult>> for FfiPromptResult {
+    fn from(value: Option<PromptResult>) -> Self {
+        let (kind, text) = match value {
+            None => { (FfiPromptResultKind::PromptPending, None) }
+            Some(PromptResult::Submitted(text)) => { (FfiPromptResultKind::PromptSubmitted, Some(text)) }
+            Some(PromptResult::Dismissed) => { (FfiPromptResultKind::PromptDismissed, None) }
+            Some(PromptResult::TimedOut) => { (FfiPromptResultKind::PromptTimedOut, None) }
+            Some(PromptResult::Cancelled) => { (FfiPromptResultKind::PromptCancelled, None) }
+            Some(PromptResult::Disconnected) => { (FfiPromptResultKind::PromptDisconnected, None) }
+        };
+        FfiPromptResult {
+            kind,
+            text: text.map(|text| unsafe { str_rs_to_c(text) }).unwrap_or(std::ptr::null_mut())
+        }
+    }
+}
+
 impl From<&ExitReason> for FfiExitReason {
     fn from(value: &ExitReason) -> Self {
         match value {
@@ -360,6 +1085,42 @@ impl From<ConnectionMessage> for FfiConnectionMessage {
                     data: FfiConnectionMessageUnion { none: () }
                 }
             }
+            ConnectionMessage::EnterRoom(room) => unsafe {
+                FfiConnectionMessage {
+                    tag: FfiConnectionMessageTag::ConnectionEnterRoom,
+                    data: FfiConnectionMessageUnion {
+                        room: str_rs_to_c(room)
+                    }
+                }
+            }
+            ConnectionMessage::RequestRooms => {
+                FfiConnectionMessage {
+                    tag: FfiConnectionMessageTag::ConnectionRequestRooms,
+                    data: FfiConnectionMessageUnion { none: () }
+                }
+            }
+            ConnectionMessage::RequestKeys => {
+                FfiConnectionMessage {
+                    tag: FfiConnectionMessageTag::ConnectionRequestKeys,
+                    data: FfiConnectionMessageUnion { none: () }
+                }
+            }
+            ConnectionMessage::Signature(signature) => {
+                FfiConnectionMessage {
+                    tag: FfiConnectionMessageTag::ConnectionSignature,
+                    data: FfiConnectionMessageUnion {
+                        signature: fixed_bytes(&signature)
+                    }
+                }
+            }
+            ConnectionMessage::JoinAsGuest(customize) => {
+                FfiConnectionMessage {
+                    tag: FfiConnectionMessageTag::ConnectionJoinAsGuest,
+                    data: FfiConnectionMessageUnion {
+                        customize: ManuallyDrop::new(FfiCustomize::from(customize))
+                    }
+                }
+            }
             ConnectionMessage::Err => {
                 FfiConnectionMessage {
                     tag: FfiConnectionMessageTag::ConnectionError,
@@ -380,6 +1141,17 @@ impl From<FfiConnectionMessage> for ConnectionMessage {
             FfiConnectionMessageTag::ConnectionRequestLayoutConfigure => { ConnectionMessage::RequestLayoutConfigure }
             FfiConnectionMessageTag::ConnectionRequestSkinPackage => { ConnectionMessage::RequestSkinPackage }
             FfiConnectionMessageTag::ConnectionReady => { ConnectionMessage::Ready }
+            FfiConnectionMessageTag::ConnectionEnterRoom => unsafe {
+                ConnectionMessage::EnterRoom(str_c_to_rs(value.data.room))
+            }
+            FfiConnectionMessageTag::ConnectionRequestRooms => { ConnectionMessage::RequestRooms }
+            FfiConnectionMessageTag::ConnectionRequestKeys => { ConnectionMessage::RequestKeys }
+            FfiConnectionMessageTag::ConnectionSignature => unsafe {
+                ConnectionMessage::Signature(value.data.signature.to_vec())
+            }
+            FfiConnectionMessageTag::ConnectionJoinAsGuest => unsafe {
+                ConnectionMessage::JoinAsGuest(value.data.customize.deref().try_into().unwrap_or_default())
+            }
             FfiConnectionMessageTag::ConnectionError => { ConnectionMessage::Err }
         }
     }
@@ -396,6 +1168,22 @@ impl From<ConnectionResponseMessage> for FfiConnectionResponseMessage {
                     }
                 }
             }
+            ConnectionResponseMessage::Rooms(rooms) => {
+                FfiConnectionResponseMessage {
+                    tag: FfiConnectionResponseMessageTag::RoomsResponse,
+                    data: FfiConnectionResponseMessageUnion {
+                        rooms: ManuallyDrop::new((&rooms).into())
+                    }
+                }
+            }
+            ConnectionResponseMessage::Keys(keys) => {
+                FfiConnectionResponseMessage {
+                    tag: FfiConnectionResponseMessageTag::KeysResponse,
+                    data: FfiConnectionResponseMessageUnion {
+                        keys: ManuallyDrop::new((&keys).into())
+                    }
+                }
+            }
             ConnectionResponseMessage::Deny(fail) => {
                 FfiConnectionResponseMessage {
                     tag: FfiConnectionResponseMessageTag::DenyResponse,
@@ -424,6 +1212,38 @@ impl From<ConnectionResponseMessage> for FfiConnectionResponseMessage {
                     data: FfiConnectionResponseMessageUnion { none: () }
                 }
             }
+            ConnectionResponseMessage::Challenge(challenge) => {
+                FfiConnectionResponseMessage {
+                    tag: FfiConnectionResponseMessageTag::ChallengeResponse,
+                    data: FfiConnectionResponseMessageUnion {
+                        challenge: fixed_bytes(&challenge)
+                    }
+                }
+            }
+            ConnectionResponseMessage::GuestWelcome(account, team, customize) => unsafe {
+                FfiConnectionResponseMessage {
+                    tag: FfiConnectionResponseMessageTag::GuestWelcomeResponse,
+                    data: FfiConnectionResponseMessageUnion {
+                        welcome: ManuallyDrop::new(FfiJoinWelcome {
+                            account: FfiAccount::from(account),
+                            team: team.map(|team| str_rs_to_c(team)).unwrap_or(std::ptr::null_mut()),
+                            customize: FfiCustomize::from(customize)
+                        })
+                    }
+                }
+            }
+            ConnectionResponseMessage::JoinWelcome(team, customize) => unsafe {
+                FfiConnectionResponseMessage {
+                    tag: FfiConnectionResponseMessageTag::JoinWelcomeResponse,
+                    data: FfiConnectionResponseMessageUnion {
+                        welcome: ManuallyDrop::new(FfiJoinWelcome {
+                            account: FfiAccount { id: std::ptr::null_mut(), player_hash: std::ptr::null_mut() },
+                            team: team.map(|team| str_rs_to_c(team)).unwrap_or(std::ptr::null_mut()),
+                            customize: FfiCustomize::from(customize)
+                        })
+                    }
+                }
+            }
             ConnectionResponseMessage::Err => {
                 FfiConnectionResponseMessage {
                     tag: FfiConnectionResponseMessageTag::ErrorResponse,
@@ -440,6 +1260,12 @@ impl From<FfiConnectionResponseMessage> for ConnectionResponseMessage {
             FfiConnectionResponseMessageTag::GameInfosResponse => unsafe {
                 ConnectionResponseMessage::GameInfos(value.data.game_info.deref().try_into().unwrap_or_default())
             }
+            FfiConnectionResponseMessageTag::RoomsResponse => unsafe {
+                ConnectionResponseMessage::Rooms(value.data.rooms.deref().try_into().unwrap_or_default())
+            }
+            FfiConnectionResponseMessageTag::KeysResponse => unsafe {
+                ConnectionResponseMessage::Keys(value.data.keys.deref().try_into().unwrap_or_default())
+            }
             FfiConnectionResponseMessageTag::DenyResponse => unsafe {
                 ConnectionResponseMessage::Deny(value.data.failed_message.deref().try_into().unwrap_or_default())
             }
@@ -452,6 +1278,20 @@ impl From<FfiConnectionResponseMessage> for ConnectionResponseMessage {
             FfiConnectionResponseMessageTag::WelcomeResponse => {
                 ConnectionResponseMessage::Welcome
             }
+            FfiConnectionResponseMessageTag::ChallengeResponse => unsafe {
+                ConnectionResponseMessage::Challenge(value.data.challenge.to_vec())
+            }
+            FfiConnectionResponseMessageTag::GuestWelcomeResponse => unsafe {
+                let welcome = value.data.welcome.deref();
+                let team = if welcome.team.is_null() { None } else { Some(str_c_to_rs(welcome.team)) };
+                ConnectionResponseMessage::GuestWelcome(
+                    (&welcome.account).try_into().unwrap_or_default(), team, (&welcome.customize).try_into().unwrap_or_default())
+            }
+            FfiConnectionResponseMessageTag::JoinWelcomeResponse => unsafe {
+                let welcome = value.data.welcome.deref();
+                let team = if welcome.team.is_null() { None } else { Some(str_c_to_rs(welcome.team)) };
+                ConnectionResponseMessage::JoinWelcome(team, (&welcome.customize).try_into().unwrap_or_default())
+            }
             FfiConnectionResponseMessageTag::ErrorResponse => {
                 ConnectionResponseMessage::Err
             }
@@ -459,28 +1299,78 @@ impl From<FfiConnectionResponseMessage> for ConnectionResponseMessage {
     }
 }
 
-impl From<&JoinFailedMessage> for FfiJoinFailedMessage {
+impl From<&JoinFailedMessage> for FfiJoinFailed {
     fn from(value: &JoinFailedMessage) -> Self {
-        match value {
-            JoinFailedMessage::ContainIdenticalPlayer => { FfiJoinFailedMessage::ContainIdenticalPlayer }
-            JoinFailedMessage::PlayerBanned => { FfiJoinFailedMessage::PlayerBanned }
-            JoinFailedMessage::GameLocked => { FfiJoinFailedMessage::GameLocked }
-            JoinFailedMessage::UnknownError => { FfiJoinFailedMessage::UnknownError }
+        let mut profile_error = FfiProfileErrorKind::InvalidNicknameError;
+        let (kind, reason, seconds) = match value {
+            JoinFailedMessage::ContainIdenticalPlayer => { (FfiJoinFailedMessage::ContainIdenticalPlayer, None, 0) }
+            JoinFailedMessage::PlayerBanned(reason, remaining) => {
+                (FfiJoinFailedMessage::PlayerBanned, reason.clone(), remaining.unwrap_or(0))
+            }
+            JoinFailedMessage::GameLocked => { (FfiJoinFailedMessage::GameLocked, None, 0) }
+            JoinFailedMessage::RoomNotFound => { (FfiJoinFailedMessage::RoomNotFound, None, 0) }
+            JoinFailedMessage::GameFull => { (FfiJoinFailedMessage::GameFull, None, 0) }
+            JoinFailedMessage::NotAllowed => { (FfiJoinFailedMessage::NotAllowed, None, 0) }
+            JoinFailedMessage::KickCooldown(remaining) => { (FfiJoinFailedMessage::KickCooldown, None, *remaining) }
+            JoinFailedMessage::IdentityNotProven => { (FfiJoinFailedMessage::IdentityNotProven, None, 0) }
+            JoinFailedMessage::GuestsNotAllowed => { (FfiJoinFailedMessage::GuestsNotAllowed, None, 0) }
+            JoinFailedMessage::InvalidProfile(error) => {
+                let (kind, attribute) = profile_error_parts(error);
+                profile_error = kind;
+                (FfiJoinFailedMessage::InvalidProfile, attribute, 0)
+            }
+            JoinFailedMessage::UnknownError => { (FfiJoinFailedMessage::UnknownError, None, 0) }
+        };
+        FfiJoinFailed {
+            kind,
+            reason: reason.map(|reason| unsafe { str_rs_to_c(reason) }).unwrap_or(std::ptr::null_mut()),
+            seconds,
+            profile_error
         }
     }
 }
 
-impl From<&FfiJoinFailedMessage> for JoinFailedMessage {
-    fn from(value: &FfiJoinFailedMessage) -> Self {
-        match value {
+impl From<&FfiJoinFailed> for JoinFailedMessage {
+    fn from(value: &FfiJoinFailed) -> Self {
+        match value.kind {
             FfiJoinFailedMessage::ContainIdenticalPlayer => { JoinFailedMessage::ContainIdenticalPlayer }
-            FfiJoinFailedMessage::PlayerBanned => { JoinFailedMessage::PlayerBanned }
+            FfiJoinFailedMessage::PlayerBanned => {
+                let reason = if value.reason.is_null() {
+                    None
+                } else {
+                    Some(unsafe { CStr::from_ptr(value.reason) }.to_string_lossy().into_owned())
+                };
+                let remaining = if value.seconds == 0 { None } else { Some(value.seconds) };
+                JoinFailedMessage::PlayerBanned(reason, remaining)
+            }
             FfiJoinFailedMessage::GameLocked => { JoinFailedMessage::GameLocked }
+            FfiJoinFailedMessage::RoomNotFound => { JoinFailedMessage::RoomNotFound }
+            FfiJoinFailedMessage::GameFull => { JoinFailedMessage::GameFull }
+            FfiJoinFailedMessage::NotAllowed => { JoinFailedMessage::NotAllowed }
+            FfiJoinFailedMessage::KickCooldown => { JoinFailedMessage::KickCooldown(value.seconds) }
+            FfiJoinFailedMessage::IdentityNotProven => { JoinFailedMessage::IdentityNotProven }
+            FfiJoinFailedMessage::GuestsNotAllowed => { JoinFailedMessage::GuestsNotAllowed }
+            FfiJoinFailedMessage::InvalidProfile => {
+                let attribute = if value.reason.is_null() {
+                    String::new()
+                } else {
+                    unsafe { CStr::from_ptr(value.reason) }.to_string_lossy().into_owned()
+                };
+                JoinFailedMessage::InvalidProfile(profile_error(&value.profile_error, attribute))
+            }
             FfiJoinFailedMessage::UnknownError => { JoinFailedMessage::UnknownError }
         }
     }
 }
 
+/// Copy the bytes into a fixed-size array, zero-padded or truncated
+fn fixed_bytes<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut fixed = [0u8; N];
+    let len = bytes.len().min(N);
+    fixed[..len].copy_from_slice(&bytes[..len]);
+    fixed
+}
+
 /// Free ControlMessage
 #[unsafe(no_mangle)]
 pub extern "C" fn free_control_message(msg: *mut FfiControlMessage) {
@@ -502,6 +1392,18 @@ pub extern "C" fn free_control_message(msg: *mut FfiControlMessage) {
                 let ptr = ManuallyDrop::into_inner(msg.data.key_and_direction);
                 drop(ptr);
             }
+            FfiControlMessageTag::CtrlPromptReply => {
+                let reply = ManuallyDrop::into_inner(msg.data.prompt_reply);
+                if !reply.text.is_null() {
+                    drop(CString::from_raw(reply.text));
+                }
+            }
+            FfiControlMessageTag::CtrlAdmin => {
+                free_admin_command_strings(ManuallyDrop::into_inner(msg.data.admin_command));
+            }
+            FfiControlMessageTag::CtrlUpdateProfile => {
+                free_customize_strings(ManuallyDrop::into_inner(msg.data.customize));
+            }
             _ => {}
         }
     }
@@ -520,6 +1422,31 @@ pub extern "C" fn free_game_message(msg: *mut FfiGameMessage) {
                     drop(CString::from_raw(msg.data.message));
                 }
             }
+            FfiGameMessageTag::GameTeamChanged => {
+                let team_changed = ManuallyDrop::into_inner(msg.data.team_changed);
+                if !team_changed.team.is_null() {
+                    drop(CString::from_raw(team_changed.team));
+                }
+                free_customize_strings(team_changed.customize);
+            }
+            FfiGameMessageTag::GameProfileChanged => {
+                free_customize_strings(ManuallyDrop::into_inner(msg.data.customize));
+            }
+            FfiGameMessageTag::GameProfileRejected => {
+                let error = ManuallyDrop::into_inner(msg.data.profile_error);
+                if !error.attribute.is_null() {
+                    drop(CString::from_raw(error.attribute));
+                }
+            }
+            FfiGameMessageTag::GameVibrate => {
+                free_haptic_pulses(ManuallyDrop::into_inner(msg.data.haptic));
+            }
+            FfiGameMessageTag::GamePrompt => {
+                free_text_prompt_strings(ManuallyDrop::into_inner(msg.data.prompt));
+            }
+            FfiGameMessageTag::GameAdminReply => {
+                free_admin_command_strings(ManuallyDrop::into_inner(msg.data.admin_reply).command);
+            }
             FfiGameMessageTag::GameLetExit => {
                 let reason = ManuallyDrop::into_inner(msg.data.exit_reason);
                 drop(reason);
@@ -529,6 +1456,56 @@ pub extern "C" fn free_game_message(msg: *mut FfiGameMessage) {
     }
 }
 
+/// Free HapticPattern
+#[unsafe(no_mangle)]
+pub extern "C" fn free_haptic_pattern(pattern: *mut FfiHapticPattern) {
+    if pattern.is_null() { return; }
+    let pattern = unsafe { Box::from_raw(pattern) };
+    free_haptic_pulses(*pattern);
+}
+
+fn free_haptic_pulses(pattern: FfiHapticPattern) {
+    if !pattern.pulses.is_null() {
+        drop(unsafe { Vec::from_raw_parts(pattern.pulses, pattern.len, pattern.cap) });
+    }
+}
+
+fn free_admin_command_strings(command: FfiAdminCommand) {
+    if !command.account.is_null() {
+        drop(unsafe { CString::from_raw(command.account) });
+    }
+    if !command.reason.is_null() {
+        drop(unsafe { CString::from_raw(command.reason) });
+    }
+}
+
+/// Free TextPrompt
+#[unsafe(no_mangle)]
+pub extern "C" fn free_text_prompt(prompt: *mut FfiTextPrompt) {
+    if prompt.is_null() { return; }
+    let prompt = unsafe { Box::from_raw(prompt) };
+    free_text_prompt_strings(*prompt);
+}
+
+fn free_text_prompt_strings(prompt: FfiTextPrompt) {
+    if !prompt.title.is_null() {
+        drop(unsafe { CString::from_raw(prompt.title) });
+    }
+    if !prompt.placeholder.is_null() {
+        drop(unsafe { CString::from_raw(prompt.placeholder) });
+    }
+}
+
+/// Free PromptResult
+#[unsafe(no_mangle)]
+pub extern "C" fn free_prompt_result(result: *mut FfiPromptResult) {
+    if result.is_null() { return; }
+    let result = unsafe { Box::from_raw(result) };
+    if !result.text.is_null() {
+        drop(unsafe { CString::from_raw(result.text) });
+    }
+}
+
 /// Free ExitReason
 #[unsafe(no_mangle)]
 pub extern "C" fn free_exit_reason(msg: *mut FfiExitReason) {
@@ -551,6 +1528,14 @@ pub extern "C" fn free_connection_message(msg: *mut FfiConnectionMessage) {
 
                 free_player(player_ptr);
             }
+            FfiConnectionMessageTag::ConnectionEnterRoom => {
+                if !msg.data.room.is_null() {
+                    drop(CString::from_raw(msg.data.room));
+                }
+            }
+            FfiConnectionMessageTag::ConnectionJoinAsGuest => {
+                free_customize_strings(ManuallyDrop::into_inner(msg.data.customize));
+            }
             _ => {}
         }
     }
@@ -568,10 +1553,34 @@ pub extern "C" fn free_connection_response_message(msg: *mut FfiConnectionRespon
                 let game_info = ManuallyDrop::into_inner(msg.data.game_info);
                 free_game_info(game_info);
             }
+            FfiConnectionResponseMessageTag::RoomsResponse => {
+                let rooms = ManuallyDrop::into_inner(msg.data.rooms);
+                free_room_list(rooms);
+            }
+            FfiConnectionResponseMessageTag::KeysResponse => {
+                let keys = ManuallyDrop::into_inner(msg.data.keys);
+                free_key_list(keys);
+            }
             FfiConnectionResponseMessageTag::DenyResponse |
             FfiConnectionResponseMessageTag::FailResponse => {
                 let failed_msg = ManuallyDrop::into_inner(msg.data.failed_message);
-                drop(failed_msg);
+                if !failed_msg.reason.is_null() {
+                    drop(CString::from_raw(failed_msg.reason));
+                }
+            }
+            FfiConnectionResponseMessageTag::GuestWelcomeResponse |
+            FfiConnectionResponseMessageTag::JoinWelcomeResponse => {
+                let welcome = ManuallyDrop::into_inner(msg.data.welcome);
+                if !welcome.account.id.is_null() {
+                    drop(CString::from_raw(welcome.account.id));
+                }
+                if !welcome.account.player_hash.is_null() {
+                    drop(CString::from_raw(welcome.account.player_hash));
+                }
+                if !welcome.team.is_null() {
+                    drop(CString::from_raw(welcome.team));
+                }
+                free_customize_strings(welcome.customize);
             }
             _ => {}
         }
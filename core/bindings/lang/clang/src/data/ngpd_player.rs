@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::ffi::{c_char, c_double, c_int, CStr, CString};
 use std::ptr;
 use nogamepads_core::data::player::player_data::{Account, Customize, Player};
+use nogamepads_core::data::player::player_identity::PlayerKey;
 use crate::converter::string_converter::str_rs_to_c;
+use crate::data::ngpd_game_info::{free_game_info, FfiGameInfo};
 
 #[repr(C)]
 pub struct FfiAccount {
@@ -11,16 +14,20 @@ pub struct FfiAccount {
 
 #[repr(C)]
 pub struct FfiCustomize {
-    nickname: *mut c_char,
-    color_hue: c_int,
-    color_saturation: c_double,
-    color_value: c_double,
+    pub(crate) nickname: *mut c_char,
+    pub(crate) color_hue: c_int,
+    pub(crate) color_saturation: c_double,
+    pub(crate) color_value: c_double,
+    /// Profile attributes, e.g. a preferred language or an avatar reference
+    pub(crate) attributes: FfiGameInfo,
 }
 
 #[repr(C)]
 pub struct FfiPlayer {
     account: FfiAccount,
     customize: *mut FfiCustomize,
+    /// Legacy player hash kept after upgrading the credentials (Null if none)
+    previous_hash: *mut c_char,
 }
 
 impl From<Account> for FfiAccount {
@@ -32,19 +39,54 @@ impl From<Account> for FfiAccount {
     }
 }
 
+impl TryFrom<&FfiAccount> for Account {
+    type Error = ();
+
+    fn try_from(acc: &FfiAccount) -> Result<Self, Self::Error> {
+        Ok(Account {
+            id: unsafe { CStr::from_ptr(acc.id) }.to_str().map_err(|_| ())?.to_owned(),
+            player_hash: unsafe { CStr::from_ptr(acc.player_hash) }.to_str().map_err(|_| ())?.to_owned(),
+        })
+    }
+}
+
+impl From<Customize> for FfiCustomize {
+    fn from(c: Customize) -> Self {
+        FfiCustomize {
+            nickname: CString::new(c.nickname).unwrap().into_raw(),
+            color_hue: c.color_hue,
+            color_saturation: c.color_saturation,
+            color_value: c.color_value,
+            attributes: FfiGameInfo::from(&c.attributes),
+        }
+    }
+}
+
+impl TryFrom<&FfiCustomize> for Customize {
+    type Error = ();
+
+    fn try_from(c: &FfiCustomize) -> Result<Self, Self::Error> {
+        Ok(Customize {
+            nickname: unsafe { CStr::from_ptr(c.nickname) }.to_str().map_err(|_| ())?.to_owned(),
+            color_hue: c.color_hue,
+            color_saturation: c.color_saturation,
+            color_value: c.color_value,
+            attributes: HashMap::try_from(&c.attributes).map_err(|_| ())?,
+        })
+    }
+}
+
 impl From<&Player> for FfiPlayer {
     fn from(player: &Player) -> Self {
         let account = FfiAccount::from(player.account.clone());
         let customize = player.clone().customize.map(|c| {
-            Box::into_raw(Box::new(FfiCustomize {
-                nickname: CString::new(c.nickname).unwrap().into_raw(),
-                color_hue: c.color_hue,
-                color_saturation: c.color_saturation,
-                color_value: c.color_value,
-            }))
+            Box::into_raw(Box::new(FfiCustomize::from(c)))
         }).unwrap_or(ptr::null_mut());
+        let previous_hash = player.previous_hash.clone()
+            .map(|hash| CString::new(hash).unwrap().into_raw())
+            .unwrap_or(ptr::null_mut());
 
-        FfiPlayer { account, customize }
+        FfiPlayer { account, customize, previous_hash }
     }
 }
 
@@ -58,28 +100,45 @@ impl TryFrom<&FfiPlayer> for Player {
         };
 
         let customize = if !ffi.customize.is_null() {
-            let c = unsafe { &*ffi.customize };
-            Some(Customize {
-                nickname: unsafe { CStr::from_ptr(c.nickname) }.to_str().map_err(|_| ())?.to_owned(),
-                color_hue: c.color_hue,
-                color_saturation: c.color_saturation,
-                color_value: c.color_value,
-            })
+            Some(Customize::try_from(unsafe { &*ffi.customize })?)
         } else {
             None
         };
 
-        Ok(Player { account, customize })
+        let previous_hash = if !ffi.previous_hash.is_null() {
+            Some(unsafe { CStr::from_ptr(ffi.previous_hash) }.to_str().map_err(|_| ())?.to_owned())
+        } else {
+            None
+        };
+
+        Ok(Player { account, customize, previous_hash })
     }
 }
 
 /// Register a player
+/// Returns null if the credentials cannot be derived, such as when the pepper is not accepted
 #[unsafe(no_mangle)]
 pub extern "C" fn player_register(id: *const c_char, password: *const c_char) -> *mut FfiPlayer {
     let id_str = unsafe { CStr::from_ptr(id) }.to_string_lossy();
     let pass_str = unsafe { CStr::from_ptr(password) }.to_string_lossy();
 
-    let player = Player::register(id_str.into_owned(), pass_str.into_owned());
+    let Ok(player) = Player::register(id_str.into_owned(), pass_str.into_owned()) else {
+        return ptr::null_mut();
+    };
+    Box::into_raw(Box::new(FfiPlayer::from(&player)))
+}
+
+/// Restore a registered player from the player hash saved at registration
+/// Returns null if the password does not match the hash
+#[unsafe(no_mangle)]
+pub extern "C" fn player_login(id: *const c_char, password: *const c_char, hash: *const c_char) -> *mut FfiPlayer {
+    let id_str = unsafe { CStr::from_ptr(id) }.to_string_lossy();
+    let pass_str = unsafe { CStr::from_ptr(password) }.to_string_lossy();
+    let hash_str = unsafe { CStr::from_ptr(hash) }.to_string_lossy();
+
+    let Some(player) = Player::login(id_str.into_owned(), pass_str.into_owned(), hash_str.into_owned()) else {
+        return ptr::null_mut();
+    };
     Box::into_raw(Box::new(FfiPlayer::from(&player)))
 }
 
@@ -92,6 +151,45 @@ pub extern "C" fn player_from_hash(hash: *const c_char) -> *mut FfiPlayer {
     Box::into_raw(Box::new(FfiPlayer::from(&player)))
 }
 
+/// Generate a new key for a key account
+/// Returns the secret key in hex, keep it safe and free it with free_c_string
+#[unsafe(no_mangle)]
+pub extern "C" fn player_generate_key() -> *mut c_char {
+    unsafe { str_rs_to_c(PlayerKey::generate().secret().to_string()) }
+}
+
+/// Register a key account from the secret key in hex
+/// Returns null if the secret is invalid
+#[unsafe(no_mangle)]
+pub extern "C" fn player_register_with_key(secret: *const c_char) -> *mut FfiPlayer {
+    if secret.is_null() { return ptr::null_mut(); }
+    let secret_str = unsafe { CStr::from_ptr(secret) }.to_string_lossy();
+
+    let Some(key) = PlayerKey::from_secret(&secret_str) else {
+        return ptr::null_mut();
+    };
+    let player = Player::register_with_key(&key);
+    Box::into_raw(Box::new(FfiPlayer::from(&player)))
+}
+
+/// Check if the player is identified by a public key
+#[unsafe(no_mangle)]
+pub extern "C" fn player_is_key_account(player: *const FfiPlayer) -> bool {
+    if player.is_null() { return false; }
+    Player::try_from(unsafe { &*player })
+        .map(|p| p.is_key_account())
+        .unwrap_or(false)
+}
+
+/// Check if the player is an ephemeral guest
+#[unsafe(no_mangle)]
+pub extern "C" fn player_is_guest(player: *const FfiPlayer) -> bool {
+    if player.is_null() { return false; }
+    Player::try_from(unsafe { &*player })
+        .map(|p| p.is_guest())
+        .unwrap_or(false)
+}
+
 /// Get a hash from player
 #[unsafe(no_mangle)]
 pub extern "C" fn player_get_hash(player: *mut FfiPlayer) -> *const c_char {
@@ -113,6 +211,33 @@ pub extern "C" fn player_check(player: *const FfiPlayer, password: *const c_char
         .unwrap_or(false)
 }
 
+/// Check if the player hash is in an outdated credential format
+#[unsafe(no_mangle)]
+pub extern "C" fn player_needs_migration(player: *const FfiPlayer) -> bool {
+    if player.is_null() { return false; }
+    Player::try_from(unsafe { &*player })
+        .map(|p| p.needs_migration())
+        .unwrap_or(false)
+}
+
+/// Check the password and upgrade outdated credentials of the player
+/// Returns true if the player hash was replaced
+#[unsafe(no_mangle)]
+pub extern "C" fn player_migrate(player: *mut FfiPlayer, password: *const c_char) -> bool {
+    if player.is_null() || password.is_null() { return false; }
+    let Ok(mut rust_player) = Player::try_from(unsafe { &*player }) else {
+        return false;
+    };
+    let pass_str = unsafe { CStr::from_ptr(password) }.to_string_lossy();
+
+    if rust_player.migrate(pass_str.into_owned()) != Ok(true) {
+        return false;
+    }
+    let old = std::mem::replace(unsafe { &mut *player }, FfiPlayer::from(&rust_player));
+    free_player(Box::into_raw(Box::new(old)));
+    true
+}
+
 /// Set the player's nickname
 #[unsafe(no_mangle)]
 pub extern "C" fn player_set_nickname(player: *mut FfiPlayer, nickname: *const c_char) {
@@ -143,6 +268,43 @@ pub extern "C" fn player_set_hsv(
     *unsafe { &mut *player } = FfiPlayer::from(&rust_player);
 }
 
+/// Set a profile attribute of the player, an empty value removes it
+#[unsafe(no_mangle)]
+pub extern "C" fn player_set_attribute(player: *mut FfiPlayer, key: *const c_char, value: *const c_char) {
+    if player.is_null() || key.is_null() || value.is_null() { return; }
+    let key_str = unsafe { CStr::from_ptr(key) }.to_string_lossy();
+    let value_str = unsafe { CStr::from_ptr(value) }.to_string_lossy();
+    let Ok(mut rust_player) = Player::try_from(unsafe { &*player }) else {
+        return;
+    };
+
+    rust_player.attribute(&key_str, &value_str);
+    let old = std::mem::replace(unsafe { &mut *player }, FfiPlayer::from(&rust_player));
+    free_player(Box::into_raw(Box::new(old)));
+}
+
+/// Get a profile attribute of the player
+/// Returns null if the attribute is not set, free the value with free_c_string
+#[unsafe(no_mangle)]
+pub extern "C" fn player_get_attribute(player: *const FfiPlayer, key: *const c_char) -> *mut c_char {
+    if player.is_null() || key.is_null() { return ptr::null_mut(); }
+    let key_str = unsafe { CStr::from_ptr(key) }.to_string_lossy();
+
+    Player::try_from(unsafe { &*player }).ok()
+        .and_then(|p| p.customize)
+        .and_then(|c| c.attribute(&key_str).map(str::to_string))
+        .map(|value| unsafe { str_rs_to_c(value) })
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Free the strings and attributes owned by the custom information
+pub(crate) fn free_customize_strings(customize: FfiCustomize) {
+    if !customize.nickname.is_null() {
+        drop(unsafe { CString::from_raw(customize.nickname) });
+    }
+    free_game_info(customize.attributes);
+}
+
 /// Free the player
 #[unsafe(no_mangle)]
 pub extern "C" fn free_player(player: *mut FfiPlayer) {
@@ -162,13 +324,15 @@ pub extern "C" fn free_player(player: *mut FfiPlayer) {
         }
 
         if !player.customize.is_null() {
-            let customize = &mut *player.customize;
-            if !customize.nickname.is_null() {
-                drop(CString::from_raw(customize.nickname));
-            }
+            free_customize_strings(*Box::from_raw(player.customize));
             player.customize = ptr::null_mut();
         }
 
+        if !player.previous_hash.is_null() {
+            drop(CString::from_raw(player.previous_hash));
+            player.previous_hash = ptr::null_mut();
+        }
+
         drop(Box::from_raw(player));
     }
 }
\ No newline at end of file
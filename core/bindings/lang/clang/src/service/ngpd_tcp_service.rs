@@ -148,6 +148,19 @@ impl FfiTcpClientService {
         false
     }
 
+    /// Bind the room to join
+    #[unsafe(no_mangle)]
+    pub extern "C" fn tcp_client_bind_room(
+        service: *mut FfiTcpClientService,
+        room: *const c_char
+    ) {
+        if service.is_null() || room.is_null() { return; }
+
+        let inner = unsafe { &mut *((*service).0 as *mut PadClientNetwork) };
+        let room_str = unsafe { CStr::from_ptr(room) }.to_string_lossy().into_owned();
+        inner.bind_room(room_str);
+    }
+
     /// Connect
     #[unsafe(no_mangle)]
     pub extern "C" fn tcp_client_connect(
@@ -306,6 +319,27 @@ impl FfiTcpServerService {
         false
     }
 
+    /// Host another runtime in the specified room
+    #[unsafe(no_mangle)]
+    pub extern "C" fn tcp_server_add_room(
+        service: *mut FfiTcpServerService,
+        name: *const c_char,
+        runtime: *mut FfiGameRuntime
+    ) {
+        if service.is_null() || name.is_null() || runtime.is_null() { return; }
+
+        let inner = unsafe { &mut *((*service).0 as *mut PadServerNetwork) };
+        let name_str = unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned();
+
+        let runtime_ref = unsafe { &*runtime };
+        let arc = unsafe {
+            Arc::increment_strong_count(runtime_ref.inner as *const Mutex<GameRuntime>);
+            Arc::from_raw(runtime_ref.inner as *const Mutex<GameRuntime>)
+        };
+
+        inner.add_room(name_str, arc);
+    }
+
     /// Start listening
     #[unsafe(no_mangle)]
     pub extern "C" fn tcp_server_listening_block_on(
@@ -17,6 +17,12 @@ public class Player : IRustDataBorrow<FfiPlayer>, IRustDataUse<FfiPlayer>
         _ffi = ffi;
     }
 
+    public static Player? Login(string account, string password, string hash)
+    {
+        var ffi = nogamepads_data.PlayerLogin(account, password, hash);
+        return ffi == null ? null : new Player(ffi);
+    }
+
     public string NickName
     {
         get
@@ -1,9 +1,15 @@
 use std::sync::{Arc, Mutex};
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use clearscreen::clear;
-use log::info;
+use log::{info, warn};
 use nogamepads::entry_mutex;
+use tokio_stream::StreamExt;
 use crate::data::controller::controller_runtime::ControllerRuntime;
+use crate::data::game::game_data::KeyKind;
+use crate::data::message::message_admin::AdminCommand;
+use crate::data::message::message_sensors::MotionData;
+use crate::data::message::message_touch::{TouchData, TouchPhase};
+use crate::service::service_runner::NoGamepadsService;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -36,9 +42,75 @@ enum Commands {
     #[command(about = "Change a direction value")]
     Direction(DirectionArgs),
 
-    Pop,
+    #[command(about = "Send a motion sample")]
+    Motion(MotionArgs),
 
-    PopAll
+    #[command(about = "Touch a touchpad")]
+    Touch(TouchArgs),
+
+    #[command(about = "List the prompts waiting for a reply")]
+    Prompts,
+
+    #[command(about = "Reply to a prompt")]
+    Reply(ReplyArgs),
+
+    #[command(about = "Dismiss a prompt")]
+    Dismiss(PromptArgs),
+
+    #[command(subcommand, about = "Moderate the game, only for admin accounts")]
+    Admin(AdminCommands),
+
+    #[command(about = "Print the profile, or change it with the options, e.g. profile -n Alice -a lang=en")]
+    Profile(ProfileArgs),
+}
+
+#[derive(Subcommand, Debug)]
+enum AdminCommands {
+
+    #[command(about = "Lock the game")]
+    Lock,
+
+    #[command(about = "Unlock the game")]
+    Unlock,
+
+    #[command(about = "Kick a player")]
+    Kick(AdminTargetArgs),
+
+    #[command(about = "Ban a player")]
+    Ban(AdminBanArgs),
+
+    #[command(about = "Pardon a banned player")]
+    Pardon(AdminTargetArgs),
+
+    #[command(about = "Close the game")]
+    Close,
+}
+
+#[derive(Args, Debug)]
+struct AdminTargetArgs {
+    #[arg(help = "Account ID")]
+    account: String
+}
+
+#[derive(Args, Debug)]
+struct AdminBanArgs {
+    #[arg(help = "Account ID")]
+    account: String,
+
+    #[arg(long)]
+    reason: Option<String>
+}
+
+#[derive(Args, Debug)]
+struct ProfileArgs {
+    #[arg(short, long, help = "Nickname")]
+    nickname: Option<String>,
+
+    #[arg(short = 'c', long = "color", num_args = 3, value_names = ["H", "S", "V"], help = "Player color, h(0 - 360), s(0 - 1), v(0 - 1)")]
+    color: Option<Vec<f64>>,
+
+    #[arg(short, long = "attr", value_name = "KEY=VALUE", value_parser = parse_attribute, help = "Set an attribute, an empty value removes it")]
+    attributes: Vec<(String, String)>
 }
 
 #[derive(Args, Debug)]
@@ -48,22 +120,68 @@ struct MessageArgs {
 
 #[derive(Args, Debug)]
 struct ButtonArgs {
-    button_key: u8
+    #[arg(help = "Key ID or key name")]
+    button_key: String
 }
 
 #[derive(Args, Debug)]
 struct AxisArgs {
-    axis_key: u8,
+    #[arg(help = "Key ID or key name")]
+    axis_key: String,
     axis_value: f64
 }
 
 #[derive(Args, Debug)]
 struct DirectionArgs {
-    dir_key: u8,
+    #[arg(help = "Key ID or key name")]
+    dir_key: String,
     x: f64,
     y: f64
 }
 
+#[derive(Args, Debug)]
+struct TouchArgs {
+    #[arg(help = "Key ID or key name")]
+    touch_key: String,
+    #[arg(value_enum)]
+    phase: TouchPhaseArg,
+    id: u32,
+    x: f64,
+    y: f64,
+    #[arg(long, default_value_t = 1.0)]
+    pressure: f64
+}
+
+#[derive(Args, Debug)]
+struct ReplyArgs {
+    id: u32,
+    text: String
+}
+
+#[derive(Args, Debug)]
+struct PromptArgs {
+    id: u32
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum TouchPhaseArg {
+    Begin,
+    Move,
+    End
+}
+
+#[derive(Args, Debug)]
+struct MotionArgs {
+    #[arg(long, num_args = 3, value_names = ["X", "Y", "Z"], allow_negative_numbers = true, help = "Acceleration (m/s²)")]
+    accel: Option<Vec<f64>>,
+
+    #[arg(long, num_args = 3, value_names = ["X", "Y", "Z"], allow_negative_numbers = true, help = "Angular velocity (rad/s)")]
+    gyro: Option<Vec<f64>>,
+
+    #[arg(long, num_args = 4, value_names = ["X", "Y", "Z", "W"], allow_negative_numbers = true, help = "Orientation quaternion")]
+    orientation: Option<Vec<f64>>
+}
+
 pub fn process_controller_cli(runtime: Arc<Mutex<ControllerRuntime>>, cmd: ControllerCli) -> bool {
 
     match cmd.command {
@@ -86,45 +204,162 @@ pub fn process_controller_cli(runtime: Arc<Mutex<ControllerRuntime>>, cmd: Contr
 
         Commands::Press(args) => {
             entry_mutex!(runtime, |guard| {
-                guard.press_button(args.button_key);
+                if let Some(key) = resolve_key(guard, KeyKind::Button, &args.button_key) {
+                    guard.press_button(key);
+                }
             });
         }
 
         Commands::Release(args) => {
             entry_mutex!(runtime, |guard| {
-                guard.release_button(args.button_key);
+                if let Some(key) = resolve_key(guard, KeyKind::Button, &args.button_key) {
+                    guard.release_button(key);
+                }
             });
         }
 
         Commands::Axis(args) => {
             entry_mutex!(runtime, |guard| {
-                guard.change_axis(args.axis_key, args.axis_value);
+                if let Some(key) = resolve_key(guard, KeyKind::Axis, &args.axis_key) {
+                    guard.change_axis(key, args.axis_value);
+                }
+            });
+        }
+
+        Commands::Touch(args) => {
+            let phase = match args.phase {
+                TouchPhaseArg::Begin => { TouchPhase::Begin }
+                TouchPhaseArg::Move => { TouchPhase::Move }
+                TouchPhaseArg::End => { TouchPhase::End }
+            };
+            entry_mutex!(runtime, |guard| {
+                if let Some(key) = resolve_key(guard, KeyKind::Touchpad, &args.touch_key) {
+                    guard.touch(key, TouchData::new(args.id, phase, args.x, args.y, args.pressure));
+                }
+            });
+        }
+
+        Commands::Motion(args) => {
+            let mut motion = MotionData::default();
+            if let Some(accel) = args.accel {
+                motion.acceleration = [accel[0], accel[1], accel[2]];
+            }
+            if let Some(gyro) = args.gyro {
+                motion.angular_velocity = [gyro[0], gyro[1], gyro[2]];
+            }
+            if let Some(orientation) = args.orientation {
+                motion.orientation = [orientation[0], orientation[1], orientation[2], orientation[3]];
+            }
+            entry_mutex!(runtime, |guard| {
+                if guard.motion_sample_rate().is_none() {
+                    warn!("Motion sensors are not enabled by the game");
+                }
+                guard.send_motion(motion);
             });
         }
 
         Commands::Direction(args) => {
             entry_mutex!(runtime, |guard| {
-                guard.change_direction(args.dir_key, args.x, args.y);
+                if let Some(key) = resolve_key(guard, KeyKind::Direction, &args.dir_key) {
+                    guard.change_direction(key, args.x, args.y);
+                }
             });
         }
 
-        Commands::Pop => {
+        Commands::Prompts => {
             entry_mutex!(runtime, |guard| {
-                if let Some(msg) = guard.pop() {
-                    info!("{:?}", msg);
-                } else {
-                    info!("None");
+                if guard.pending_prompts().is_empty() {
+                    info!("No prompts pending");
                 }
+                for prompt in guard.pending_prompts() {
+                    let max_length = prompt.max_length.map(|max| format!(", max {} chars", max)).unwrap_or_default();
+                    info!("#{} \"{}\" ({:?}{}) {}", prompt.id, prompt.title, prompt.kind, max_length, prompt.placeholder);
+                }
+            });
+        }
+
+        Commands::Reply(args) => {
+            entry_mutex!(runtime, |guard| {
+                guard.reply_prompt(args.id, args.text);
             });
         }
 
-        Commands::PopAll => {
+        Commands::Dismiss(args) => {
             entry_mutex!(runtime, |guard| {
-                while let Some(msg) = guard.pop() {
-                    info!("{:?}", msg);
+                guard.dismiss_prompt(args.id);
+            });
+        }
+
+        Commands::Admin(cmds) => {
+            let command = match cmds {
+                AdminCommands::Lock => { AdminCommand::Lock }
+                AdminCommands::Unlock => { AdminCommand::Unlock }
+                AdminCommands::Kick(args) => { AdminCommand::Kick(args.account) }
+                AdminCommands::Ban(args) => { AdminCommand::Ban(args.account, args.reason) }
+                AdminCommands::Pardon(args) => { AdminCommand::Pardon(args.account) }
+                AdminCommands::Close => { AdminCommand::Close }
+            };
+            entry_mutex!(runtime, |guard| {
+                guard.admin(command);
+            });
+        }
+
+        Commands::Profile(args) => {
+            entry_mutex!(runtime, |guard| {
+                let mut player = guard.player().clone();
+                if args.nickname.is_none() && args.color.is_none() && args.attributes.is_empty() {
+                    let customize = player.customize.unwrap_or_default();
+                    info!("\"{}\", color ({}, {:.2}, {:.2})",
+                        customize.nickname, customize.color_hue, customize.color_saturation, customize.color_value);
+                    let mut attributes: Vec<_> = customize.attributes.iter().collect();
+                    attributes.sort();
+                    for (key, value) in attributes {
+                        info!("    {}: {}", key, value);
+                    }
+                    return true;
+                }
+
+                if let Some(nickname) = &args.nickname {
+                    player.nickname(nickname);
+                }
+                if let Some(color) = &args.color {
+                    player.hsv(color[0].round() as i32, color[1], color[2]);
                 }
+                for (key, value) in &args.attributes {
+                    player.attribute(key, value);
+                }
+                guard.update_profile(player.customize.unwrap_or_default());
             });
         }
     }
     true
+}
+
+/// Parse an attribute like lang=en, the value may be empty
+pub fn parse_attribute(text: &str) -> Result<(String, String), String> {
+    match text.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => { Ok((key.trim().to_string(), value.to_string())) }
+        _ => { Err(format!("Expected KEY=VALUE, got \"{}\"", text)) }
+    }
+}
+
+/// Resolve the key argument, numbers are used as IDs directly, others are looked up by name
+fn resolve_key(runtime: &ControllerRuntime, kind: KeyKind, key: &str) -> Option<u8> {
+    key.parse::<u8>().ok().or_else(|| runtime.find_key(kind, key))
+}
+
+/// Build a service that prints the messages sent by the game as soon as they arrive
+pub fn build_message_printer(runtime: Arc<Mutex<ControllerRuntime>>) -> NoGamepadsService {
+    let mut stream = None;
+    entry_mutex!(runtime, |guard| {
+        stream = Some(guard.subscribe_messages());
+    });
+
+    Box::pin(async move {
+        if let Some(mut stream) = stream {
+            while let Some(msg) = stream.next().await {
+                info!("{:?}", msg);
+            }
+        }
+    })
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use std::sync::{Arc, Mutex};
 use crate::data::controller::controller_runtime::ControllerRuntime;
-use crate::data::player::player_data::Player;
+use crate::data::player::player_data::{Customize, Player};
+use crate::data::player::player_identity::PlayerKey;
 
 /// Controller-side Data
 /// Describes the basic information of the controller side
@@ -8,7 +9,13 @@ use crate::data::player::player_data::Player;
 pub struct ControllerData {
 
     /// Player bound to the controller side
-    pub(crate) player: Player
+    pub(crate) player: Player,
+
+    /// Key proving the identity of a key account
+    pub(crate) key: Option<PlayerKey>,
+
+    /// Join as a guest, the account is generated by the pad_server
+    pub(crate) guest: bool
 }
 
 impl ControllerData {
@@ -18,10 +25,26 @@ impl ControllerData {
         self
     }
 
+    /// Join as a guest with the custom information instead of an account
+    pub fn bind_guest(&mut self, customize: Customize) -> &mut ControllerData {
+        self.player = Player { account: Default::default(), customize: Some(customize), previous_hash: None };
+        self.key = None;
+        self.guest = true;
+        self
+    }
+
+    /// Bind the key that signs the join challenges of a key account
+    pub fn bind_key(&mut self, key: PlayerKey) -> &mut ControllerData {
+        self.key = Some(key);
+        self
+    }
+
     /// Build the controller-side runtime using controller data
     pub fn runtime(self) -> Arc<Mutex<ControllerRuntime>> {
         let runtime = ControllerRuntime {
             player: self.player,
+            key: self.key,
+            guest: self.guest,
             ..Default::default()
         };
         Arc::new(Mutex::new(runtime))
@@ -31,6 +54,8 @@ impl ControllerData {
     pub fn runtime_with_borrowed_data(&self) -> Arc<Mutex<ControllerRuntime>> {
         let runtime = ControllerRuntime {
             player: self.player.clone(),
+            key: self.key.clone(),
+            guest: self.guest,
             ..Default::default()
         };
         Arc::new(Mutex::new(runtime))
@@ -0,0 +1,89 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::data::message::message_enums::ControlMessage;
+use crate::data::message::message_touch::{TouchData, TouchPhase};
+
+/// Controller inputs
+/// What the controller believes it is holding, built from the inputs it sent
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ControllerInputs {
+    pub(crate) buttons: BTreeSet<u8>,
+    pub(crate) axes: BTreeMap<u8, f64>,
+    pub(crate) directions: BTreeMap<u8, (f64, f64)>,
+    pub(crate) touches: BTreeMap<u8, BTreeMap<u32, TouchData>>,
+}
+
+impl ControllerInputs {
+
+    /// Record an input sent to the game, other messages are ignored
+    pub(crate) fn apply(&mut self, message: &ControlMessage) {
+        match message {
+            ControlMessage::Pressed(key) => { self.buttons.insert(*key); }
+            ControlMessage::Released(key) => { self.buttons.remove(key); }
+            ControlMessage::Axis(key, axis) => { self.axes.insert(*key, *axis); }
+            ControlMessage::Dir(key, dir) => { self.directions.insert(*key, *dir); }
+            ControlMessage::Touch(key, touch) => {
+                let points = self.touches.entry(*key).or_default();
+                match touch.phase {
+                    TouchPhase::Begin | TouchPhase::Move => { points.insert(touch.id, *touch); }
+                    TouchPhase::End => { points.remove(&touch.id); }
+                }
+                if points.is_empty() {
+                    self.touches.remove(key);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Messages that bring the game to the same input state
+    /// Fingers still on a touchpad begin again
+    pub fn messages(&self) -> Vec<ControlMessage> {
+        let buttons = self.buttons.iter()
+            .map(|key| ControlMessage::Pressed(*key));
+        let axes = self.axes.iter()
+            .map(|(key, axis)| ControlMessage::Axis(*key, *axis));
+        let directions = self.directions.iter()
+            .map(|(key, dir)| ControlMessage::Dir(*key, *dir));
+        let touches = self.touches.iter()
+            .flat_map(|(key, points)| points.values()
+                .map(|touch| ControlMessage::Touch(*key, TouchData { phase: TouchPhase::Begin, ..*touch })));
+
+        buttons.chain(axes).chain(directions).chain(touches).collect()
+    }
+
+    /// Check if the message is an input tracked by the controller
+    pub fn is_input(message: &ControlMessage) -> bool {
+        matches!(message, ControlMessage::Pressed(_)
+            | ControlMessage::Released(_)
+            | ControlMessage::Axis(..)
+            | ControlMessage::Dir(..)
+            | ControlMessage::Touch(..))
+    }
+
+    /// Check if the button is held
+    pub fn is_pressed(&self, key: u8) -> bool {
+        self.buttons.contains(&key)
+    }
+
+    /// Held buttons, ordered by keys
+    pub fn pressed_buttons(&self) -> Vec<u8> {
+        self.buttons.iter().copied().collect()
+    }
+
+    /// Last value sent for the axis
+    pub fn axis(&self, key: u8) -> Option<f64> {
+        self.axes.get(&key).copied()
+    }
+
+    /// Last value sent for the direction
+    pub fn direction(&self, key: u8) -> Option<(f64, f64)> {
+        self.directions.get(&key).copied()
+    }
+
+    /// Fingers still on the touchpad, ordered by touch ids
+    pub fn touches(&self, key: u8) -> Vec<TouchData> {
+        self.touches.get(&key)
+            .map(|points| points.values().copied().collect())
+            .unwrap_or_default()
+    }
+}
\ No newline at end of file
@@ -1,13 +1,27 @@
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::SeqCst;
-use log::trace;
+use log::{trace, warn};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use crate::data::controller::controller_inputs::ControllerInputs;
+use crate::data::game::game_data::{GameControlData, KeyKind};
 use crate::data::game::types::GameInfo;
+use crate::data::message::message_admin::AdminCommand;
 use crate::data::message::message_enums::{ControlMessage, GameMessage};
+use crate::data::message::message_haptics::HapticPattern;
+use crate::data::message::message_prompt::TextPrompt;
+use crate::data::message::message_sensors::MotionData;
+use crate::data::message::message_touch::TouchData;
 use crate::data::message::traits::MessageManager;
-use crate::data::player::player_data::Player;
+use crate::data::player::player_data::{Customize, Player};
+use crate::data::player::player_identity::PlayerKey;
 use crate::service::service_types::ServiceType;
 
+/// Stream of the messages sent by the game
+/// The stream ends when the controller is closed
+pub type GameMessageStream = UnboundedReceiverStream<GameMessage>;
+
 /// Controller-side runtime
 /// Stores all data involved in game pad_client interactions during runtime
 #[derive(Default)]
@@ -18,8 +32,18 @@ pub struct ControllerRuntime {
     pub(crate) send: HashMap<(ServiceType, u8), VecDeque<ControlMessage>>,
 
     pub(crate) player: Player,
+    pub(crate) key: Option<PlayerKey>,
+    pub(crate) guest: bool,
+    pub(crate) team: Option<String>,
+    pub(crate) haptic: Option<HapticPattern>,
+    pub(crate) sample_rate: Option<u16>,
+    pub(crate) prompts: Vec<TextPrompt>,
+    pub(crate) inputs: ControllerInputs,
+
+    pub(crate) subscribers: Vec<UnboundedSender<GameMessage>>,
 
     pub game_info: GameInfo,
+    pub(crate) keys: GameControlData,
     pub close: AtomicBool,
 }
 
@@ -33,6 +57,60 @@ impl MessageManager<GameMessage, ControlMessage, u8> for ControllerRuntime {
     fn borrow_send_list_mut(&mut self) -> &mut HashMap<(ServiceType, u8), VecDeque<ControlMessage>> {
         &mut self.send
     }
+
+    fn put_into_receive_list(&mut self, message: GameMessage, key: u8, service: ServiceType) {
+        if let GameMessage::TeamChanged(team, customize) = &message {
+            trace!("[Controller Runtime] Team changed to {:?}.", team);
+            self.team = team.clone();
+            self.player.customize = Some(customize.clone());
+        }
+
+        if let GameMessage::SampleRate(sample_rate) = &message {
+            trace!("[Controller Runtime] Sample rate changed to {} Hz.", sample_rate);
+            self.sample_rate = Some(*sample_rate);
+        }
+
+        // A new vibration interrupts the one not yet played
+        if let GameMessage::Vibrate(pattern) = &message {
+            trace!("[Controller Runtime] Vibrate: {:?}.", pattern);
+            self.haptic = Some(pattern.clone());
+        }
+
+        if let GameMessage::Prompt(prompt) = &message {
+            trace!("[Controller Runtime] Prompt {} received: \"{}\".", prompt.id, prompt.title);
+            self.prompts.retain(|pending| pending.id != prompt.id);
+            self.prompts.push(prompt.clone());
+        }
+
+        if let GameMessage::CancelPrompt(id) = &message {
+            trace!("[Controller Runtime] Prompt {} cancelled.", id);
+            self.prompts.retain(|pending| pending.id != *id);
+        }
+
+        if let GameMessage::AdminReply(command, result) = &message {
+            trace!("[Controller Runtime] Admin command {:?}: {:?}.", command, result);
+        }
+
+        if let GameMessage::ProfileChanged(customize) = &message {
+            trace!("[Controller Runtime] Profile changed to \"{}\".", customize.nickname);
+            self.player.customize = Some(customize.clone());
+        }
+
+        if let GameMessage::ProfileRejected(why) = &message {
+            warn!("[Controller Runtime] Profile rejected: {:?}.", why);
+        }
+
+        // Messages are delivered to the streams instead of the list while subscribed
+        self.subscribers.retain(|subscriber| subscriber.send(message.clone()).is_ok());
+        if !self.subscribers.is_empty() {
+            return;
+        }
+
+        self.borrow_received_list_mut()
+            .entry((service, key))
+            .or_insert_with(VecDeque::new)
+            .push_back(message);
+    }
 }
 
 impl ControllerRuntime {
@@ -40,6 +118,7 @@ impl ControllerRuntime {
     pub fn close(&mut self) {
         if !self.close.load(SeqCst) {
             self.close.store(true, SeqCst);
+            self.subscribers.clear();
             trace!("[Controller Runtime] Closed.");
         }
     }
@@ -68,6 +147,167 @@ impl ControllerRuntime {
         self.send_message(ControlMessage::Dir(key, (x, y)));
     }
 
+    pub fn touch(&mut self, key: u8, touch: TouchData) {
+        trace!("[Controller Runtime] Touch touch_{} #{} {:?} at ({}, {}).", key, touch.id, touch.phase, touch.x, touch.y);
+        self.send_message(ControlMessage::Touch(key, touch));
+    }
+
+    /// Touch a touchpad by its name, returns false if the name is not found
+    pub fn touch_by_name(&mut self, name: &str, touch: TouchData) -> bool {
+        self.find_key(KeyKind::Touchpad, name)
+            .map(|key| self.touch(key, touch))
+            .is_some()
+    }
+
+    /// Send a motion sample, ignored if the game does not enable motion sensors
+    pub fn send_motion(&mut self, motion: MotionData) {
+        if self.keys.motion.is_none() {
+            return;
+        }
+        self.send_message(ControlMessage::Motion(motion));
+    }
+
+    /// Samples per second requested by the game (None if motion sensors are not enabled)
+    pub fn motion_sample_rate(&self) -> Option<u16> {
+        self.keys.motion.map(|motion| self.sample_rate.unwrap_or(motion.sample_rate))
+    }
+
+    /// Press a button by its name, returns false if the name is not found
+    pub fn press_button_by_name(&mut self, name: &str) -> bool {
+        self.find_key(KeyKind::Button, name)
+            .map(|key| self.press_button(key))
+            .is_some()
+    }
+
+    /// Release a button by its name, returns false if the name is not found
+    pub fn release_button_by_name(&mut self, name: &str) -> bool {
+        self.find_key(KeyKind::Button, name)
+            .map(|key| self.release_button(key))
+            .is_some()
+    }
+
+    /// Change an axis by its name, returns false if the name is not found
+    pub fn change_axis_by_name(&mut self, name: &str, ax_val: f64) -> bool {
+        self.find_key(KeyKind::Axis, name)
+            .map(|key| self.change_axis(key, ax_val))
+            .is_some()
+    }
+
+    /// Change a direction by its name, returns false if the name is not found
+    pub fn change_direction_by_name(&mut self, name: &str, x: f64, y: f64) -> bool {
+        self.find_key(KeyKind::Direction, name)
+            .map(|key| self.change_direction(key, x, y))
+            .is_some()
+    }
+
+    /// Resolve the key name to its ID using the keys downloaded from the game
+    pub fn find_key(&self, kind: KeyKind, name: &str) -> Option<u8> {
+        let key = self.keys.find_key(kind, name);
+        if key.is_none() {
+            warn!("[Controller Runtime] {:?} \"{}\" not found!", kind, name);
+        }
+        key
+    }
+
+    /// Take the latest vibration requested by the game
+    /// The device plays it, or shows it if the device has no vibration
+    pub fn take_haptic(&mut self) -> Option<HapticPattern> {
+        self.haptic.take()
+    }
+
+    /// Send an admin command, the game replies with AdminReply
+    /// Only carried out if the account is an admin of the game
+    pub fn admin(&mut self, command: AdminCommand) {
+        trace!("[Controller Runtime] Admin command {:?}.", command);
+        self.send_message(ControlMessage::Admin(command));
+    }
+
+    /// Ask the game to replace the custom information of the player
+    /// The game replies with ProfileChanged, or ProfileRejected if it is not accepted
+    pub fn update_profile(&mut self, customize: Customize) {
+        trace!("[Controller Runtime] Update profile: \"{}\".", customize.nickname);
+        self.send_message(ControlMessage::UpdateProfile(customize));
+    }
+
+    /// Get the prompts waiting for the reply of the player, oldest first
+    pub fn pending_prompts(&self) -> &[TextPrompt] {
+        &self.prompts
+    }
+
+    /// Reply to the prompt with the text, cut to its maximum length
+    /// Returns false if the prompt is not pending
+    pub fn reply_prompt(&mut self, id: u32, text: String) -> bool {
+        match self.take_prompt(id) {
+            None => { false }
+            Some(prompt) => {
+                self.send_message(ControlMessage::PromptReply(id, Some(prompt.limit(text))));
+                true
+            }
+        }
+    }
+
+    /// Dismiss the prompt without replying text, returns false if the prompt is not pending
+    pub fn dismiss_prompt(&mut self, id: u32) -> bool {
+        match self.take_prompt(id) {
+            None => { false }
+            Some(_) => {
+                self.send_message(ControlMessage::PromptReply(id, None));
+                true
+            }
+        }
+    }
+
+    fn take_prompt(&mut self, id: u32) -> Option<TextPrompt> {
+        let index = self.prompts.iter().position(|prompt| prompt.id == id);
+        if index.is_none() {
+            warn!("[Controller Runtime] Prompt {} not pending!", id);
+        }
+        index.map(|index| self.prompts.remove(index))
+    }
+
+    /// Get the inputs the controller is holding
+    pub fn inputs(&self) -> &ControllerInputs {
+        &self.inputs
+    }
+
+    /// Resend the full input state, called when the controller (re)joins the game
+    /// The game releases all inputs of a player who leaves, queued inputs are replaced since the state covers them
+    pub fn resync_inputs(&mut self) {
+        let service = self.service_type.clone();
+        if let Some(queue) = self.send.get_mut(&(service.clone(), 0)) {
+            queue.retain(|msg| !ControllerInputs::is_input(msg));
+        }
+
+        let messages = self.inputs.messages();
+        trace!("[Controller Runtime] Resend {} inputs.", messages.len());
+        for msg in messages {
+            self.send(msg, 0, service.clone());
+        }
+    }
+
+    /// Get the keys downloaded from the game
+    pub fn keys(&self) -> &GameControlData {
+        &self.keys
+    }
+
+    /// Get the player bound to the controller
+    pub fn player(&self) -> &Player {
+        &self.player
+    }
+
+    /// Get the team assigned by the game
+    pub fn team(&self) -> Option<String> {
+        self.team.clone()
+    }
+
+    /// Subscribe to the messages sent by the game as an async stream
+    /// While subscribed, the messages are no longer kept for `pop`
+    pub fn subscribe_messages(&mut self) -> GameMessageStream {
+        let (sender, receiver) = unbounded_channel();
+        self.subscribers.push(sender);
+        UnboundedReceiverStream::new(receiver)
+    }
+
     pub fn pop(&mut self) -> Option<GameMessage> {
         let result = self.receive(0, self.service_type.clone());
         if result.is_none() {
@@ -80,6 +320,7 @@ impl ControllerRuntime {
 
     pub fn send_message (&mut self, msg: ControlMessage) {
         trace!("[Controller Runtime] Message {:?} sent.", &msg);
+        self.inputs.apply(&msg);
         let service = self.service_type.clone();
         self.send(msg, 0, service);
     }
@@ -1,3 +1,4 @@
 pub mod controller_cli;
+pub mod controller_inputs;
 pub mod controller_runtime;
 pub mod controller_data;
\ No newline at end of file
@@ -0,0 +1,80 @@
+use log::{info, warn};
+use nogamepads::string_utils::process_id_text;
+use crate::data::game::game_runtime::{GameRuntime, GameRuntimeData};
+use crate::data::message::message_admin::{AdminCommand, AdminResult};
+use crate::data::message::message_enums::GameMessage;
+use crate::data::player::player_data::{Account, Player};
+use crate::service::service_types::ServiceType;
+
+impl GameRuntimeData {
+
+    /// Check if the account can send admin commands
+    pub fn is_admin(&self, account: &Account) -> bool {
+        self.admins.contains(account)
+    }
+
+    /// Returns all admin accounts, ordered by account id
+    pub fn admin_accounts(&self) -> Vec<Account> {
+        let mut vec: Vec<Account> = self.admins.iter().cloned().collect();
+        vec.sort_by(|a, b| a.id.cmp(&b.id));
+        vec
+    }
+}
+
+impl GameRuntime {
+
+    /// Carry out the admin command of the account and reply with the result
+    pub(crate) fn run_admin_command(&mut self, account: &Account, command: AdminCommand, service_type: ServiceType) {
+        let result = if self.data.is_admin(account) {
+            self.moderate(&command)
+        } else {
+            AdminResult::PermissionDenied
+        };
+
+        match result {
+            AdminResult::Done => { info!("[Game Runtime] Admin \"{}\": {:?}", account.id, command); }
+            _ => { warn!("[Game Runtime] Admin command {:?} from \"{}\" failed: {:?}", command, account.id, result); }
+        }
+
+        let close = command == AdminCommand::Close && result == AdminResult::Done;
+        self.send_game_message(account, GameMessage::AdminReply(command, result), service_type);
+        if close {
+            self.close_game();
+        }
+    }
+
+    fn moderate(&mut self, command: &AdminCommand) -> AdminResult {
+        match command {
+            AdminCommand::Lock => { self.lock_game(); }
+            AdminCommand::Unlock => { self.unlock_game(); }
+            AdminCommand::Close => {}
+            AdminCommand::Kick(id) | AdminCommand::Ban(id, _) => {
+                let id = process_id_text(id.clone());
+                let Some(target) = self.data.online_accounts().into_iter().find(|online| online.id == id) else {
+                    return AdminResult::PlayerNotFound;
+                };
+                // Admins can't remove each other
+                if self.data.is_admin(&target) {
+                    return AdminResult::PermissionDenied;
+                }
+                let Some(service_type) = self.data.get_service_type(&target) else {
+                    return AdminResult::PlayerNotFound;
+                };
+
+                let player = Player::from(target);
+                match command {
+                    AdminCommand::Ban(_, reason) => { self.ban_player_with_reason(&player, reason.clone(), service_type); }
+                    _ => { self.kick_player(&player, service_type); }
+                }
+            }
+            AdminCommand::Pardon(id) => {
+                let id = process_id_text(id.clone());
+                let Some(target) = self.data.banned_accounts().into_iter().find(|banned| banned.id == id) else {
+                    return AdminResult::PlayerNotFound;
+                };
+                self.pardon_player(&Player::from(target));
+            }
+        }
+        AdminResult::Done
+    }
+}
\ No newline at end of file
@@ -1,9 +1,16 @@
 use std::sync::{Arc, Mutex};
-use clap::{Args, Parser, Subcommand};
+use std::time::Duration;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use clearscreen::clear;
 use log::{info, warn};
 use nogamepads::entry_mutex;
+use crate::data::game::game_data::unix_now;
 use crate::data::game::game_runtime::GameRuntime;
+use crate::data::game::game_stats::PlayerStats;
+use crate::data::message::message_enums::GameMessage;
+use crate::data::message::message_enums::GameMessage::EventTrigger;
+use crate::data::message::message_haptics::{HapticPattern, HapticPreset, HapticPulse};
+use crate::data::message::message_prompt::{TextInputKind, TextPrompt};
 use crate::data::player::player_data::Player;
 
 #[derive(Parser, Debug)]
@@ -11,6 +18,10 @@ use crate::data::player::player_data::Player;
 pub struct GameCli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Room of the game to control, hosts with several rooms use their default room if not set
+    #[arg(long, global = true, value_name = "Room")]
+    pub room: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -23,6 +34,27 @@ enum Commands {
 
     UnlockGame,
 
+    #[command(about = "Only admit the accounts on the allow list")]
+    EnableAllowList,
+
+    #[command(about = "Admit every account again")]
+    DisableAllowList,
+
+    #[command(about = "List the accounts on the allow list")]
+    AllowedList,
+
+    #[command(about = "Put an account on the allow list")]
+    Allow(AccountIdArgs),
+
+    #[command(about = "Take an account off the allow list")]
+    Disallow(AccountIdArgs),
+
+    #[command(about = "Let players join as guests without an account")]
+    AllowGuests,
+
+    #[command(about = "Stop guests from joining, online guests stay")]
+    DenyGuests,
+
     #[command(about = "Close the game")]
     Close,
 
@@ -30,19 +62,59 @@ enum Commands {
 
     BannedList,
 
-    Ban(PlayerIndex),
+    #[command(about = "Ban a player, e.g. ban 2 --for 30m --reason \"spam\"")]
+    Ban(BanArgs),
 
     Pardon(PlayerIndex),
 
-    Kick(PlayerIndex),
+    #[command(about = "Kick a player, e.g. kick 2 --cooldown 5m")]
+    Kick(KickArgs),
 
+    #[command(about = "Send an event to a player, some players or all players", allow_missing_positional = true)]
     Event(SendEventArgs),
 
+    #[command(about = "Send a message to a player, some players or all players", allow_missing_positional = true)]
     Message(SendMessageArgs),
 
+    #[command(about = "Vibrate the controller of a player")]
+    Vibrate(VibrateArgs),
+
+    #[command(about = "Request the motion sample rate (Hz) from a player")]
+    SampleRate(SampleRateArgs),
+
+    #[command(about = "Ask a player to type a text")]
+    Prompt(PromptArgs),
+
+    #[command(about = "Print the result of a prompt")]
+    PromptResult(PromptIdArgs),
+
+    #[command(about = "Cancel a pending prompt")]
+    CancelPrompt(PromptIdArgs),
+
+    #[command(about = "List teams and their online members")]
+    TeamList,
+
+    #[command(about = "Assign a player to a team, or remove it from its team")]
+    Team(AssignTeamArgs),
+
+    #[command(about = "Send an event to all members of a team")]
+    TeamEvent(SendTeamEventArgs),
+
+    #[command(about = "Send a message to all members of a team")]
+    TeamMessage(SendTeamMessageArgs),
+
     Pop,
 
     PopAll,
+
+    #[command(about = "Print the inputs of all players since the last snapshot")]
+    Snapshot,
+
+    #[command(about = "Print the session history of a player, or a summary of all players")]
+    Stats(StatsArgs),
+
+    #[command(about = "Print the nickname, color and attributes of an online player")]
+    Profile(ProfileArgs),
 }
 
 #[derive(Args, Debug)]
@@ -50,15 +122,139 @@ struct PlayerIndex {
     index: usize,
 }
 
+#[derive(Args, Debug)]
+struct PlayerSeat {
+    seat: u8,
+}
+
+#[derive(Args, Debug)]
+struct BanArgs {
+    seat: u8,
+
+    #[arg(long = "for", value_name = "DURATION", value_parser = parse_duration, help = "Lift the ban after the duration, e.g. 90s, 30m, 1h30m, 7d (Permanent if not set)")]
+    duration: Option<Duration>,
+
+    #[arg(long)]
+    reason: Option<String>
+}
+
+#[derive(Args, Debug)]
+struct KickArgs {
+    seat: u8,
+
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, help = "Time before the player can rejoin, e.g. 90s, 5m")]
+    cooldown: Option<Duration>
+}
+
+#[derive(Args, Debug)]
+struct AccountIdArgs {
+    id: String,
+}
+
+#[derive(Args, Debug)]
+struct TargetArgs {
+    #[arg(required_unless_present_any = ["all", "seats"], conflicts_with_all = ["all", "seats"])]
+    seat: Option<u8>,
+
+    #[arg(long, help = "Send to all online players")]
+    all: bool,
+
+    #[arg(long, value_name = "SEAT", requires = "all", help = "Skip the player at the seat when sending to all")]
+    except: Option<u8>,
+
+    #[arg(long, value_delimiter = ',', conflicts_with = "all", help = "Send to the players at the seats, e.g. --seats 1,3")]
+    seats: Vec<u8>
+}
+
 #[derive(Args, Debug)]
 struct SendEventArgs {
-    index: usize,
+    #[command(flatten)]
+    targets: TargetArgs,
     event: u8
 }
 
 #[derive(Args, Debug)]
 struct SendMessageArgs {
-    index: usize,
+    #[command(flatten)]
+    targets: TargetArgs,
+    msg: String
+}
+
+#[derive(Args, Debug)]
+struct VibrateArgs {
+    seat: u8,
+
+    #[arg(help = "Preset name: tap, double-tap, long, heartbeat, error")]
+    preset: Option<String>,
+
+    #[arg(long = "pulse", value_name = "Milliseconds:Intensity", help = "Custom pulse, e.g. --pulse 100:1 --pulse 50:0")]
+    pulses: Vec<String>
+}
+
+#[derive(Args, Debug)]
+struct SampleRateArgs {
+    seat: u8,
+    sample_rate: u16
+}
+
+#[derive(Args, Debug)]
+struct PromptArgs {
+    seat: u8,
+    title: String,
+
+    #[arg(long, default_value = "")]
+    placeholder: String,
+
+    #[arg(long)]
+    max_length: Option<u32>,
+
+    #[arg(long, value_enum, default_value_t = PromptKindArg::Text)]
+    kind: PromptKindArg,
+
+    #[arg(long, help = "Timeout in seconds")]
+    timeout: Option<u64>
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum PromptKindArg {
+    Text,
+    Number,
+    Password,
+    Multiline
+}
+
+#[derive(Args, Debug)]
+struct PromptIdArgs {
+    id: u32
+}
+
+#[derive(Args, Debug)]
+struct StatsArgs {
+    #[arg(help = "Seat of an online player, or account id")]
+    player: Option<String>
+}
+
+#[derive(Args, Debug)]
+struct ProfileArgs {
+    #[arg(help = "Seat of an online player, or nickname")]
+    player: String
+}
+
+#[derive(Args, Debug)]
+struct AssignTeamArgs {
+    seat: u8,
+    team: Option<String>
+}
+
+#[derive(Args, Debug)]
+struct SendTeamEventArgs {
+    team: String,
+    event: u8
+}
+
+#[derive(Args, Debug)]
+struct SendTeamMessageArgs {
+    team: String,
     msg: String
 }
 
@@ -80,6 +276,53 @@ pub fn process_game_cli(runtime: Arc<Mutex<GameRuntime>>, cmd: GameCli) -> bool
             });
         }
 
+        Commands::EnableAllowList => {
+            entry_mutex!(runtime, |guard| {
+                guard.enable_allow_list();
+            });
+        }
+
+        Commands::DisableAllowList => {
+            entry_mutex!(runtime, |guard| {
+                guard.disable_allow_list();
+            });
+        }
+
+        Commands::AllowedList => {
+            entry_mutex!(runtime, |guard| {
+                info!("Allow list {}", if guard.is_allow_list_enabled() { "enabled" } else { "disabled" });
+                for id in guard.data.allowed_accounts() {
+                    info!("{}", id);
+                }
+            });
+        }
+
+        Commands::Allow(args) => {
+            entry_mutex!(runtime, |guard| {
+                guard.allow_account(args.id.clone());
+                info!("Account {} allowed.", args.id);
+            });
+        }
+
+        Commands::Disallow(args) => {
+            entry_mutex!(runtime, |guard| {
+                guard.disallow_account(args.id.clone());
+                info!("Account {} disallowed.", args.id);
+            });
+        }
+
+        Commands::AllowGuests => {
+            entry_mutex!(runtime, |guard| {
+                guard.allow_guests();
+            });
+        }
+
+        Commands::DenyGuests => {
+            entry_mutex!(runtime, |guard| {
+                guard.deny_guests();
+            });
+        }
+
         Commands::Close => {
             entry_mutex!(runtime, |guard| {
                 guard.close_game();
@@ -89,10 +332,13 @@ pub fn process_game_cli(runtime: Arc<Mutex<GameRuntime>>, cmd: GameCli) -> bool
 
         Commands::OnlineList => {
             entry_mutex!(runtime, |guard| {
-                let mut i = 0;
-                for account in guard.data.online_accounts() {
-                    info!("{}.{}", i, account.id);
-                    i += 1;
+                for (seat, account) in guard.data.seated_accounts() {
+                    let nickname = guard.data.customize_of(&account)
+                        .filter(|customize| !customize.nickname.is_empty())
+                        .map(|customize| format!(" \"{}\"", customize.nickname))
+                        .unwrap_or_default();
+                    let guest = if account.is_guest() { " (guest)" } else { "" };
+                    info!("{}.{}{}{}", seat, account.id, nickname, guest);
                 }
             });
         }
@@ -101,7 +347,14 @@ pub fn process_game_cli(runtime: Arc<Mutex<GameRuntime>>, cmd: GameCli) -> bool
             entry_mutex!(runtime, |guard| {
                 let mut i = 0;
                 for account in guard.data.banned_accounts() {
-                    info!("{}.{}", i, account.id);
+                    let ban = guard.data.ban_of(&account).unwrap_or_default();
+                    let remaining = ban.remaining()
+                        .map(|remaining| format!(", {} left", duration_text(remaining)))
+                        .unwrap_or_default();
+                    match ban.reason {
+                        None => { info!("{}.{}{}", i, account.id, remaining); }
+                        Some(reason) => { info!("{}.{} ({}{})", i, account.id, reason, remaining); }
+                    }
                     i += 1;
                 }
             });
@@ -109,13 +362,16 @@ pub fn process_game_cli(runtime: Arc<Mutex<GameRuntime>>, cmd: GameCli) -> bool
 
         Commands::Ban(args) => {
             entry_mutex!(runtime, |guard| {
-                if let Some(account) = guard.data.online_accounts().get(args.index) {
+                if let Some(account) = &guard.data.account_at_seat(args.seat) {
                     if let Some(service_type) = guard.data.get_service_type(account) {
-                        guard.ban_player(&Player::from(account.clone()), service_type);
-                        info!("Account {} banned.", account.id);
+                        guard.ban_player_for(&Player::from(account.clone()), args.reason.clone(), args.duration, service_type);
+                        match args.duration {
+                            None => { info!("Account {} banned.", account.id); }
+                            Some(duration) => { info!("Account {} banned for {}.", account.id, duration_text(duration.as_secs())); }
+                        }
                     }
                 } else {
-                    warn!("Account number {} not found", args.index);
+                    warn!("Seat {} is empty", args.seat);
                 }
             });
         }
@@ -133,47 +389,146 @@ pub fn process_game_cli(runtime: Arc<Mutex<GameRuntime>>, cmd: GameCli) -> bool
 
         Commands::Kick(args) => {
             entry_mutex!(runtime, |guard| {
-                if let Some(account) = guard.data.online_accounts().get(args.index) {
+                if let Some(account) = &guard.data.account_at_seat(args.seat) {
                     if let Some(service_type) = guard.data.get_service_type(account) {
-                        guard.kick_player(&Player::from(account.clone()), service_type);
+                        guard.kick_player_with_cooldown(&Player::from(account.clone()), args.cooldown, service_type);
                         info!("Account {} kicked.", account.id);
                     }
                 } else {
-                    warn!("Account number {} not found", args.index);
+                    warn!("Seat {} is empty", args.seat);
                 }
             });
         }
 
         Commands::Event(args) => {
             entry_mutex!(runtime, |guard| {
-                if let Some(account) = guard.data.online_accounts().get(args.index) {
-                    if let Some(service_type) = guard.data.get_service_type(account) {
-                        guard.send_event(account, args.event, service_type);
-                        info!("Sent event {} to {}.", args.event, account.id);
+                let count = send_to_targets(guard, &args.targets, EventTrigger(args.event));
+                info!("Sent event {} to {} player(s).", args.event, count);
+            });
+        }
+
+        Commands::Message(args) => {
+            entry_mutex!(runtime, |guard| {
+                let count = send_to_targets(guard, &args.targets, GameMessage::Msg(args.msg.clone()));
+                info!("Sent message \"{}\" to {} player(s).", args.msg, count);
+            });
+        }
+
+        Commands::Vibrate(args) => {
+            let Some(pattern) = parse_haptic_pattern(&args) else {
+                warn!("Invalid haptic pattern");
+                return true;
+            };
+            entry_mutex!(runtime, |guard| {
+                if let Some(account) = &guard.data.account_at_seat(args.seat) {
+                    if guard.vibrate(account, pattern.clone()) {
+                        info!("Sent vibration {:?} to {}.", pattern, account.id);
                     }
                 } else {
-                    warn!("Account number {} not found", args.index);
+                    warn!("Seat {} is empty", args.seat);
                 }
             });
         }
 
-        Commands::Message(args) => {
+        Commands::SampleRate(args) => {
             entry_mutex!(runtime, |guard| {
-                if let Some(account) = guard.data.online_accounts().get(args.index) {
-                    if let Some(service_type) = guard.data.get_service_type(account) {
-                        guard.send_message(account, args.msg.clone(), service_type);
-                        info!("Sent message \"{}\" to {}.", args.msg, account.id);
+                if let Some(account) = &guard.data.account_at_seat(args.seat) {
+                    if guard.request_sample_rate(account, args.sample_rate) {
+                        info!("Requested {} Hz from {}.", args.sample_rate, account.id);
                     }
                 } else {
-                    warn!("Account number {} not found", args.index);
+                    warn!("Seat {} is empty", args.seat);
                 }
             });
         }
 
+        Commands::Prompt(args) => {
+            let kind = match args.kind {
+                PromptKindArg::Text => { TextInputKind::Text }
+                PromptKindArg::Number => { TextInputKind::Number }
+                PromptKindArg::Password => { TextInputKind::Password }
+                PromptKindArg::Multiline => { TextInputKind::Multiline }
+            };
+            let mut prompt = TextPrompt::new(args.title.clone());
+            prompt.placeholder(args.placeholder.clone()).kind(kind);
+            if let Some(max_length) = args.max_length {
+                prompt.max_length(max_length);
+            }
+            entry_mutex!(runtime, |guard| {
+                if let Some(account) = &guard.data.account_at_seat(args.seat) {
+                    if let Some(id) = guard.prompt(account, prompt.clone(), args.timeout.map(Duration::from_secs)) {
+                        info!("Sent prompt #{} to {}.", id, account.id);
+                    }
+                } else {
+                    warn!("Seat {} is empty", args.seat);
+                }
+            });
+        }
+
+        Commands::PromptResult(args) => {
+            entry_mutex!(runtime, |guard| {
+                match guard.take_prompt_result(args.id) {
+                    Some(result) => { info!("Prompt #{}: {:?}", args.id, result); }
+                    None if guard.is_prompt_pending(args.id) => { info!("Prompt #{} is pending", args.id); }
+                    None => { warn!("Prompt #{} not found", args.id); }
+                }
+            });
+        }
+
+        Commands::CancelPrompt(args) => {
+            entry_mutex!(runtime, |guard| {
+                if guard.cancel_prompt(args.id) {
+                    info!("Prompt #{} cancelled.", args.id);
+                } else {
+                    warn!("Prompt #{} is not pending", args.id);
+                }
+            });
+        }
+
+        Commands::TeamList => {
+            entry_mutex!(runtime, |guard| {
+                for team in guard.data.teams() {
+                    let members: Vec<String> = guard.data.team_members(&team.name).iter()
+                        .map(|account| format!("{}.{}", seat_text(guard.data.seat_of(account)), account.id))
+                        .collect();
+                    info!("{}: [{}]", team.name, members.join(", "));
+                }
+            });
+        }
+
+        Commands::Team(args) => {
+            entry_mutex!(runtime, |guard| {
+                if let Some(account) = &guard.data.account_at_seat(args.seat) {
+                    if guard.assign_team(account, args.team.clone()) {
+                        match &args.team {
+                            Some(team) => { info!("Account {} assigned to team {}.", account.id, team); }
+                            None => { info!("Account {} removed from its team.", account.id); }
+                        }
+                    }
+                } else {
+                    warn!("Seat {} is empty", args.seat);
+                }
+            });
+        }
+
+        Commands::TeamEvent(args) => {
+            entry_mutex!(runtime, |guard| {
+                guard.send_team_message(&args.team, EventTrigger(args.event));
+                info!("Sent event {} to team {}.", args.event, args.team);
+            });
+        }
+
+        Commands::TeamMessage(args) => {
+            entry_mutex!(runtime, |guard| {
+                guard.send_team_message(&args.team, GameMessage::Msg(args.msg.clone()));
+                info!("Sent message \"{}\" to team {}.", args.msg, args.team);
+            });
+        }
+
         Commands::Pop => {
             entry_mutex!(runtime, |guard| {
-                if let Some((account, message)) = guard.pop_control_event() {
-                    info!("{}: {:?}", account.id, message);
+                if let Some((account, event)) = guard.pop_control_event() {
+                    info!("[{}] {}: {:?}", seat_text(guard.data.seat_of(&account)), account.id, event);
                 } else {
                     info!("None")
                 }
@@ -182,11 +537,221 @@ pub fn process_game_cli(runtime: Arc<Mutex<GameRuntime>>, cmd: GameCli) -> bool
 
         Commands::PopAll => {
             entry_mutex!(runtime, |guard| {
-                while let Some((account, message)) = guard.pop_control_event() {
-                    info!("{}: {:?}", account.id, message);
+                while let Some((account, event)) = guard.pop_control_event() {
+                    info!("[{}] {}: {:?}", seat_text(guard.data.seat_of(&account)), account.id, event);
+                }
+            });
+        }
+
+        Commands::Stats(args) => {
+            entry_mutex!(runtime, |guard| {
+                let Some(player) = args.player.as_ref() else {
+                    for stats in guard.data.all_stats() {
+                        print_stats_summary(&stats);
+                    }
+                    return true;
+                };
+
+                let account = player.parse::<u8>().ok()
+                    .and_then(|seat| guard.data.account_at_seat(seat))
+                    .or_else(|| guard.data.all_stats().into_iter()
+                        .map(|stats| stats.account)
+                        .find(|account| account.id.eq_ignore_ascii_case(player.trim())));
+                let Some(stats) = account.and_then(|account| guard.data.stats_of(&account)) else {
+                    warn!("No stats of player \"{}\"", player);
+                    return true;
+                };
+
+                print_stats_summary(&stats);
+                let now = unix_now();
+                for (i, session) in stats.sessions.iter().enumerate() {
+                    let state = match &session.exit_reason {
+                        None => { "online".to_string() }
+                        Some(reason) => { format!("left {} ago ({:?})", duration_text(now.saturating_sub(session.left_at.unwrap_or(now))), reason) }
+                    };
+                    info!("    #{} joined {} ago, played {}, {} input(s), {}",
+                        i, duration_text(now.saturating_sub(session.joined_at)), duration_text(session.play_time()), session.inputs, state);
+                }
+            });
+        }
+
+        Commands::Profile(args) => {
+            entry_mutex!(runtime, |guard| {
+                let account = args.player.parse::<u8>().ok()
+                    .and_then(|seat| guard.data.account_at_seat(seat))
+                    .or_else(|| guard.data.account_by_nickname(&args.player));
+                let Some((account, customize)) = account
+                    .and_then(|account| guard.data.customize_of(&account).map(|customize| (account, customize))) else {
+                    warn!("No profile of player \"{}\"", args.player);
+                    return true;
+                };
+
+                info!("{}: \"{}\", color ({}, {:.2}, {:.2})",
+                    account.id, customize.nickname, customize.color_hue, customize.color_saturation, customize.color_value);
+                let mut attributes: Vec<_> = customize.attributes.iter().collect();
+                attributes.sort();
+                for (key, value) in attributes {
+                    info!("    {}: {}", key, value);
+                }
+            });
+        }
+
+        Commands::Snapshot => {
+            entry_mutex!(runtime, |guard| {
+                for player in guard.snapshot().players {
+                    info!("[{}] {}:", seat_text(player.seat), player.account.id);
+                    for button in player.buttons {
+                        info!("    btn_{}: {} (pressed {} times)",
+                            button.key, if button.pressed { "down" } else { "up" }, button.press_count);
+                    }
+                    for (key, axis) in player.axes {
+                        info!("    ax_{}: ({})", key, axis);
+                    }
+                    for (key, (x, y)) in player.directions {
+                        info!("    dir_{}: ({}, {})", key, x, y);
+                    }
+                    for (key, points) in player.touches {
+                        for point in points {
+                            info!("    touch_{} #{}: ({}, {}) pressure {}", key, point.id, point.x, point.y, point.pressure);
+                        }
+                    }
+                    if let Some(motion) = guard.control.get_motion(&player.account) {
+                        info!("    motion: {:?}", motion);
+                    }
                 }
             });
         }
     }
     true
+}
+
+/// Send the message to the targets, returns the count of players reached
+fn send_to_targets(runtime: &mut GameRuntime, targets: &TargetArgs, message: GameMessage) -> usize {
+    if targets.all {
+        return match targets.except.and_then(|seat| runtime.data.account_at_seat(seat)) {
+            None => { runtime.broadcast(message) }
+            Some(except) => { runtime.broadcast_except(&except, message) }
+        };
+    }
+
+    let mut accounts = Vec::new();
+    for seat in targets.seat.iter().chain(targets.seats.iter()) {
+        match runtime.data.account_at_seat(*seat) {
+            None => { warn!("Seat {} is empty", seat); }
+            Some(account) => { accounts.push(account); }
+        }
+    }
+    runtime.multicast(&accounts, message)
+}
+
+fn print_stats_summary(stats: &PlayerStats) {
+    info!("{}: {} session(s), played {}, {} input(s), {} press(es)",
+        stats.account.id, stats.session_count(), duration_text(stats.total_play_time()),
+        stats.total_inputs(), stats.total_presses());
+}
+
+/// Parse a duration like 90s, 30m, 1h30m or 7d, plain numbers are seconds
+/// Empty and zero durations are rejected
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let seconds = match text.parse::<u64>() {
+        Ok(seconds) => { seconds }
+        Err(_) => { parse_duration_units(text)? }
+    };
+    if seconds == 0 {
+        return Err(format!("Duration \"{}\" must be longer than zero", text));
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+fn parse_duration_units(text: &str) -> Result<u64, String> {
+    let mut seconds = 0u64;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => { 1 }
+            'm' => { 60 }
+            'h' => { 3600 }
+            'd' => { 86400 }
+            _ => { return Err(format!("Unknown unit '{}' in \"{}\"", c, text)); }
+        };
+        let value = number.parse::<u64>().map_err(|_| format!("Missing number before '{}' in \"{}\"", c, text))?;
+        seconds = seconds.saturating_add(value.saturating_mul(unit));
+        number.clear();
+    }
+    if !number.is_empty() || text.is_empty() {
+        return Err(format!("Missing unit after \"{}\" in \"{}\"", number, text));
+    }
+    Ok(seconds)
+}
+
+fn duration_text(seconds: u64) -> String {
+    format!("{}h {:02}m {:02}s", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+fn parse_haptic_pattern(args: &VibrateArgs) -> Option<HapticPattern> {
+    if args.pulses.is_empty() {
+        return match &args.preset {
+            None => { Some(HapticPattern::default()) }
+            Some(name) => { HapticPreset::from_name(name).map(HapticPattern::from) }
+        };
+    }
+
+    let mut pulses = Vec::new();
+    for pulse in &args.pulses {
+        let (duration, intensity) = pulse.split_once(':')?;
+        pulses.push(HapticPulse::new(duration.trim().parse().ok()?, intensity.trim().parse().ok()?));
+    }
+    Some(HapticPattern::Custom(pulses))
+}
+
+fn seat_text(seat: Option<u8>) -> String {
+    seat.map(|seat| seat.to_string()).unwrap_or("-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::parse_duration;
+
+    #[test]
+    fn parse_plain_seconds() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration(" 45 "), Ok(Duration::from_secs(45)));
+    }
+
+    #[test]
+    fn parse_units() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(30 * 60)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(3600 + 30 * 60)));
+        assert_eq!(parse_duration("7d"), Ok(Duration::from_secs(7 * 86400)));
+        assert_eq!(parse_duration("1d2h3m4s"), Ok(Duration::from_secs(86400 + 2 * 3600 + 3 * 60 + 4)));
+    }
+
+    #[test]
+    fn reject_empty() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("   ").is_err());
+    }
+
+    #[test]
+    fn reject_zero() {
+        assert!(parse_duration("0").is_err());
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("0h0m").is_err());
+    }
+
+    #[test]
+    fn reject_malformed() {
+        assert!(parse_duration("10").is_ok());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("5w").is_err());
+        assert!(parse_duration("-5s").is_err());
+    }
 }
\ No newline at end of file
@@ -1,10 +1,17 @@
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::SeqCst;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 use nogamepads::entry_mutex;
+use nogamepads::string_utils::process_id_text;
+use crate::data::game::game_input::InputProcessing;
 use crate::data::game::game_runtime::{GameControlRuntime, GameRuntime, GameRuntimeData};
-use crate::data::game::types::{GameInfo, Players};
-use crate::data::player::player_data::{Account, Player};
+use crate::data::game::game_stats::PlayerStats;
+use crate::data::game::types::GameInfo;
+use crate::data::player::player_data::{Account, Customize, Player};
 
 /// Game pad_client data
 /// Describes the basic information of the game pad_client
@@ -12,23 +19,346 @@ use crate::data::player::player_data::{Account, Player};
 pub struct GameData {
     pub info: GameInfo,
     pub control: GameControlData,
-    pub archive: GameRuntimeDataArchive
+    pub archive: GameRuntimeDataArchive,
+
+    /// Maximum number of players, each joined player takes a seat (Unlimited if not set)
+    #[serde(default)]
+    pub max_players: Option<u8>,
+
+    /// Teams that players can be assigned to
+    #[serde(default)]
+    pub teams: Vec<TeamData>,
+
+    /// How players are assigned to teams when they join
+    #[serde(default)]
+    pub team_policy: TeamPolicy,
+
+    /// Accounts allowed to send admin commands from their controllers
+    #[serde(default)]
+    pub admins: Vec<Account>,
+
+    /// Players can join as guests without an account
+    #[serde(default)]
+    pub allow_guests: bool,
+
+    /// How the nicknames of online players are kept apart
+    #[serde(default)]
+    pub nickname_policy: NicknamePolicy
 }
 
 /// Game control information
 /// Describes the buttons, axes, and directions that can be controlled.
-#[derive(Default, Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Default, Clone, Serialize, Deserialize, Encode, Decode, PartialEq, Debug)]
+#[serde(from = "GameControlDataRepr")]
 pub struct GameControlData {
-    pub direction_keys : HashMap<u8, String>,
-    pub axis_keys : HashMap<u8, String>,
-    pub button_keys : HashMap<u8, String>,
+    pub keys : HashMap<u8, KeyData>,
+
+    /// Motion sensors the game accepts (Disabled if not set)
+    pub motion : Option<MotionSettings>,
+}
+
+/// Stored form of the control information, accepts the key maps used before the key registry
+#[derive(Default, Deserialize)]
+struct GameControlDataRepr {
+    #[serde(default)]
+    keys : HashMap<u8, KeyData>,
+    #[serde(default)]
+    motion : Option<MotionSettings>,
+    #[serde(default)]
+    direction_keys : HashMap<u8, String>,
+    #[serde(default)]
+    axis_keys : HashMap<u8, String>,
+    #[serde(default)]
+    button_keys : HashMap<u8, String>,
+}
+
+/// Motion sensor settings
+/// Describes how the controller sends its motion samples
+#[derive(Clone, Copy, Serialize, Deserialize, Encode, Decode, PartialEq, Debug)]
+pub struct MotionSettings {
+
+    /// Samples per second requested from the controller
+    #[serde(default = "MotionSettings::default_sample_rate")]
+    pub sample_rate: u16,
+}
+
+impl Default for MotionSettings {
+    fn default() -> Self {
+        MotionSettings { sample_rate: Self::default_sample_rate() }
+    }
+}
+
+impl MotionSettings {
+    fn default_sample_rate() -> u16 { 60 }
+}
+
+/// Kind of a key
+#[derive(Default, Clone, Copy, Serialize, Deserialize, Encode, Decode, PartialEq, Eq, Hash, Debug)]
+pub enum KeyKind {
+    #[default]
+    Button,
+    Axis,
+    Direction,
+    Touchpad,
+}
+
+/// Key information
+/// Describes a registered key and the values it accepts
+#[derive(Clone, Serialize, Deserialize, Encode, Decode, PartialEq, Debug)]
+pub struct KeyData {
+
+    /// Kind of the key
+    pub kind: KeyKind,
+
+    /// Display name
+    pub name: String,
+
+    /// Minimum accepted value (Each component of a direction or a touch position)
+    #[serde(default = "KeyData::default_min")]
+    pub min: f64,
+
+    /// Maximum accepted value (Each component of a direction or a touch position)
+    #[serde(default = "KeyData::default_max")]
+    pub max: f64,
+
+    /// Value before the player changes it
+    #[serde(default)]
+    pub default: f64,
+
+    /// Description of the key (Optional)
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Filters applied to the values before they are stored
+    #[serde(default)]
+    pub processing: InputProcessing,
+}
+
+/// Team information
+/// Describes a team and the color applied to its members
+#[derive(Default, Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct TeamData {
+
+    /// Team name
+    pub name: String,
+
+    /// HSV Color - Hue (Range: 0 - 360)
+    pub color_hue: i32,
+
+    /// HSV Color - Saturation (Range: 0 - 1)
+    pub color_saturation: f64,
+
+    /// HSV Color - Value (Range: 0 - 1)
+    pub color_value: f64
+}
+
+/// Team assignment policy
+/// Decides which team a player joins when entering the game
+#[derive(Default, Clone, Copy, Serialize, Deserialize, PartialEq, Debug)]
+pub enum TeamPolicy {
+
+    /// Players are only assigned by the game
+    #[default]
+    Manual,
+
+    /// Players are assigned to the teams in turn
+    RoundRobin,
+
+    /// Players are assigned to the team with the fewest online members
+    Balanced
 }
 
+/// Nickname policy
+/// Decides what happens when a player uses the nickname of another online player (Case insensitive)
+#[derive(Default, Clone, Copy, Serialize, Deserialize, PartialEq, Debug)]
+pub enum NicknamePolicy {
+
+    /// Players can share nicknames
+    Any,
+
+    /// The player is rejected with NicknameTaken
+    Unique,
+
+    /// A number is appended to the nickname, e.g. "Player 2"
+    #[default]
+    Suffix
+}
+
+/// Current version of the runtime data archive
+/// 1: Banned accounts only
+/// 2: Bans with reasons and timestamps, known players and their seats
+/// 3: Session history of the players
+/// 4: Allow list
+/// 5: Temporary bans
+pub const GAME_ARCHIVE_VERSION: u32 = 5;
+
 /// Archive of game runtime data
 /// The game pad_client can convert data into this structure for persistence.
-#[derive(Default, Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(from = "GameRuntimeDataArchiveRepr")]
 pub struct GameRuntimeDataArchive {
-    pub banned: Vec<Account>
+
+    /// Version of the archive, older archives are migrated when loaded
+    pub version: u32,
+
+    /// Banned accounts
+    pub bans: Vec<BanRecord>,
+
+    /// Players who joined the game before
+    pub players: Vec<KnownPlayer>,
+
+    /// Session history of the players
+    pub stats: Vec<PlayerStats>,
+
+    /// Only the accounts on the allow list can join
+    pub allow_list_enabled: bool,
+
+    /// Account ids on the allow list
+    pub allowed: Vec<String>,
+}
+
+/// Stored form of the archive, accepts the archives of all versions
+#[derive(Default, Deserialize)]
+struct GameRuntimeDataArchiveRepr {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    bans: Vec<BanRecord>,
+    #[serde(default)]
+    players: Vec<KnownPlayer>,
+    #[serde(default)]
+    stats: Vec<PlayerStats>,
+    #[serde(default)]
+    allow_list_enabled: bool,
+    #[serde(default)]
+    allowed: Vec<String>,
+
+    // Version 1
+    #[serde(default)]
+    banned: Vec<Account>,
+}
+
+/// Ban record
+/// Describes why and when an account was banned
+#[derive(Default, Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct BanRecord {
+    pub account: Account,
+
+    /// Reason shown to the banned player (Optional)
+    #[serde(default)]
+    pub reason: Option<String>,
+
+    /// Seconds since the Unix epoch (0 if unknown)
+    #[serde(default)]
+    pub banned_at: u64,
+
+    /// When the ban is lifted, seconds since the Unix epoch (Permanent if not set)
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+/// Known player
+/// A player who joined the game before, with its last custom information and seat
+#[derive(Default, Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct KnownPlayer {
+    pub account: Account,
+
+    /// Custom information sent when the player last joined
+    #[serde(default)]
+    pub customize: Option<Customize>,
+
+    /// Seat taken by the player, it gets the seat back when returning
+    #[serde(default)]
+    pub seat: Option<u8>,
+}
+
+impl From<GameControlDataRepr> for GameControlData {
+    fn from(repr: GameControlDataRepr) -> Self {
+        let mut control = GameControlData { keys: repr.keys, motion: repr.motion };
+        let legacy = [
+            (KeyKind::Button, repr.button_keys),
+            (KeyKind::Axis, repr.axis_keys),
+            (KeyKind::Direction, repr.direction_keys),
+        ];
+        for (kind, keys) in legacy {
+            for (key, name) in keys {
+                control.keys.entry(key).or_insert_with(|| KeyData::new(kind, name));
+            }
+        }
+        control
+    }
+}
+
+impl GameControlData {
+
+    /// Get the registered key
+    pub fn key(&self, key: &u8) -> Option<&KeyData> {
+        self.keys.get(key)
+    }
+
+    /// Returns the ordered keys of the specified kind
+    pub fn keys_of(&self, kind: KeyKind) -> Vec<u8> {
+        let mut keys: Vec<u8> = self.keys.iter()
+            .filter(|(_, data)| data.kind == kind)
+            .map(|(key, _)| *key)
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Find the key of the specified kind by its name (Case insensitive)
+    /// Names stay valid when the keys are renumbered
+    pub fn find_key(&self, kind: KeyKind, name: &str) -> Option<u8> {
+        self.keys_of(kind).into_iter()
+            .find(|key| self.keys[key].name.trim().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+impl KeyData {
+
+    /// Create key data with the default range of the kind
+    pub fn new(kind: KeyKind, name: String) -> KeyData {
+        let (min, max) = match kind {
+            KeyKind::Button | KeyKind::Touchpad => { (0.0, 1.0) }
+            _ => { (Self::default_min(), Self::default_max()) }
+        };
+        KeyData { kind, name, min, max, default: 0.0, description: None, processing: InputProcessing::default() }
+    }
+
+    /// Set the accepted range
+    pub fn range(&mut self, min: f64, max: f64) -> &mut KeyData {
+        self.min = min.min(max);
+        self.max = max.max(min);
+        self.default = self.default.clamp(self.min, self.max);
+        self
+    }
+
+    /// Set the default value
+    pub fn default_value(&mut self, value: f64) -> &mut KeyData {
+        self.default = value.clamp(self.min, self.max);
+        self
+    }
+
+    /// Set the description
+    pub fn description(&mut self, description: String) -> &mut KeyData {
+        self.description = Some(description);
+        self
+    }
+
+    /// Set the input processing
+    pub fn processing(&mut self, processing: InputProcessing) -> &mut KeyData {
+        self.processing = processing;
+        self
+    }
+
+    /// Check if the value is in the accepted range
+    pub fn accepts(&self, value: f64) -> bool {
+        !value.is_nan() && value >= self.min && value <= self.max
+    }
+
+    fn default_min() -> f64 { -1.0 }
+
+    fn default_max() -> f64 { 1.0 }
 }
 
 impl Default for GameData {
@@ -45,6 +375,12 @@ impl GameData {
             info: GameInfo::default(),
             control: GameControlData::default(),
             archive: GameRuntimeDataArchive::default(),
+            max_players: None,
+            teams: Vec::new(),
+            team_policy: TeamPolicy::default(),
+            admins: Vec::new(),
+            allow_guests: false,
+            nickname_policy: NicknamePolicy::default(),
         };
 
         game.name("Mini Hero".to_string());
@@ -70,6 +406,80 @@ impl GameData {
         self
     }
 
+    /// Register or modify a key
+    pub fn key(&mut self, key: u8, data: KeyData) -> &mut GameData {
+        self.control.keys.insert(key, data);
+        self
+    }
+
+    /// Register or modify a button
+    pub fn button(&mut self, key: u8, name: String) -> &mut GameData {
+        self.key(key, KeyData::new(KeyKind::Button, name))
+    }
+
+    /// Register or modify an axis
+    pub fn axis(&mut self, key: u8, name: String) -> &mut GameData {
+        self.key(key, KeyData::new(KeyKind::Axis, name))
+    }
+
+    /// Register or modify a direction
+    pub fn direction(&mut self, key: u8, name: String) -> &mut GameData {
+        self.key(key, KeyData::new(KeyKind::Direction, name))
+    }
+
+    /// Set the maximum number of players
+    pub fn max_players(&mut self, max_players: u8) -> &mut GameData {
+        self.max_players = Some(max_players);
+        self
+    }
+
+    /// Add or modify a team
+    pub fn team(&mut self, name: String, hue: i32, saturation: f64, value: f64) -> &mut GameData {
+        let team = TeamData {
+            name,
+            color_hue: hue.clamp(0, 360),
+            color_saturation: saturation.clamp(0.0, 1.0),
+            color_value: value.clamp(0.0, 1.0),
+        };
+        match self.teams.iter_mut().find(|t| t.name == team.name) {
+            Some(exists) => { *exists = team; }
+            None => { self.teams.push(team); }
+        }
+        self
+    }
+
+    /// Let the account send admin commands
+    pub fn admin(&mut self, account: Account) -> &mut GameData {
+        if !self.admins.contains(&account) {
+            self.admins.push(account);
+        }
+        self
+    }
+
+    /// Let players join as guests without an account
+    pub fn allow_guests(&mut self, allow: bool) -> &mut GameData {
+        self.allow_guests = allow;
+        self
+    }
+
+    /// Set how the nicknames of online players are kept apart
+    pub fn nickname_policy(&mut self, policy: NicknamePolicy) -> &mut GameData {
+        self.nickname_policy = policy;
+        self
+    }
+
+    /// Set the team assignment policy
+    pub fn team_policy(&mut self, policy: TeamPolicy) -> &mut GameData {
+        self.team_policy = policy;
+        self
+    }
+
+    /// Accept motion samples from the controllers at the sample rate (Hz)
+    pub fn motion(&mut self, sample_rate: u16) -> &mut GameData {
+        self.control.motion = Some(MotionSettings { sample_rate: sample_rate.max(1) });
+        self
+    }
+
     /// Read game runtime archive data
     pub fn load_data(&mut self, archive: GameRuntimeDataArchive) -> &mut GameData {
         self.archive = archive;
@@ -80,11 +490,20 @@ impl GameData {
     pub fn runtime(self) -> Arc<Mutex<GameRuntime>> {
         let runtime = GameRuntime {
             info: self.info,
-            data: self.archive.into(),
+            data: GameRuntimeData {
+                max_players: self.max_players,
+                teams: self.teams,
+                team_policy: self.team_policy,
+                admins: self.admins.into_iter().collect(),
+                guests_allowed: AtomicBool::new(self.allow_guests),
+                nickname_policy: self.nickname_policy,
+                ..self.archive.into()
+            },
             control: GameControlRuntime {
                 keys: self.control,
                 ..Default::default()
             },
+            prompts: Default::default(),
 
             writer_count: 0,
             reader_count: 0,
@@ -99,33 +518,201 @@ impl GameData {
     }
 }
 
+impl Default for GameRuntimeDataArchive {
+    fn default() -> Self {
+        GameRuntimeDataArchive {
+            version: GAME_ARCHIVE_VERSION,
+            bans: Vec::new(),
+            players: Vec::new(),
+            stats: Vec::new(),
+            allow_list_enabled: false,
+            allowed: Vec::new(),
+        }
+    }
+}
+
+impl From<GameRuntimeDataArchiveRepr> for GameRuntimeDataArchive {
+    fn from(repr: GameRuntimeDataArchiveRepr) -> Self {
+        let mut archive = GameRuntimeDataArchive {
+            version: GAME_ARCHIVE_VERSION,
+            bans: repr.bans,
+            players: repr.players,
+            stats: repr.stats,
+            allow_list_enabled: repr.allow_list_enabled,
+            allowed: repr.allowed,
+        };
+
+        // Version 1 only stored the banned accounts
+        if repr.version < 2 {
+            for account in repr.banned {
+                if !archive.bans.iter().any(|ban| ban.account == account) {
+                    archive.bans.push(BanRecord { account, ..Default::default() });
+                }
+            }
+        }
+        archive
+    }
+}
+
+impl GameRuntimeDataArchive {
+
+    /// Ban the account, the ban is stamped with the current time
+    pub fn ban(&mut self, account: Account, reason: Option<String>) -> &mut GameRuntimeDataArchive {
+        self.bans.retain(|ban| ban.account != account);
+        self.bans.push(BanRecord::new(account, reason));
+        self
+    }
+
+    /// Ban the account for the duration, the ban is lifted when it expires
+    pub fn ban_for(&mut self, account: Account, reason: Option<String>, duration: Duration) -> &mut GameRuntimeDataArchive {
+        self.bans.retain(|ban| ban.account != account);
+        self.bans.push(BanRecord::new(account, reason).expires_in(duration));
+        self
+    }
+
+    /// Put the account id on the allow list
+    pub fn allow(&mut self, id: String) -> &mut GameRuntimeDataArchive {
+        let id = process_id_text(id);
+        if !self.allowed.contains(&id) {
+            self.allowed.push(id);
+            self.allowed.sort();
+        }
+        self
+    }
+
+    /// Take the account id off the allow list
+    pub fn disallow(&mut self, id: String) -> &mut GameRuntimeDataArchive {
+        let id = process_id_text(id);
+        self.allowed.retain(|allowed| allowed != &id);
+        self
+    }
+
+    /// Enable or disable the allow list
+    pub fn allow_list(&mut self, enabled: bool) -> &mut GameRuntimeDataArchive {
+        self.allow_list_enabled = enabled;
+        self
+    }
+
+    /// Remember the player, replacing the record of the same account
+    pub fn remember(&mut self, player: KnownPlayer) -> &mut GameRuntimeDataArchive {
+        self.players.retain(|known| known.account != player.account);
+        self.players.push(player);
+        self
+    }
+}
+
+impl BanRecord {
+
+    /// Create a ban record stamped with the current time
+    pub fn new(account: Account, reason: Option<String>) -> BanRecord {
+        BanRecord { account, reason, banned_at: unix_now(), expires_at: None }
+    }
+
+    /// Lift the ban after the duration
+    pub fn expires_in(mut self, duration: Duration) -> BanRecord {
+        self.expires_at = Some(unix_now().saturating_add(duration.as_secs()));
+        self
+    }
+
+    /// Check if the ban was lifted
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= unix_now())
+    }
+
+    /// Seconds until the ban is lifted (Permanent if None)
+    pub fn remaining(&self) -> Option<u64> {
+        self.expires_at.map(|expires_at| expires_at.saturating_sub(unix_now()))
+    }
+}
+
+/// Seconds since the Unix epoch
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
 impl From<GameRuntimeDataArchive> for GameRuntimeData {
     fn from(archive: GameRuntimeDataArchive) -> Self {
-        let banned_mutex = Players::default();
-        entry_mutex!(banned_mutex, |guard| {
-            for account in archive.banned {
-                let player_info = Player::from(account.clone());
-                guard.entry(account).or_insert_with(|| player_info);
+        let data = GameRuntimeData::default();
+        entry_mutex!(data.players_banned, |guard| {
+            for ban in archive.bans.into_iter().filter(|ban| !ban.is_expired()) {
+                guard.insert(ban.account.clone(), ban);
             }
         });
-        GameRuntimeData {
-            players_banned : banned_mutex,
-            ..Self::default()
-        }
+        entry_mutex!(data.players_known, |guard| {
+            for known in &archive.players {
+                guard.insert(known.account.clone(), Player {
+                    account: known.account.clone(),
+                    customize: known.customize.clone(),
+                    previous_hash: None
+                });
+            }
+        });
+        entry_mutex!(data.seats, |guard| {
+            for known in archive.players {
+                if let Some(seat) = known.seat {
+                    guard.insert(known.account, seat);
+                }
+            }
+        });
+        entry_mutex!(data.stats, |guard| {
+            for stats in archive.stats {
+                guard.insert(stats.account.clone(), stats);
+            }
+        });
+        entry_mutex!(data.players_allowed, |guard| {
+            guard.extend(archive.allowed);
+        });
+        data.allow_list_enabled.store(archive.allow_list_enabled, SeqCst);
+        data
     }
 }
 
-impl From<GameRuntimeData> for GameRuntimeDataArchive {
-    fn from(data: GameRuntimeData) -> Self {
-        let mut banned = Vec::new();
-        entry_mutex!(data.players_online, |guard| {
-            for account in guard.keys().into_iter() {
-                banned.push(account.to_owned());
+impl From<&GameRuntimeData> for GameRuntimeDataArchive {
+    fn from(data: &GameRuntimeData) -> Self {
+        let mut archive = GameRuntimeDataArchive::default();
+
+        // Guests are never archived
+        entry_mutex!(data.players_banned, |guard| {
+            archive.bans = guard.values()
+                .filter(|ban| !ban.is_expired() && !ban.account.is_guest())
+                .cloned()
+                .collect();
+        });
+
+        let mut players: HashMap<Account, KnownPlayer> = HashMap::new();
+        entry_mutex!(data.players_known, |guard| {
+            for player in guard.values() {
+                players.insert(player.account.clone(), KnownPlayer {
+                    account: player.account.clone(),
+                    customize: player.customize.clone(),
+                    seat: None
+                });
             }
         });
+        entry_mutex!(data.seats, |guard| {
+            for (account, seat) in guard.iter().filter(|(account, _)| !account.is_guest()) {
+                players.entry(account.clone())
+                    .or_insert_with(|| KnownPlayer { account: account.clone(), ..Default::default() })
+                    .seat = Some(*seat);
+            }
+        });
+        archive.players = players.into_values().collect();
+        archive.stats = data.all_stats().into_iter().filter(|stats| !stats.account.is_guest()).collect();
+        archive.allow_list_enabled = data.allow_list_enabled.load(SeqCst);
+        archive.allowed = data.allowed_accounts();
 
-        GameRuntimeDataArchive {
-            banned
-        }
+        // Keep the archive stable between saves
+        archive.bans.sort_by(|a, b| a.account.id.cmp(&b.account.id));
+        archive.players.sort_by(|a, b| a.account.id.cmp(&b.account.id));
+        archive
+    }
+}
+
+impl From<GameRuntimeData> for GameRuntimeDataArchive {
+    fn from(data: GameRuntimeData) -> Self {
+        GameRuntimeDataArchive::from(&data)
     }
 }
\ No newline at end of file
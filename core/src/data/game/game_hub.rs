@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::Ordering::SeqCst;
+use log::{info, warn};
+use nogamepads::entry_mutex;
+use nogamepads::string_utils::process_id_text;
+use crate::data::game::game_runtime::GameRuntime;
+use crate::data::game::types::GameInfo;
+
+/// Name of the room used when a single runtime is hosted
+pub const DEFAULT_ROOM : &str = "default";
+
+/// Game hub
+/// Hosts multiple game runtimes on one service, each addressed by its room name
+#[derive(Default, Clone)]
+pub struct GameHub {
+    rooms: HashMap<String, Arc<Mutex<GameRuntime>>>,
+    default_room: Option<String>,
+}
+
+impl GameHub {
+
+    /// Create an empty hub
+    pub fn new() -> GameHub {
+        GameHub::default()
+    }
+
+    /// Create a hub that hosts a single runtime in the default room
+    pub fn single(runtime: Arc<Mutex<GameRuntime>>) -> GameHub {
+        let mut hub = GameHub::new();
+        hub.add_room(DEFAULT_ROOM.to_string(), runtime);
+        hub
+    }
+
+    /// Add a room, the first room added becomes the default room
+    pub fn add_room(&mut self, name: String, runtime: Arc<Mutex<GameRuntime>>) -> &mut GameHub {
+        let name = process_id_text(name);
+        if self.rooms.contains_key(&name) {
+            warn!("[Game Hub] Room \"{}\" already exists, replaced.", name);
+        } else {
+            info!("[Game Hub] Room \"{}\" added.", name);
+        }
+        if self.default_room.is_none() {
+            self.default_room = Some(name.clone());
+        }
+        self.rooms.insert(name, runtime);
+        self
+    }
+
+    /// Set the room used by clients that do not specify one
+    pub fn set_default_room(&mut self, name: String) -> &mut GameHub {
+        let name = process_id_text(name);
+        if self.rooms.contains_key(&name) {
+            self.default_room = Some(name);
+        } else {
+            warn!("[Game Hub] Set default room failed: Room \"{}\" not found!", name);
+        }
+        self
+    }
+
+    /// Get the runtime of the specified room
+    pub fn room(&self, name: &str) -> Option<Arc<Mutex<GameRuntime>>> {
+        self.rooms.get(&process_id_text(name.to_string())).cloned()
+    }
+
+    /// Get the runtime of the default room
+    pub fn default_room(&self) -> Option<Arc<Mutex<GameRuntime>>> {
+        self.default_room.as_ref()
+            .and_then(|name| self.rooms.get(name))
+            .cloned()
+    }
+
+    /// Returns all room names
+    pub fn room_names(&self) -> Vec<String> {
+        self.rooms.keys().cloned().collect()
+    }
+
+    /// Returns the game infos of all rooms
+    pub fn room_infos(&self) -> HashMap<String, GameInfo> {
+        let mut infos = HashMap::new();
+        for (name, runtime) in self.rooms.iter() {
+            entry_mutex!(runtime, |guard| {
+                infos.insert(name.clone(), guard.info.clone());
+            });
+        }
+        infos
+    }
+
+    /// Returns all runtimes
+    pub fn runtimes(&self) -> Vec<Arc<Mutex<GameRuntime>>> {
+        self.rooms.values().cloned().collect()
+    }
+
+    /// Close the games of all rooms
+    pub fn close_all(&self) {
+        for runtime in self.rooms.values() {
+            entry_mutex!(runtime, |guard| {
+                guard.close_game();
+            });
+        }
+    }
+
+    /// Check if the games of all rooms are closed
+    pub fn is_all_closed(&self) -> bool {
+        let mut closed = true;
+        for runtime in self.rooms.values() {
+            entry_mutex!(runtime, |guard| {
+                if !guard.data.close.load(SeqCst) {
+                    closed = false;
+                }
+            });
+        }
+        closed
+    }
+}
\ No newline at end of file
@@ -0,0 +1,240 @@
+use std::time::Duration;
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+/// Smoothed values closer than this to their target snap to it
+pub const SMOOTHING_SNAP: f64 = 0.001;
+
+/// Time a smoothed value takes to move one step toward its target (60 steps per second)
+pub const SMOOTHING_STEP: Duration = Duration::from_micros(16_667);
+
+/// Input processing of a key
+/// Filters applied to axis and direction values before they are stored, buttons are not processed
+/// Designed for the default range -1..1, values are processed in the order of the fields
+#[derive(Default, Clone, Serialize, Deserialize, Encode, Decode, PartialEq, Debug)]
+pub struct InputProcessing {
+
+    /// Clamp values into the range of the key instead of rejecting them
+    #[serde(default)]
+    pub clamp: bool,
+
+    /// Scale direction vectors longer than 1 back to the unit circle
+    #[serde(default)]
+    pub normalize: bool,
+
+    /// Values closer to the center are treated as 0 (Range: 0 - 1)
+    #[serde(default)]
+    pub dead_zone: f64,
+
+    /// How the dead zone of a direction is measured
+    #[serde(default)]
+    pub dead_zone_shape: DeadZoneShape,
+
+    /// Response curve applied after the dead zone
+    #[serde(default)]
+    pub curve: ResponseCurve,
+
+    /// Weight of the previous value in the exponential smoothing (Range: 0 - 1, 0 disables it)
+    /// The value moves one step toward the latest input when it arrives, then one step every SMOOTHING_STEP
+    #[serde(default)]
+    pub smoothing: f64,
+}
+
+/// Shape of the dead zone
+#[derive(Default, Clone, Copy, Serialize, Deserialize, Encode, Decode, PartialEq, Debug)]
+pub enum DeadZoneShape {
+
+    /// Measured on the length of the direction
+    #[default]
+    Radial,
+
+    /// Measured on each component separately
+    Axial,
+}
+
+/// Response curve
+/// Maps the magnitude of a value, the sign is kept
+#[derive(Default, Clone, Serialize, Deserialize, Encode, Decode, PartialEq, Debug)]
+pub enum ResponseCurve {
+
+    /// Output equals input
+    #[default]
+    Linear,
+
+    /// Output is the input raised to the power
+    Power(f64),
+
+    /// Output is interpolated between the points (Input, Output)
+    Points(Vec<(f64, f64)>),
+}
+
+impl InputProcessing {
+
+    /// Clamp values into the range of the key instead of rejecting them
+    pub fn clamp(&mut self, clamp: bool) -> &mut InputProcessing {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Scale direction vectors longer than 1 back to the unit circle
+    pub fn normalize(&mut self, normalize: bool) -> &mut InputProcessing {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Set the dead zone
+    pub fn dead_zone(&mut self, size: f64, shape: DeadZoneShape) -> &mut InputProcessing {
+        self.dead_zone = size.clamp(0.0, 0.99);
+        self.dead_zone_shape = shape;
+        self
+    }
+
+    /// Set the response curve
+    pub fn curve(&mut self, curve: ResponseCurve) -> &mut InputProcessing {
+        self.curve = curve;
+        self
+    }
+
+    /// Set the weight of the previous value in the exponential smoothing
+    pub fn smoothing(&mut self, smoothing: f64) -> &mut InputProcessing {
+        self.smoothing = smoothing.clamp(0.0, 0.99);
+        self
+    }
+
+    /// Process an axis value, the previous value is used for smoothing
+    pub fn process_axis(&self, value: f64, previous: f64, min: f64, max: f64) -> f64 {
+        if value.is_nan() { return value; }
+        self.step_axis(self.filter_axis(value, min, max), previous)
+    }
+
+    /// Process a direction value, the previous value is used for smoothing
+    pub fn process_direction(&self, value: (f64, f64), previous: (f64, f64), min: f64, max: f64) -> (f64, f64) {
+        if value.0.is_nan() || value.1.is_nan() { return value; }
+        self.step_direction(self.filter_direction(value, min, max), previous)
+    }
+
+    /// Apply all filters but the smoothing to an axis value
+    pub fn filter_axis(&self, value: f64, min: f64, max: f64) -> f64 {
+        let mut value = if self.clamp { value.clamp(min, max) } else { value };
+        value = self.apply_dead_zone(value);
+        self.curve.apply(value)
+    }
+
+    /// Apply all filters but the smoothing to a direction value
+    pub fn filter_direction(&self, value: (f64, f64), min: f64, max: f64) -> (f64, f64) {
+        let (mut x, mut y) = value;
+        if self.clamp {
+            x = x.clamp(min, max);
+            y = y.clamp(min, max);
+        }
+
+        let length = x.hypot(y);
+        if self.normalize && length > 1.0 {
+            x /= length;
+            y /= length;
+        }
+
+        match self.dead_zone_shape {
+            DeadZoneShape::Radial => {
+                let length = x.hypot(y);
+                if length > 0.0 {
+                    let scale = self.curve.apply(self.apply_dead_zone(length)) / length;
+                    x *= scale;
+                    y *= scale;
+                }
+            }
+            DeadZoneShape::Axial => {
+                x = self.curve.apply(self.apply_dead_zone(x));
+                y = self.curve.apply(self.apply_dead_zone(y));
+            }
+        }
+        (x, y)
+    }
+
+    /// Move a smoothed axis value one step toward the target
+    pub fn step_axis(&self, target: f64, current: f64) -> f64 {
+        self.smooth(target, current)
+    }
+
+    /// Move a smoothed direction value one step toward the target
+    pub fn step_direction(&self, target: (f64, f64), current: (f64, f64)) -> (f64, f64) {
+        let (x, y) = (self.smooth(target.0, current.0), self.smooth(target.1, current.1));
+        if (x - target.0).hypot(y - target.1) < SMOOTHING_SNAP { target } else { (x, y) }
+    }
+
+    /// Move a smoothed axis value toward the target for the steps that fit in the elapsed time
+    pub fn settle_axis(&self, target: f64, value: f64, elapsed: Duration) -> f64 {
+        if self.smoothing <= 0.0 { return target; }
+        let settled = target + (value - target) * self.remaining_weight(elapsed);
+        if (settled - target).abs() < SMOOTHING_SNAP { target } else { settled }
+    }
+
+    /// Move a smoothed direction value toward the target for the steps that fit in the elapsed time
+    pub fn settle_direction(&self, target: (f64, f64), value: (f64, f64), elapsed: Duration) -> (f64, f64) {
+        if self.smoothing <= 0.0 { return target; }
+        let weight = self.remaining_weight(elapsed);
+        let (x, y) = (target.0 + (value.0 - target.0) * weight, target.1 + (value.1 - target.1) * weight);
+        if (x - target.0).hypot(y - target.1) < SMOOTHING_SNAP { target } else { (x, y) }
+    }
+
+    /// Weight of the value left after the elapsed steps, partial steps are interpolated
+    fn remaining_weight(&self, elapsed: Duration) -> f64 {
+        self.smoothing.powf(elapsed.as_secs_f64() / SMOOTHING_STEP.as_secs_f64())
+    }
+
+    /// Values inside the dead zone become 0, the rest is rescaled to start from 0
+    fn apply_dead_zone(&self, value: f64) -> f64 {
+        if self.dead_zone <= 0.0 { return value; }
+
+        let magnitude = value.abs();
+        if magnitude <= self.dead_zone {
+            0.0
+        } else {
+            value.signum() * (magnitude - self.dead_zone) / (1.0 - self.dead_zone)
+        }
+    }
+
+    fn smooth(&self, value: f64, previous: f64) -> f64 {
+        if self.smoothing <= 0.0 { return value; }
+        let smoothed = previous + (value - previous) * (1.0 - self.smoothing);
+        if (smoothed - value).abs() < SMOOTHING_SNAP { value } else { smoothed }
+    }
+
+    /// Check if any filter is enabled
+    pub fn is_enabled(&self) -> bool {
+        self != &InputProcessing::default()
+    }
+}
+
+impl ResponseCurve {
+
+    /// Map the value, the sign is kept
+    pub fn apply(&self, value: f64) -> f64 {
+        let magnitude = value.abs();
+        let mapped = match self {
+            ResponseCurve::Linear => { magnitude }
+            ResponseCurve::Power(exponent) => { magnitude.powf(*exponent) }
+            ResponseCurve::Points(points) => { Self::interpolate(points, magnitude) }
+        };
+        value.signum() * mapped
+    }
+
+    fn interpolate(points: &[(f64, f64)], input: f64) -> f64 {
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return input;
+        };
+        if input <= first.0 { return first.1; }
+        if input >= last.0 { return last.1; }
+
+        points.windows(2)
+            .find(|pair| input <= pair[1].0)
+            .map(|pair| {
+                let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                if x1 == x0 { y1 } else { y0 + (input - x0) / (x1 - x0) * (y1 - y0) }
+            })
+            .unwrap_or(input)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,276 @@
+use std::collections::VecDeque;
+use log::trace;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use crate::data::message::message_prompt::PromptResult;
+use crate::data::message::message_touch::TouchData;
+use crate::data::player::player_data::{Account, Customize};
+
+type KeyHandler = Box<dyn FnMut(&Account, u8) + Send>;
+type AxisHandler = Box<dyn FnMut(&Account, u8, f64) + Send>;
+type DirectionHandler = Box<dyn FnMut(&Account, u8, (f64, f64)) + Send>;
+type TouchHandler = Box<dyn FnMut(&Account, u8, &TouchData) + Send>;
+type MessageHandler = Box<dyn FnMut(&Account, &str) + Send>;
+type PlayerHandler = Box<dyn FnMut(&Account) + Send>;
+type PromptHandler = Box<dyn FnMut(&Account, u32, &PromptResult) + Send>;
+type ProfileHandler = Box<dyn FnMut(&Account, &Customize) + Send>;
+
+/// Stream of the events observed on a game runtime
+/// The stream ends when the game is closed
+pub type GameEventStream = UnboundedReceiverStream<ObservedEvent>;
+
+/// Event observed on the game runtime
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObservedEvent {
+    Pressed(Account, u8),
+    Released(Account, u8),
+    AxisChanged(Account, u8, f64),
+    DirectionChanged(Account, u8, (f64, f64)),
+    Touched(Account, u8, TouchData),
+    Message(Account, String),
+    PromptResolved(Account, u32, PromptResult),
+    PlayerJoined(Account),
+    PlayerLeft(Account),
+    PlayerKicked(Account),
+    PlayerBanned(Account),
+    ProfileUpdated(Account, Customize),
+    LockChanged(bool),
+    ConnectionError(Account, String),
+}
+
+/// Handlers registered on the observer
+/// They are taken out of the observer while dispatching, so they can run without the runtime locked
+#[derive(Default)]
+pub(crate) struct ObserverHandlers {
+    on_pressed: Vec<KeyHandler>,
+    on_released: Vec<KeyHandler>,
+    on_axis_changed: Vec<AxisHandler>,
+    on_direction_changed: Vec<DirectionHandler>,
+    on_touch: Vec<TouchHandler>,
+    on_message: Vec<MessageHandler>,
+    on_prompt_resolved: Vec<PromptHandler>,
+    on_player_joined: Vec<PlayerHandler>,
+    on_player_left: Vec<PlayerHandler>,
+    on_profile_updated: Vec<ProfileHandler>,
+}
+
+/// Game observer
+/// Holds the handlers registered by the game, they are invoked when the game dispatches the recorded events
+#[derive(Default)]
+pub struct GameObserver {
+    pending: VecDeque<ObservedEvent>,
+    subscribers: Vec<UnboundedSender<ObservedEvent>>,
+    handlers: ObserverHandlers,
+
+    // Set while the handlers are taken out for a dispatch
+    dispatching: bool,
+
+    // Set if the handlers were cleared during the dispatch, so the taken ones are not put back
+    cleared: bool,
+}
+
+impl GameObserver {
+
+    /// Register a handler invoked when a button is pressed
+    pub fn on_pressed(&mut self, handler: impl FnMut(&Account, u8) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_pressed.push(Box::new(handler));
+        self
+    }
+
+    /// Register a handler invoked when a button is released
+    pub fn on_released(&mut self, handler: impl FnMut(&Account, u8) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_released.push(Box::new(handler));
+        self
+    }
+
+    /// Register a handler invoked when the value of an axis is changed
+    pub fn on_axis_changed(&mut self, handler: impl FnMut(&Account, u8, f64) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_axis_changed.push(Box::new(handler));
+        self
+    }
+
+    /// Register a handler invoked when the value of a direction is changed
+    pub fn on_direction_changed(&mut self, handler: impl FnMut(&Account, u8, (f64, f64)) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_direction_changed.push(Box::new(handler));
+        self
+    }
+
+    /// Register a handler invoked when a finger touched, moved on or left a touchpad
+    pub fn on_touch(&mut self, handler: impl FnMut(&Account, u8, &TouchData) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_touch.push(Box::new(handler));
+        self
+    }
+
+    /// Register a handler invoked when a player sends a plain message
+    pub fn on_message(&mut self, handler: impl FnMut(&Account, &str) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_message.push(Box::new(handler));
+        self
+    }
+
+    /// Register a handler invoked when a text prompt is replied, timed out, cancelled or abandoned
+    pub fn on_prompt_resolved(&mut self, handler: impl FnMut(&Account, u32, &PromptResult) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_prompt_resolved.push(Box::new(handler));
+        self
+    }
+
+    /// Register a handler invoked when a player joined the game
+    pub fn on_player_joined(&mut self, handler: impl FnMut(&Account) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_player_joined.push(Box::new(handler));
+        self
+    }
+
+    /// Register a handler invoked when a player left the game
+    pub fn on_player_left(&mut self, handler: impl FnMut(&Account) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_player_left.push(Box::new(handler));
+        self
+    }
+
+    /// Register a handler invoked when a player changed its custom information
+    pub fn on_profile_updated(&mut self, handler: impl FnMut(&Account, &Customize) + Send + 'static) -> &mut GameObserver {
+        self.handlers.on_profile_updated.push(Box::new(handler));
+        self
+    }
+
+    /// Subscribe to all events as an async stream
+    /// Unlike handlers, the stream receives events as soon as they are recorded
+    pub fn subscribe(&mut self) -> GameEventStream {
+        let (sender, receiver) = unbounded_channel();
+        self.subscribers.push(sender);
+        UnboundedReceiverStream::new(receiver)
+    }
+
+    /// Remove all registered handlers and pending events
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.handlers = ObserverHandlers::default();
+        self.cleared = self.dispatching;
+    }
+
+    /// Close all subscribed streams
+    pub fn close_streams(&mut self) {
+        self.subscribers.clear();
+    }
+
+    /// Check if any handler is registered
+    /// Events are only recorded while observing, so an unused observer never piles them up
+    pub fn is_observing(&self) -> bool {
+        self.dispatching || !self.handlers.is_empty()
+    }
+
+    /// Count of events waiting to be dispatched
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Record an event, it will be handled on the next dispatch
+    pub(crate) fn record(&mut self, event: ObservedEvent) {
+        self.subscribers.retain(|subscriber| subscriber.send(event.clone()).is_ok());
+        if self.is_observing() {
+            self.pending.push_back(event);
+        }
+    }
+
+    /// Invoke the handlers of all recorded events, returns the count of dispatched events
+    pub fn dispatch(&mut self) -> usize {
+        match self.begin_dispatch() {
+            Some((events, mut handlers)) => {
+                let count = handlers.dispatch(events);
+                self.end_dispatch(handlers);
+                count
+            }
+            None => { 0 }
+        }
+    }
+
+    /// Take the recorded events and the handlers out of the observer
+    /// Returns None if another dispatch is running, its events stay recorded
+    pub(crate) fn begin_dispatch(&mut self) -> Option<(VecDeque<ObservedEvent>, ObserverHandlers)> {
+        if self.dispatching {
+            return None;
+        }
+        self.dispatching = true;
+        self.cleared = false;
+        Some((std::mem::take(&mut self.pending), std::mem::take(&mut self.handlers)))
+    }
+
+    /// Put the handlers back after a dispatch, after the handlers registered during it
+    pub(crate) fn end_dispatch(&mut self, mut handlers: ObserverHandlers) {
+        self.dispatching = false;
+        if !self.cleared {
+            handlers.append(std::mem::take(&mut self.handlers));
+            self.handlers = handlers;
+        }
+        self.cleared = false;
+    }
+}
+
+impl ObserverHandlers {
+
+    fn is_empty(&self) -> bool {
+        self.on_pressed.is_empty()
+            && self.on_released.is_empty()
+            && self.on_axis_changed.is_empty()
+            && self.on_direction_changed.is_empty()
+            && self.on_touch.is_empty()
+            && self.on_message.is_empty()
+            && self.on_prompt_resolved.is_empty()
+            && self.on_player_joined.is_empty()
+            && self.on_player_left.is_empty()
+            && self.on_profile_updated.is_empty()
+    }
+
+    fn append(&mut self, mut other: ObserverHandlers) {
+        self.on_pressed.append(&mut other.on_pressed);
+        self.on_released.append(&mut other.on_released);
+        self.on_axis_changed.append(&mut other.on_axis_changed);
+        self.on_direction_changed.append(&mut other.on_direction_changed);
+        self.on_touch.append(&mut other.on_touch);
+        self.on_message.append(&mut other.on_message);
+        self.on_prompt_resolved.append(&mut other.on_prompt_resolved);
+        self.on_player_joined.append(&mut other.on_player_joined);
+        self.on_player_left.append(&mut other.on_player_left);
+        self.on_profile_updated.append(&mut other.on_profile_updated);
+    }
+
+    /// Invoke the handlers of the events, returns the count of dispatched events
+    pub(crate) fn dispatch(&mut self, events: VecDeque<ObservedEvent>) -> usize {
+        let count = events.len();
+        for event in events {
+            trace!("[Game Observer] Dispatch: {:?}", event);
+            match &event {
+                ObservedEvent::Pressed(account, key) => {
+                    self.on_pressed.iter_mut().for_each(|handler| handler(account, *key));
+                }
+                ObservedEvent::Released(account, key) => {
+                    self.on_released.iter_mut().for_each(|handler| handler(account, *key));
+                }
+                ObservedEvent::AxisChanged(account, key, axis) => {
+                    self.on_axis_changed.iter_mut().for_each(|handler| handler(account, *key, *axis));
+                }
+                ObservedEvent::DirectionChanged(account, key, dir) => {
+                    self.on_direction_changed.iter_mut().for_each(|handler| handler(account, *key, *dir));
+                }
+                ObservedEvent::Touched(account, key, touch) => {
+                    self.on_touch.iter_mut().for_each(|handler| handler(account, *key, touch));
+                }
+                ObservedEvent::Message(account, msg) => {
+                    self.on_message.iter_mut().for_each(|handler| handler(account, msg));
+                }
+                ObservedEvent::PromptResolved(account, id, result) => {
+                    self.on_prompt_resolved.iter_mut().for_each(|handler| handler(account, *id, result));
+                }
+                ObservedEvent::PlayerJoined(account) => {
+                    self.on_player_joined.iter_mut().for_each(|handler| handler(account));
+                }
+                ObservedEvent::PlayerLeft(account) => {
+                    self.on_player_left.iter_mut().for_each(|handler| handler(account));
+                }
+                ObservedEvent::ProfileUpdated(account, customize) => {
+                    self.on_profile_updated.iter_mut().for_each(|handler| handler(account, customize));
+                }
+                _ => {}
+            }
+        }
+        count
+    }
+}
\ No newline at end of file
@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+use log::{info, warn};
+use nogamepads::entry_mutex;
+use crate::data::game::game_data::NicknamePolicy;
+use crate::data::game::game_observer::ObservedEvent;
+use crate::data::game::game_runtime::{ControlEvent, GameRuntime, GameRuntimeData};
+use crate::data::message::message_enums::GameMessage::{ProfileChanged, ProfileRejected};
+use crate::data::player::player_data::{Account, Customize, Player};
+use crate::data::player::player_profile;
+use crate::data::player::player_profile::{nickname_key, suffixed, ProfileError};
+use crate::service::service_types::ServiceType;
+
+impl GameRuntimeData {
+
+    /// Get the nickname policy
+    pub fn nickname_policy(&self) -> NicknamePolicy {
+        self.nickname_policy
+    }
+
+    /// Get the custom information of the online player
+    pub fn customize_of(&self, account: &Account) -> Option<Customize> {
+        let mut result = None;
+        entry_mutex!(self.players_online, |guard| {
+            result = guard.get(account).and_then(|player| player.customize.clone());
+        });
+        result
+    }
+
+    /// Find the online account by its nickname (Case insensitive)
+    pub fn account_by_nickname(&self, nickname: &str) -> Option<Account> {
+        let key = nickname_key(nickname);
+        let mut result = None;
+        entry_mutex!(self.players_online, |guard| {
+            result = guard.values()
+                .find(|player| player.customize.as_ref().is_some_and(|c| nickname_key(&c.nickname) == key))
+                .map(|player| player.account.clone());
+        });
+        result
+    }
+
+    /// Validate the custom information of the account, and keep its nickname apart from the other online players by policy
+    pub(crate) fn settle_profile(&self, account: &Account, customize: &mut Customize) -> Result<(), ProfileError> {
+        player_profile::validate(customize)?;
+        if customize.nickname.is_empty() || self.nickname_policy == NicknamePolicy::Any {
+            return Ok(());
+        }
+
+        let mut taken = HashSet::new();
+        entry_mutex!(self.players_online, |guard| {
+            taken = guard.values()
+                .filter(|player| &player.account != account)
+                .filter_map(|player| player.customize.as_ref())
+                .map(|c| nickname_key(&c.nickname))
+                .collect();
+        });
+        if !taken.contains(&nickname_key(&customize.nickname)) {
+            return Ok(());
+        }
+
+        match self.nickname_policy {
+            NicknamePolicy::Unique => { Err(ProfileError::NicknameTaken) }
+            _ => {
+                // Only finitely many nicknames are taken, so a free number is always found
+                customize.nickname = (2..)
+                    .map(|number| suffixed(&customize.nickname, number))
+                    .find(|nickname| !taken.contains(&nickname_key(nickname)))
+                    .unwrap_or_default();
+                Ok(())
+            }
+        }
+    }
+}
+
+impl GameRuntime {
+
+    /// Replace the custom information of the online player
+    /// The profile is settled as if the player sent it, returns false if it is not accepted
+    pub fn change_profile(&mut self, account: &Account, customize: Customize) -> bool {
+        if !self.data.is_account_online(account) {
+            warn!("[Game Runtime] Change profile failed: Account \"{}\" not online!", account.id);
+            return false;
+        }
+        match self.apply_profile(account, customize) {
+            Ok(_) => { true }
+            Err(why) => {
+                warn!("[Game Runtime] Change profile of \"{}\" failed: {:?}", account.id, why);
+                false
+            }
+        }
+    }
+
+    /// Apply the profile update sent by the controller of the account, it is told if the profile is not accepted
+    pub(crate) fn update_profile(&mut self, account: &Account, customize: Customize, service_type: ServiceType) {
+        if !self.data.is_account_online(account) {
+            return;
+        }
+        if let Err(why) = self.apply_profile(account, customize) {
+            warn!("[Game Runtime] Profile update from \"{}\" rejected: {:?}", account.id, why);
+            self.send_game_message(account, ProfileRejected(why), service_type);
+        }
+    }
+
+    fn apply_profile(&mut self, account: &Account, mut customize: Customize) -> Result<Customize, ProfileError> {
+        self.data.settle_profile(account, &mut customize)?;
+
+        // Known players keep their own color, the team color is applied while online
+        if !account.is_guest() {
+            self.data.remember_player(&Player { account: account.clone(), customize: Some(customize.clone()), previous_hash: None });
+        }
+
+        let team = self.data.team_of(account);
+        let mut accepted = customize.clone();
+        entry_mutex!(self.data.players_online, |guard| {
+            if let Some(player) = guard.get_mut(account) {
+                player.customize = Some(customize);
+                if let Some(team) = &team {
+                    self.data.apply_team_color(player, team);
+                }
+                accepted = player.customize.clone().unwrap_or_default();
+            }
+        });
+
+        info!("[Game Runtime] Player \"{}\" updated its profile: \"{}\"", account, accepted.nickname);
+        self.control.events.push_back((account.clone(), ControlEvent::ProfileUpdated(accepted.clone())));
+        self.control.observer.record(ObservedEvent::ProfileUpdated(account.clone(), accepted.clone()));
+        if let Some(service_type) = self.data.get_service_type(account) {
+            self.send_game_message(account, ProfileChanged(accepted.clone()), service_type);
+        }
+        Ok(accepted)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use log::{trace, warn};
+use crate::data::game::game_observer::ObservedEvent;
+use crate::data::game::game_runtime::GameRuntime;
+use crate::data::message::message_enums::GameMessage;
+use crate::data::message::message_prompt::{PromptResult, TextPrompt};
+use crate::data::player::player_data::Account;
+
+/// Time the results of resolved prompts are kept, results not taken by then are dropped
+pub const PROMPT_RESULT_LIFETIME: Duration = Duration::from_secs(300);
+
+/// Text prompts sent by the game
+/// Pending prompts wait for the replies, resolved prompts keep their results until taken
+#[derive(Default)]
+pub struct GamePrompts {
+    pending: HashMap<u32, PendingPrompt>,
+    results: HashMap<u32, (Account, PromptResult, Instant)>,
+    next_id: u32,
+}
+
+struct PendingPrompt {
+    account: Account,
+    prompt: TextPrompt,
+    deadline: Option<Instant>,
+}
+
+impl GameRuntime {
+
+    /// Ask the online account to type something, returns the request id
+    /// The prompt times out after the timeout (Never if not set)
+    pub fn prompt(&mut self, account: &Account, mut prompt: TextPrompt, timeout: Option<Duration>) -> Option<u32> {
+        let Some(service_type) = self.data.online_service_type(account) else {
+            warn!("[Game Runtime] Prompt failed: Account \"{}\" not online!", account.id);
+            return None;
+        };
+
+        self.prompts.next_id = self.prompts.next_id.wrapping_add(1);
+        let id = self.prompts.next_id;
+        prompt.id = id;
+
+        self.send_game_message(account, GameMessage::Prompt(prompt.clone()), service_type);
+        self.prompts.pending.insert(id, PendingPrompt {
+            account: account.clone(),
+            prompt,
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+        });
+        trace!("[Game Runtime] Prompt {} sent to \"{}\"", id, account.id);
+        Some(id)
+    }
+
+    /// Cancel the pending prompt, the controller closes its text field
+    pub fn cancel_prompt(&mut self, id: u32) -> bool {
+        self.close_prompt(id, PromptResult::Cancelled)
+    }
+
+    /// Take the result of the prompt, returns None while it is pending or if it is unknown
+    pub fn take_prompt_result(&mut self, id: u32) -> Option<PromptResult> {
+        self.expire_prompts();
+        self.prompts.results.remove(&id).map(|(_, result, _)| result)
+    }
+
+    /// Check if the prompt is waiting for the reply, prompts past their deadlines are not
+    pub fn is_prompt_pending(&self, id: u32) -> bool {
+        let now = Instant::now();
+        self.prompts.pending.get(&id)
+            .is_some_and(|pending| pending.deadline.is_none_or(|deadline| deadline > now))
+    }
+
+    /// Resolve the prompts past their deadlines as timed out, and drop the results kept longer than PROMPT_RESULT_LIFETIME
+    /// Called when results are taken, messages are received and snapshots are taken
+    pub fn expire_prompts(&mut self) {
+        let now = Instant::now();
+        let expired: Vec<u32> = self.prompts.pending.iter()
+            .filter(|(_, pending)| pending.deadline.is_some_and(|deadline| deadline <= now))
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in expired {
+            self.close_prompt(id, PromptResult::TimedOut);
+        }
+
+        self.prompts.results.retain(|_, (_, _, resolved)| now.duration_since(*resolved) < PROMPT_RESULT_LIFETIME);
+    }
+
+    /// Resolve the reply of the controller
+    pub(crate) fn reply_prompt(&mut self, account: &Account, id: u32, text: Option<String>) {
+        let Some(pending) = self.prompts.pending.get(&id) else {
+            trace!("[Game Runtime] Reply to unknown prompt {} from \"{}\" ignored", id, account.id);
+            return;
+        };
+        if &pending.account != account {
+            warn!("[Game Runtime] Reply to prompt {} from \"{}\" ignored: Prompt not sent to the account", id, account.id);
+            return;
+        }
+
+        let result = match text {
+            None => { PromptResult::Dismissed }
+            Some(text) => { PromptResult::Submitted(pending.prompt.limit(text)) }
+        };
+        self.resolve_prompt(id, result);
+    }
+
+    /// Resolve all pending prompts of the account, used when it leaves
+    pub(crate) fn disconnect_prompts(&mut self, account: &Account) {
+        let ids: Vec<u32> = self.prompts.pending.iter()
+            .filter(|(_, pending)| &pending.account == account)
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in ids {
+            self.resolve_prompt(id, PromptResult::Disconnected);
+        }
+    }
+
+    /// Resolve the prompt and tell the controller to close its text field
+    fn close_prompt(&mut self, id: u32, result: PromptResult) -> bool {
+        let Some(pending) = self.prompts.pending.get(&id) else {
+            return false;
+        };
+        let account = pending.account.clone();
+        if let Some(service_type) = self.data.get_service_type(&account) {
+            self.send_game_message(&account, GameMessage::CancelPrompt(id), service_type);
+        }
+        self.resolve_prompt(id, result);
+        true
+    }
+
+    fn resolve_prompt(&mut self, id: u32, result: PromptResult) {
+        if let Some(pending) = self.prompts.pending.remove(&id) {
+            trace!("[Game Runtime] Prompt {} of \"{}\" resolved: {:?}", id, pending.account.id, result);
+            self.control.observer.record(ObservedEvent::PromptResolved(pending.account.clone(), id, result.clone()));
+            self.prompts.results.insert(id, (pending.account, result, Instant::now()));
+        }
+    }
+}
\ No newline at end of file
@@ -1,18 +1,26 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::SeqCst;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use log::{info, trace, warn};
 use nogamepads::entry_mutex;
-use crate::data::game::game_data::GameControlData;
-use crate::data::game::types::{GameInfo, Players};
-use crate::data::message::message_enums::{JoinFailedMessage, ControlMessage, ExitReason, GameMessage};
-use crate::data::message::message_enums::JoinFailedMessage::{ContainIdenticalPlayer, GameLocked, PlayerBanned};
-use crate::data::message::message_enums::ControlMessage::{Axis, Dir, Msg, Pressed, Released};
+use nogamepads::string_utils::process_id_text;
+use crate::data::game::game_data::{BanRecord, GameControlData, GameRuntimeDataArchive, KeyKind, NicknamePolicy, TeamData, TeamPolicy};
+use crate::data::game::game_observer::{GameEventStream, GameObserver, ObservedEvent};
+use crate::data::game::game_prompt::GamePrompts;
+use crate::data::game::game_stats::PlayerStats;
+use crate::data::game::types::{GameInfo, Players, SmoothingTargets};
+use crate::data::message::message_enums::{JoinFailedMessage, ControlMessage, ExitReason, GameMessage, InputError};
+use crate::data::message::message_enums::JoinFailedMessage::{ContainIdenticalPlayer, GameFull, GameLocked, GuestsNotAllowed, InvalidProfile, KickCooldown, NotAllowed, PlayerBanned};
+use crate::data::message::message_enums::ControlMessage::{Admin, Axis, Dir, Motion, Msg, Pressed, PromptReply, Released, Touch, UpdateProfile};
 use crate::data::message::message_enums::ExitReason::{YouAreBanned, YouAreKicked};
-use crate::data::message::message_enums::GameMessage::{EventTrigger, LetExit};
+use crate::data::message::message_enums::GameMessage::{EventTrigger, InputRejected, LetExit, TeamChanged};
+use crate::data::message::message_haptics::HapticPattern;
+use crate::data::message::message_sensors::MotionData;
+use crate::data::message::message_touch::{TouchData, TouchPhase, TouchPoint};
 use crate::data::message::traits::MessageManager;
-use crate::data::player::player_data::{Account, Player};
+use crate::data::player::player_data::{Account, Customize, Player};
 use crate::service::service_types::ServiceType;
 use crate::service::service_types::ServiceType::TCPConnection;
 
@@ -23,6 +31,7 @@ pub struct GameRuntime {
     pub info: GameInfo,
     pub data: GameRuntimeData,
     pub control: GameControlRuntime,
+    pub(crate) prompts: GamePrompts,
 
     pub writer_count: i32,
     pub reader_count: i32,
@@ -34,32 +43,110 @@ pub struct GameRuntimeData {
     pub(crate) send: HashMap<(ServiceType, Account), VecDeque<(Account, GameMessage)>>,
 
     pub(crate) players_online: Players,
-    pub(crate) players_banned: Players,
+    pub(crate) players_banned: Mutex<HashMap<Account, BanRecord>>,
+    pub(crate) kick_cooldowns: Mutex<HashMap<Account, Instant>>,
+    pub(crate) players_known: Players,
+    pub(crate) players_allowed: Mutex<HashSet<String>>,
+    pub(crate) admins: HashSet<Account>,
     pub(crate) account_service_type: Mutex<HashMap<Account, ServiceType>>,
 
+    pub(crate) max_players: Option<u8>,
+    pub(crate) seats: Mutex<HashMap<Account, u8>>,
+
+    pub(crate) teams: Vec<TeamData>,
+    pub(crate) team_policy: TeamPolicy,
+    pub(crate) team_members: Mutex<HashMap<Account, String>>,
+    pub(crate) team_cursor: usize,
+
+    pub(crate) nickname_policy: NicknamePolicy,
+
+    pub(crate) exit_reasons: Mutex<HashMap<Account, ExitReason>>,
+    pub(crate) stats: Mutex<HashMap<Account, PlayerStats>>,
+
     pub locked: AtomicBool,
+    pub allow_list_enabled: AtomicBool,
+    pub guests_allowed: AtomicBool,
     pub close: AtomicBool,
 }
 
+/// Event popped from the game runtime
+/// Either a control message sent by the player, or a change of its lifecycle
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlEvent {
+    /// Control message sent by the player
+    Control(ControlMessage),
+
+    /// Player joined the game
+    Joined,
+
+    /// Player left the game, by itself or at the request of the game
+    Left(ExitReason),
+
+    /// Player lost its connection without exiting
+    TimedOut,
+
+    /// Player left after being kicked
+    Kicked,
+
+    /// Player left after being banned
+    Banned,
+
+    /// Player changed its custom information, with the profile accepted by the game
+    ProfileUpdated(Customize),
+}
+
+impl From<ExitReason> for ControlEvent {
+    fn from(reason: ExitReason) -> Self {
+        match reason {
+            YouAreKicked => { ControlEvent::Kicked }
+            YouAreBanned => { ControlEvent::Banned }
+            ExitReason::Err => { ControlEvent::TimedOut }
+            reason => { ControlEvent::Left(reason) }
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct GameControlRuntime {
     pub(crate) keys: GameControlData,
     pub(crate) directions : HashMap<u8, HashMap<Account, (f64, f64)>>,
     pub(crate) axes : HashMap<u8, HashMap<Account, f64>>,
+    pub(crate) axis_targets : SmoothingTargets<f64>,
+    pub(crate) direction_targets : SmoothingTargets<(f64, f64)>,
     pub(crate) button : HashMap<u8, HashMap<Account, bool>>,
-    pub(crate) events : VecDeque<(Account, ControlMessage)>
+    pub(crate) presses : HashMap<u8, HashMap<Account, u32>>,
+    pub(crate) releases : HashMap<u8, HashMap<Account, u32>>,
+    pub(crate) motions : HashMap<Account, MotionData>,
+    pub(crate) touches : HashMap<u8, HashMap<Account, BTreeMap<u32, TouchPoint>>>,
+    pub(crate) events : VecDeque<(Account, ControlEvent)>,
+    pub(crate) observer : GameObserver
 }
 
 impl GameRuntime {
 
     /// Attempt to have the specified player join the game
-    pub fn try_join_player(&mut self, player: Player) -> Result<(), JoinFailedMessage> {
-        let join = self.can_join_game(&player.account);
+    /// Returns the player as it joined, with its profile settled and the team color applied
+    pub fn try_join_player(&mut self, mut player: Player) -> Result<Player, JoinFailedMessage> {
+        let join = self.can_join_game(&player.account).and_then(|seat| match player.customize.as_mut() {
+            Some(customize) => { self.data.settle_profile(&player.account, customize).map(|_| seat).map_err(InvalidProfile) }
+            None => { Ok(seat) }
+        });
         match join {
-            Ok(_) => {
+            Ok(seat) => {
+                self.data.take_seat(&player.account, seat);
+                if !player.is_guest() {
+                    self.data.remember_player(&player);
+                }
+                self.data.begin_session(&player.account);
+                let team = self.data.assign_team_on_join(&player.account);
+                if let Some(team) = &team {
+                    self.data.apply_team_color(&mut player, team);
+                }
                 self.data.sign_player_online_status(&player, TCPConnection, true);
-                trace!("[Game Runtime] Player \"{}\" joined", player.account);
-                Ok(())
+                trace!("[Game Runtime] Player \"{}\" joined at seat {}", player.account, seat);
+                self.control.events.push_back((player.account.clone(), ControlEvent::Joined));
+                self.control.observer.record(ObservedEvent::PlayerJoined(player.account.clone()));
+                Ok(player)
             }
             Err(why) => {
                 warn!("[Game Runtime] Player \"{}\" join failed: {:?}", player.account, why);
@@ -68,24 +155,57 @@ impl GameRuntime {
         }
     }
 
-    fn can_join_game(&self, account: &Account) -> Result<bool, JoinFailedMessage> {
+    fn can_join_game(&self, account: &Account) -> Result<u8, JoinFailedMessage> {
 
         if self.is_game_locked() {
             Err(GameLocked)
-        } else if self.data.is_account_banned(account) {
-            Err(PlayerBanned)
+        } else if account.is_guest() && !self.are_guests_allowed() {
+            Err(GuestsNotAllowed)
+        } else if let Some(ban) = self.data.ban_of(account) {
+            Err(PlayerBanned(ban.reason.clone(), ban.remaining()))
+        } else if let Some(cooldown) = self.data.kick_cooldown_of(account) {
+            // Round up, so the player never retries a moment too early
+            Err(KickCooldown(cooldown.as_secs() + u64::from(cooldown.subsec_nanos() > 0)))
+        } else if !self.data.is_account_allowed(account) {
+            Err(NotAllowed)
         } else if self.data.is_account_online(account) {
             Err(ContainIdenticalPlayer)
+        } else if let Some(seat) = self.data.find_seat(account) {
+            Ok(seat)
         } else {
-            Ok(true)
+            Err(GameFull)
         }
     }
 
+    /// Mark a player as left, called when its connection is closed
+    /// The event depends on the exit reason recorded before, a player without one is considered timed out
+    pub fn leave_player(&mut self, player: &Player, service_type: ServiceType) {
+        if self.data.is_account_online(&player.account) {
+            self.control.release_all(&player.account);
+            self.data.sign_player_online_status(player, service_type, false);
+            let reason = self.data.take_exit_reason(&player.account).unwrap_or_default();
+            self.data.end_session(&player.account, reason.clone());
+            trace!("[Game Runtime] Player \"{}\" left: {:?}", player.account, reason);
+            self.control.events.push_back((player.account.clone(), ControlEvent::from(reason)));
+            self.control.observer.record(ObservedEvent::PlayerLeft(player.account.clone()));
+            self.disconnect_prompts(&player.account);
+            if player.is_guest() {
+                self.data.forget_guest(&player.account);
+            }
+        }
+    }
+
+    /// Report an error that occurred on the connection of an account
+    pub fn report_connection_error(&mut self, account: &Account, error: String) {
+        self.control.observer.record(ObservedEvent::ConnectionError(account.clone(), error));
+    }
+
     /// Request an account to exit
     pub fn let_account_exit(&mut self, account: &Account, reason: ExitReason, service_type: ServiceType) {
         // Send a leave message to the pad_client and wait for it to actively disconnect
         if self.data.is_account_online(account) {
             trace!("[Game Runtime] Let account \"{}\" exited.", account.id);
+            self.data.mark_exit_reason(account, reason.clone());
             self.send((account.clone(), LetExit(reason)), account.clone(), service_type);
         } else {
             trace!("[Game Runtime] Let account \"{}\" exit failed: Account not online!", account.id);
@@ -93,22 +213,51 @@ impl GameRuntime {
     }
 
     pub fn kick_player(&mut self, player: &Player, service_type: ServiceType) {
+        self.kick_player_with_cooldown(player, None, service_type);
+    }
+
+    /// Kick the online player, it can't rejoin before the cooldown ends (Immediately if not set)
+    pub fn kick_player_with_cooldown(&mut self, player: &Player, cooldown: Option<Duration>, service_type: ServiceType) {
         // Send a leave message to the pad_client and wait for it to actively disconnect
         if self.data.is_account_online(&player.account) {
             trace!("[Game Runtime] Player \"{}\" kicked!", player.account.id);
+            if let Some(cooldown) = cooldown {
+                entry_mutex!(self.data.kick_cooldowns, |guard| {
+                    guard.insert(player.account.clone(), Instant::now() + cooldown);
+                });
+            }
+            self.data.mark_exit_reason(&player.account, YouAreKicked);
             self.send((player.account.clone(), LetExit(YouAreKicked)), player.account.clone(), service_type);
+            self.control.observer.record(ObservedEvent::PlayerKicked(player.account.clone()));
         } else {
             trace!("[Game Runtime] Kick player \"{}\" failed: Account not online!", player.account.id);
         }
     }
 
     pub fn ban_player(&mut self, player: &Player, service_type: ServiceType) {
+        self.ban_player_with_reason(player, None, service_type);
+    }
+
+    /// Ban the online player, the reason is kept in the archive
+    pub fn ban_player_with_reason(&mut self, player: &Player, reason: Option<String>, service_type: ServiceType) {
+        self.ban_player_for(player, reason, None, service_type);
+    }
+
+    /// Ban the online player for the duration (Permanent if not set)
+    /// The reason and the time left are shown to the player when it tries to rejoin
+    pub fn ban_player_for(&mut self, player: &Player, reason: Option<String>, duration: Option<Duration>, service_type: ServiceType) {
         if self.data.is_account_online(&player.account) {
             trace!("[Game Runtime] Player \"{}\" Banned!", player.account.id);
+            self.data.mark_exit_reason(&player.account, YouAreBanned);
             self.send((player.account.clone(), LetExit(YouAreBanned)), player.account.clone(), service_type);
             entry_mutex!(self.data.players_banned, |guard| {
-                guard.insert(player.account.clone(), player.clone());
+                let ban = BanRecord::new(player.account.clone(), reason);
+                guard.insert(player.account.clone(), match duration {
+                    None => { ban }
+                    Some(duration) => { ban.expires_in(duration) }
+                });
             });
+            self.control.observer.record(ObservedEvent::PlayerBanned(player.account.clone()));
         } else {
             trace!("[Game Runtime] Ban player \"{}\" failed: Account not online!", player.account.id);
         }
@@ -130,26 +279,93 @@ impl GameRuntime {
     }
 
     /// Lock the game
-    pub fn lock_game(&self) {
+    pub fn lock_game(&mut self) {
         if !self.data.locked.load(SeqCst) {
             self.data.locked.store(true, SeqCst);
             info!("[Game Runtime] Game locked!");
+            self.control.observer.record(ObservedEvent::LockChanged(true));
         }
     }
 
     /// Unlock the game
-    pub fn unlock_game(&self) {
+    pub fn unlock_game(&mut self) {
         if self.data.locked.load(SeqCst) {
             self.data.locked.store(false, SeqCst);
             info!("[Game Runtime] Game unlocked!");
+            self.control.observer.record(ObservedEvent::LockChanged(false));
+        }
+    }
+
+    /// Check if only the accounts on the allow list can join
+    pub fn is_allow_list_enabled(&self) -> bool {
+        self.data.allow_list_enabled.load(SeqCst)
+    }
+
+    /// Let only the accounts on the allow list join, online players stay
+    pub fn enable_allow_list(&mut self) {
+        if !self.data.allow_list_enabled.load(SeqCst) {
+            self.data.allow_list_enabled.store(true, SeqCst);
+            info!("[Game Runtime] Allow list enabled!");
+        }
+    }
+
+    /// Let every account join again
+    pub fn disable_allow_list(&mut self) {
+        if self.data.allow_list_enabled.load(SeqCst) {
+            self.data.allow_list_enabled.store(false, SeqCst);
+            info!("[Game Runtime] Allow list disabled!");
+        }
+    }
+
+    /// Check if guests can join the game
+    pub fn are_guests_allowed(&self) -> bool {
+        self.data.guests_allowed.load(SeqCst)
+    }
+
+    /// Let guests join the game
+    pub fn allow_guests(&mut self) {
+        if !self.data.guests_allowed.load(SeqCst) {
+            self.data.guests_allowed.store(true, SeqCst);
+            info!("[Game Runtime] Guests allowed!");
         }
     }
 
+    /// Stop guests from joining the game, online guests stay
+    pub fn deny_guests(&mut self) {
+        if self.data.guests_allowed.load(SeqCst) {
+            self.data.guests_allowed.store(false, SeqCst);
+            info!("[Game Runtime] Guests denied!");
+        }
+    }
+
+    /// Put the account id on the allow list
+    pub fn allow_account(&mut self, id: String) {
+        let id = process_id_text(id);
+        entry_mutex!(self.data.players_allowed, |guard| {
+            if guard.insert(id.clone()) {
+                trace!("[Game Runtime] Account \"{}\" allowed!", id);
+            }
+        });
+    }
+
+    /// Take the account id off the allow list, online players stay
+    pub fn disallow_account(&mut self, id: String) {
+        let id = process_id_text(id);
+        entry_mutex!(self.data.players_allowed, |guard| {
+            if guard.remove(&id) {
+                trace!("[Game Runtime] Account \"{}\" disallowed!", id);
+            } else {
+                trace!("[Game Runtime] Disallow account \"{}\" failed: Account is not allowed!", id);
+            }
+        });
+    }
+
     /// Close the Game
-    pub fn close_game(&self) {
+    pub fn close_game(&mut self) {
         if !self.data.close.load(SeqCst) {
             self.data.close.store(true, SeqCst);
             info!("[Game Runtime] Game closed!");
+            self.control.observer.close_streams();
         }
     }
 
@@ -167,12 +383,143 @@ impl GameRuntime {
         self.send_game_message(account, GameMessage::Msg(message), service_type);
     }
 
+    /// Ask the controller of the online account to send motion samples at the rate (Hz)
+    /// Returns false if the account is offline or motion sensors are not enabled
+    pub fn request_sample_rate(&mut self, account: &Account, sample_rate: u16) -> bool {
+        if self.control.keys.motion.is_none() {
+            warn!("[Game Runtime] Request sample rate failed: Motion sensors are not enabled!");
+            return false;
+        }
+        match self.data.online_service_type(account) {
+            None => { false }
+            Some(service_type) => {
+                self.send_game_message(account, GameMessage::SampleRate(sample_rate.max(1)), service_type);
+                true
+            }
+        }
+    }
+
+    /// Ask the controller of the online account to vibrate, returns false if the account is offline
+    pub fn vibrate(&mut self, account: &Account, pattern: impl Into<HapticPattern>) -> bool {
+        match self.data.online_service_type(account) {
+            None => { false }
+            Some(service_type) => {
+                self.send_game_message(account, GameMessage::Vibrate(pattern.into()), service_type);
+                true
+            }
+        }
+    }
+
+    /// Send a GameMessage to all online members of the team
+    pub fn send_team_message(&mut self, team: &str, message: GameMessage) {
+        let members = self.data.team_members(team);
+        self.multicast(&members, message);
+    }
+
+    /// Send a GameMessage to all online players, returns the count of players reached
+    pub fn broadcast(&mut self, message: GameMessage) -> usize {
+        let accounts = self.data.online_accounts();
+        self.multicast(&accounts, message)
+    }
+
+    /// Send a GameMessage to all online players except the account, returns the count of players reached
+    pub fn broadcast_except(&mut self, except: &Account, message: GameMessage) -> usize {
+        let accounts: Vec<Account> = self.data.online_accounts().into_iter()
+            .filter(|account| account != except)
+            .collect();
+        self.multicast(&accounts, message)
+    }
+
+    /// Send a GameMessage to the accounts, returns the count of players reached
+    /// The service type of each account is looked up, offline accounts are skipped
+    pub fn multicast(&mut self, accounts: &[Account], message: GameMessage) -> usize {
+        let mut count = 0;
+        for account in accounts {
+            match self.data.online_service_type(account) {
+                None => {
+                    trace!("[Game Runtime] Message to \"{}\" skipped: Account not online!", account.id);
+                }
+                Some(service_type) => {
+                    self.send_game_message(account, message.clone(), service_type);
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Assign the account to a team, or remove it from its team if None
+    /// Online players get the team color applied and are notified
+    pub fn assign_team(&mut self, account: &Account, team: Option<String>) -> bool {
+        if let Some(team) = &team
+            && !self.data.teams.iter().any(|t| &t.name == team) {
+            warn!("[Game Runtime] Assign team failed: Team \"{}\" not found!", team);
+            return false;
+        }
+
+        entry_mutex!(self.data.team_members, |guard| {
+            match &team {
+                Some(team) => { guard.insert(account.clone(), team.clone()); }
+                None => { guard.remove(account); }
+            }
+        });
+        trace!("[Game Runtime] Account \"{}\" assigned to team {:?}", account.id, team);
+
+        let mut customize = None;
+        entry_mutex!(self.data.players_online, |guard| {
+            if let Some(player) = guard.get_mut(account) {
+                if let Some(team) = &team {
+                    self.data.apply_team_color(player, team);
+                }
+                customize = Some(player.customize.clone().unwrap_or_default());
+            }
+        });
+
+        if let (Some(customize), Some(service_type)) = (customize, self.data.get_service_type(account)) {
+            self.send_game_message(account, TeamChanged(team, customize), service_type);
+        }
+        true
+    }
+
+    /// Get the observer to register event handlers
+    pub fn observer(&mut self) -> &mut GameObserver {
+        &mut self.control.observer
+    }
+
+    /// Subscribe to the events of the runtime as an async stream
+    /// The stream ends when the game is closed
+    pub fn subscribe_events(&mut self) -> GameEventStream {
+        self.control.observer.subscribe()
+    }
+
+    /// Invoke the registered handlers for all events received since the last dispatch
+    /// The runtime is unlocked while the handlers run, so they may call back into it
+    /// Returns the count of dispatched events
+    pub fn dispatch_events(runtime: &Arc<Mutex<GameRuntime>>) -> usize {
+        let mut dispatch = None;
+        entry_mutex!(runtime, |guard| {
+            dispatch = guard.control.observer.begin_dispatch();
+        });
+        let Some((events, mut handlers)) = dispatch else {
+            return 0;
+        };
+        let count = handlers.dispatch(events);
+        entry_mutex!(runtime, |guard| {
+            guard.control.observer.end_dispatch(handlers);
+        });
+        count
+    }
+
     /// Pop an event message
-    pub fn pop_control_event(&mut self) -> Option<(Account, ControlMessage)> {
+    /// Lifecycle events are returned even if the player is no longer online,
+    /// so are the control messages queued before its leave (such as the releases of held buttons)
+    pub fn pop_control_event(&mut self) -> Option<(Account, ControlEvent)> {
         let pop = self.control.events.pop_front();
         if pop.is_some() {
             let (account, msg) = pop.unwrap();
-            if self.data.is_account_online(&account) {
+            if !matches!(msg, ControlEvent::Control(_))
+                || self.data.is_account_online(&account)
+                || self.control.is_leave_pending(&account) {
                 trace!("[Control Runtime] Message: {:?} from \"{}\" ", &msg, account);
                 Some((account, msg))
             } else {
@@ -204,12 +551,30 @@ impl MessageManager<(Account, ControlMessage), (Account, GameMessage), Account>
             .pop_front()
     }
 
-    fn put_into_receive_list(&mut self, message: (Account, ControlMessage), _key: Account, _service: ServiceType) {
-        let result = self.control.process_control_message(&message.0, message.1);
-        if result.is_err() {
-            let result = result.unwrap_err();
-            warn!("[Game Runtime] Can't process message: {:?}", result);
-            drop(result);
+    fn put_into_receive_list(&mut self, message: (Account, ControlMessage), _key: Account, service: ServiceType) {
+        self.expire_prompts();
+        let (account, message) = message;
+        if let PromptReply(id, text) = message {
+            self.reply_prompt(&account, id, text);
+            return;
+        }
+        if let Admin(command) = message {
+            self.run_admin_command(&account, command, service);
+            return;
+        }
+        if let UpdateProfile(customize) = message {
+            self.update_profile(&account, customize, service);
+            return;
+        }
+        let input = matches!(message, Pressed(_) | Released(_) | Axis(..) | Dir(..) | Touch(..) | Motion(_));
+        let press = matches!(message, Pressed(_));
+        match self.control.process_control_message(&account, message) {
+            Ok(()) if input => { self.data.count_input(&account, press); }
+            Ok(()) => {}
+            Err((key, error)) => {
+                warn!("[Game Runtime] Input of key {} from \"{}\" rejected: {:?}", key, account, error);
+                self.send_game_message(&account, InputRejected(key, error), service);
+            }
         }
     }
 }
@@ -220,10 +585,29 @@ impl Default for GameRuntimeData {
             received: Default::default(),
             send: Default::default(),
             players_online: Players::default(),
-            players_banned: Players::default(),
+            players_banned: Default::default(),
+            kick_cooldowns: Default::default(),
+            players_known: Players::default(),
+            players_allowed: Default::default(),
+            admins: Default::default(),
             account_service_type: Default::default(),
 
+            max_players: None,
+            seats: Default::default(),
+
+            teams: Vec::new(),
+            team_policy: TeamPolicy::default(),
+            team_members: Default::default(),
+            team_cursor: 0,
+
+            nickname_policy: NicknamePolicy::default(),
+
+            exit_reasons: Default::default(),
+            stats: Default::default(),
+
             locked: AtomicBool::new(false),
+            allow_list_enabled: AtomicBool::new(false),
+            guests_allowed: AtomicBool::new(false),
             close: AtomicBool::new(false)
         }
     }
@@ -272,6 +656,22 @@ impl GameRuntimeData {
         }
     }
 
+    /// Record why an account is about to exit, the first recorded reason is kept
+    pub(crate) fn mark_exit_reason(&self, account: &Account, reason: ExitReason) {
+        entry_mutex!(self.exit_reasons, |guard| {
+            guard.entry(account.clone()).or_insert(reason);
+        });
+    }
+
+    /// Take the recorded exit reason of an account
+    pub(crate) fn take_exit_reason(&self, account: &Account) -> Option<ExitReason> {
+        let mut reason = None;
+        entry_mutex!(self.exit_reasons, |guard| {
+            reason = guard.remove(account);
+        });
+        reason
+    }
+
     /// Returns all online accounts
     pub fn online_accounts(&self) -> Vec<Account> {
         let mut vec = Vec::new();
@@ -293,11 +693,12 @@ impl GameRuntimeData {
         false
     }
 
-    /// Returns all banned accounts
+    /// Returns all banned accounts, expired bans are lifted first
     pub fn banned_accounts(&self) -> Vec<Account> {
+        self.lift_expired_bans();
         let mut vec = Vec::new();
         entry_mutex!(self.players_banned, |guard| {
-            for account in guard.keys().into_iter() {
+            for account in guard.keys() {
                 vec.push(account.clone());
             }
         });
@@ -306,14 +707,102 @@ impl GameRuntimeData {
 
     /// Check if account is banned
     pub fn is_account_banned(&self, account: &Account) -> bool {
+        self.ban_of(account).is_some()
+    }
+
+    /// Remove the bans past their expiry, returns the accounts no longer banned
+    pub fn lift_expired_bans(&self) -> Vec<Account> {
+        let mut lifted = Vec::new();
         entry_mutex!(self.players_banned, |guard| {
-            if guard.contains_key(account) {
-                true;
+            guard.retain(|account, ban| {
+                if ban.is_expired() {
+                    trace!("[Game Runtime] Ban of \"{}\" lifted!", account.id);
+                    lifted.push(account.clone());
+                }
+                !ban.is_expired()
+            });
+        });
+        lifted
+    }
+
+    /// Time left before the kicked account can rejoin
+    pub fn kick_cooldown_of(&self, account: &Account) -> Option<Duration> {
+        let mut result = None;
+        entry_mutex!(self.kick_cooldowns, |guard| {
+            let now = Instant::now();
+            guard.retain(|_, until| *until > now);
+            result = guard.get(account).map(|until| *until - now);
+        });
+        result
+    }
+
+    /// Returns the account ids on the allow list, ordered by id
+    pub fn allowed_accounts(&self) -> Vec<String> {
+        let mut vec = Vec::new();
+        entry_mutex!(self.players_allowed, |guard| {
+            vec = guard.iter().cloned().collect();
+        });
+        vec.sort();
+        vec
+    }
+
+    /// Check if account can pass the allow list, every account can while it is disabled
+    /// Accounts are matched by their ids
+    pub fn is_account_allowed(&self, account: &Account) -> bool {
+        if !self.allow_list_enabled.load(SeqCst) {
+            return true;
+        }
+        entry_mutex!(self.players_allowed, |guard| {
+            if guard.contains(&account.id) {
+                return true;
             }
         });
         false
     }
 
+    /// Get the ban record of account, expired bans are ignored
+    pub fn ban_of(&self, account: &Account) -> Option<BanRecord> {
+        let mut result = None;
+        entry_mutex!(self.players_banned, |guard| {
+            result = guard.get(account).filter(|ban| !ban.is_expired()).cloned();
+        });
+        result
+    }
+
+    /// Returns all players who joined the game before, with their last custom information
+    pub fn known_players(&self) -> Vec<Player> {
+        let mut vec = Vec::new();
+        entry_mutex!(self.players_known, |guard| {
+            vec = guard.values().cloned().collect();
+        });
+        vec
+    }
+
+    /// Remember the player and its custom information
+    pub(crate) fn remember_player(&self, player: &Player) {
+        entry_mutex!(self.players_known, |guard| {
+            guard.insert(player.account.clone(), player.clone());
+        });
+    }
+
+    /// Drop everything kept about the guest after it left, it never returns with the same account
+    fn forget_guest(&self, account: &Account) {
+        entry_mutex!(self.seats, |guard| {
+            guard.remove(account);
+        });
+        entry_mutex!(self.team_members, |guard| {
+            guard.remove(account);
+        });
+        entry_mutex!(self.stats, |guard| {
+            guard.remove(account);
+        });
+    }
+
+    /// Convert the data into an archive without consuming it
+    pub fn archive(&self) -> GameRuntimeDataArchive {
+        GameRuntimeDataArchive::from(self)
+    }
+
     /// Get service type of account
     pub fn get_service_type(&self, account: &Account) -> Option<ServiceType> {
         let mut result = None;
@@ -322,75 +811,254 @@ impl GameRuntimeData {
         });
         result
     }
+
+    /// Get service type of the account if it is online
+    /// The service type is kept after leaving, so offline accounts return None here
+    pub fn online_service_type(&self, account: &Account) -> Option<ServiceType> {
+        if !self.is_account_online(account) {
+            return None;
+        }
+        self.get_service_type(account)
+    }
+
+    /// Get the seat of account
+    /// Seats are kept after leaving, so returning players get the same seat back
+    pub fn seat_of(&self, account: &Account) -> Option<u8> {
+        let mut result = None;
+        entry_mutex!(self.seats, |guard| {
+            result = guard.get(account).cloned();
+        });
+        result
+    }
+
+    /// Get the online account sitting at the specified seat
+    pub fn account_at_seat(&self, seat: u8) -> Option<Account> {
+        self.seated_accounts().into_iter()
+            .find(|(s, _)| *s == seat)
+            .map(|(_, account)| account)
+    }
+
+    /// Returns all online accounts with their seats, ordered by seat
+    pub fn seated_accounts(&self) -> Vec<(u8, Account)> {
+        let mut vec = Vec::new();
+        entry_mutex!(self.seats, |guard| {
+            for account in self.online_accounts() {
+                if let Some(seat) = guard.get(&account) {
+                    vec.push((*seat, account));
+                }
+            }
+        });
+        vec.sort_by_key(|(seat, _)| *seat);
+        vec
+    }
+
+    /// Returns the maximum number of players (Unlimited if None)
+    pub fn max_players(&self) -> Option<u8> {
+        self.max_players
+    }
+
+    /// Find a free seat for the account
+    /// Prefers the account's previous seat, then seats nobody has used, then seats of offline players
+    fn find_seat(&self, account: &Account) -> Option<u8> {
+        let capacity = self.max_players.map(|max| max as u16).unwrap_or(u8::MAX as u16 + 1);
+        let occupied: HashSet<u8> = self.seated_accounts().into_iter()
+            .map(|(seat, _)| seat)
+            .collect();
+        let free = |seat: &u8| (*seat as u16) < capacity && !occupied.contains(seat);
+
+        let mut result = None;
+        entry_mutex!(self.seats, |guard| {
+            let mut seats = (0..capacity).map(|seat| seat as u8);
+            result = guard.get(account).cloned().filter(free)
+                .or_else(|| seats.clone().find(|seat| free(seat) && !guard.values().any(|used| used == seat)))
+                .or_else(|| seats.find(free));
+        });
+        result
+    }
+
+    /// Record the seat taken by the account
+    fn take_seat(&self, account: &Account, seat: u8) {
+        entry_mutex!(self.seats, |guard| {
+            guard.insert(account.clone(), seat);
+        });
+    }
+
+    /// Returns all teams
+    pub fn teams(&self) -> Vec<TeamData> {
+        self.teams.clone()
+    }
+
+    /// Get the team of account
+    pub fn team_of(&self, account: &Account) -> Option<String> {
+        let mut result = None;
+        entry_mutex!(self.team_members, |guard| {
+            result = guard.get(account).cloned();
+        });
+        result
+    }
+
+    /// Returns all online members of the team
+    pub fn team_members(&self, team: &str) -> Vec<Account> {
+        let mut vec = Vec::new();
+        entry_mutex!(self.team_members, |guard| {
+            for account in self.online_accounts() {
+                if guard.get(&account).is_some_and(|t| t == team) {
+                    vec.push(account);
+                }
+            }
+        });
+        vec
+    }
+
+    /// Assign a team to the joining account by policy
+    /// Returning players keep their previous team
+    fn assign_team_on_join(&mut self, account: &Account) -> Option<String> {
+        if let Some(team) = self.team_of(account) {
+            return Some(team);
+        }
+
+        if self.teams.is_empty() {
+            return None;
+        }
+
+        let team = match self.team_policy {
+            TeamPolicy::Manual => { return None; }
+            TeamPolicy::RoundRobin => {
+                let team = self.teams[self.team_cursor % self.teams.len()].name.clone();
+                self.team_cursor += 1;
+                team
+            }
+            TeamPolicy::Balanced => {
+                self.teams.iter()
+                    .min_by_key(|t| self.team_members(&t.name).len())
+                    .map(|t| t.name.clone())
+                    .unwrap_or_default()
+            }
+        };
+
+        entry_mutex!(self.team_members, |guard| {
+            guard.insert(account.clone(), team.clone());
+        });
+        Some(team)
+    }
+
+    /// Apply the team color to the player's custom information
+    pub(crate) fn apply_team_color(&self, player: &mut Player, team: &str) {
+        if let Some(team) = self.teams.iter().find(|t| t.name == team) {
+            player.hsv(team.color_hue, team.color_saturation, team.color_value);
+        }
+    }
 }
 
 impl GameControlRuntime {
 
     /// Process a control message
-    fn process_control_message(&mut self, who: &Account, msg: ControlMessage) -> Result<(), ControlMessage> {
+    /// Returns the rejected key and the reason if the input is not accepted
+    fn process_control_message(&mut self, who: &Account, msg: ControlMessage) -> Result<(), (u8, InputError)> {
         match msg {
-            Msg(_) => {
-                self.send_event(who, msg);
-                Ok(())
+            Msg(text) => {
+                self.observer.record(ObservedEvent::Message(who.clone(), text.clone()));
+                self.send_event(who, Msg(text));
             }
 
             Pressed(button_key) => {
-                let key_valid = self.check_key(&self.keys.button_keys, &button_key);
-                if key_valid {
-                    Self::change_value(&mut self.button, button_key, who, true);
-                    self.send_event(who, msg);
-                    trace!("[Control Runtime] Player \"{}\" pressed btn_{}", &who.id, button_key);
-                } else {
-                    warn!("[Control Runtime] Key btn_{} not registered!", button_key);
-                }
-                Ok(())
+                self.check_key(button_key, KeyKind::Button, &[])?;
+                Self::change_value(&mut self.button, button_key, who, true);
+                Self::increase_count(&mut self.presses, button_key, who);
+                self.observer.record(ObservedEvent::Pressed(who.clone(), button_key));
+                self.send_event(who, msg);
+                trace!("[Control Runtime] Player \"{}\" pressed btn_{}", &who.id, button_key);
             }
 
             Released(button_key) => {
-                if self.check_key(&self.keys.button_keys, &button_key) {
-                    Self::change_value(&mut self.button, button_key, who, false);
-                    self.send_event(who, msg);
-                    trace!("[Control Runtime] Player \"{}\" released btn_{}", &who.id, button_key);
-                } else {
-                    warn!("[Control Runtime] Key btn_{} not registered!", button_key);
-                }
-                Ok(())
+                self.check_key(button_key, KeyKind::Button, &[])?;
+                Self::change_value(&mut self.button, button_key, who, false);
+                Self::increase_count(&mut self.releases, button_key, who);
+                self.observer.record(ObservedEvent::Released(who.clone(), button_key));
+                self.send_event(who, msg);
+                trace!("[Control Runtime] Player \"{}\" released btn_{}", &who.id, button_key);
             }
 
             Axis(axis_key, axis) => {
-                if self.check_key(&self.keys.button_keys, &axis_key) {
-                    Self::change_value(&mut self.axes, axis_key, who, axis);
-                    trace!("[Control Runtime] Player \"{}\" changed ax_{} to ({})", &who.id, axis_key, axis);
-                } else {
-                    warn!("[Control Runtime] Key ax_{} not registered!", axis_key);
-                }
-                Ok(())
+                let (axis, target) = self.process_axis(who, axis_key, axis);
+                self.check_key(axis_key, KeyKind::Axis, &[axis])?;
+                Self::change_value(&mut self.axes, axis_key, who, axis);
+                Self::change_target(&mut self.axis_targets, axis_key, who, target.filter(|target| *target != axis).map(|target| (target, Instant::now())));
+                self.observer.record(ObservedEvent::AxisChanged(who.clone(), axis_key, axis));
+                trace!("[Control Runtime] Player \"{}\" changed ax_{} to ({})", &who.id, axis_key, axis);
             }
 
             Dir(dir_key, dir) => {
-                if self.check_key(&self.keys.button_keys, &dir_key) {
-                    Self::change_value(&mut self.directions, dir_key, who, dir);
-                    trace!("[Control Runtime] Player \"{}\" changed dir_{} to ({}, {})", &who.id, dir_key, dir.0, dir.1);
+                let (dir, target) = self.process_direction(who, dir_key, dir);
+                self.check_key(dir_key, KeyKind::Direction, &[dir.0, dir.1])?;
+                Self::change_value(&mut self.directions, dir_key, who, dir);
+                Self::change_target(&mut self.direction_targets, dir_key, who, target.filter(|target| *target != dir).map(|target| (target, Instant::now())));
+                self.observer.record(ObservedEvent::DirectionChanged(who.clone(), dir_key, dir));
+                trace!("[Control Runtime] Player \"{}\" changed dir_{} to ({}, {})", &who.id, dir_key, dir.0, dir.1);
+            }
+
+            Touch(touch_key, touch) => {
+                self.check_key(touch_key, KeyKind::Touchpad, &[touch.x, touch.y])?;
+                if !(0.0..=1.0).contains(&touch.pressure) {
+                    return Err((touch_key, InputError::ValueOutOfRange));
+                }
+                let points = self.touches.entry(touch_key)
+                    .or_default()
+                    .entry(who.clone())
+                    .or_default();
+                match touch.phase {
+                    TouchPhase::Begin | TouchPhase::Move => { points.insert(touch.id, touch.point()); }
+                    TouchPhase::End => { points.remove(&touch.id); }
+                }
+                self.observer.record(ObservedEvent::Touched(who.clone(), touch_key, touch));
+                if touch.phase != TouchPhase::Move {
+                    self.send_event(who, msg);
+                }
+                trace!("[Control Runtime] Player \"{}\" touch_{} #{} {:?} at ({}, {})", &who.id, touch_key, touch.id, touch.phase, touch.x, touch.y);
+            }
+
+            Motion(motion) => {
+                if self.keys.motion.is_none() {
+                    warn!("[Control Runtime] Motion from \"{}\" ignored, motion sensors are not enabled", &who.id);
+                } else if !motion.is_valid() {
+                    warn!("[Control Runtime] Invalid motion from \"{}\" ignored", &who.id);
                 } else {
-                    warn!("[Control Runtime] Key dir_{} not registered!", dir_key);
+                    let orientation = motion.normalized_orientation();
+                    self.motions.insert(who.clone(), MotionData { orientation, ..motion });
                 }
-                Ok(())
             }
 
             _ => {
-                Err(msg)
+                warn!("[Control Runtime] Can't process message: {:?}", msg);
             }
         }
+        Ok(())
     }
 
     /// Get specified player's direction value
+    /// Returns the default value of the key if the player has not changed it,
+    /// smoothed directions are moved toward the latest input for the time passed since it arrived
     pub fn get_direction(&self, who: &Account, key: &u8) -> Option<(f64, f64)> {
-        Self::get(&self.directions, who, key)
+        let Some(direction) = Self::get(&self.directions, who, key) else {
+            return self.default_of(key, KeyKind::Direction).map(|value| (value, value));
+        };
+        match (Self::get(&self.direction_targets, who, key), self.keys.key(key)) {
+            (Some((target, since)), Some(data)) => { Some(data.processing.settle_direction(target, direction, since.elapsed())) }
+            _ => { Some(direction) }
+        }
     }
 
     /// Get specified player's axis value
+    /// Returns the default value of the key if the player has not changed it,
+    /// smoothed axes are moved toward the latest input for the time passed since it arrived
     pub fn get_axis(&self, who: &Account, key: &u8) -> Option<f64> {
-        Self::get(&self.axes, who, key)
+        let Some(axis) = Self::get(&self.axes, who, key) else {
+            return self.default_of(key, KeyKind::Axis);
+        };
+        match (Self::get(&self.axis_targets, who, key), self.keys.key(key)) {
+            (Some((target, since)), Some(data)) => { Some(data.processing.settle_axis(target, axis, since.elapsed())) }
+            _ => { Some(axis) }
+        }
     }
 
     /// Get specified player's button status
@@ -398,8 +1066,182 @@ impl GameControlRuntime {
         Self::get(&self.button, who, key)
     }
 
-    fn check_key(&self, map: &HashMap<u8, String>, key: &u8) -> bool {
-        map.contains_key(key)
+    /// Get specified player's fingers on the touchpad, ordered by touch ids
+    pub fn get_touches(&self, who: &Account, key: &u8) -> Vec<TouchPoint> {
+        self.touches.get(key)
+            .and_then(|players| players.get(who))
+            .map(|points| points.values().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Get specified player's finger on the touchpad by its touch id
+    pub fn get_touch(&self, who: &Account, key: &u8, id: u32) -> Option<TouchPoint> {
+        self.touches.get(key)
+            .and_then(|players| players.get(who))
+            .and_then(|points| points.get(&id))
+            .copied()
+    }
+
+    /// Get specified player's fingers on the touchpad by the key name
+    pub fn get_touches_by_name(&self, who: &Account, name: &str) -> Vec<TouchPoint> {
+        self.keys.find_key(KeyKind::Touchpad, name)
+            .map(|key| self.get_touches(who, &key))
+            .unwrap_or_default()
+    }
+
+    /// Get specified player's latest motion sample
+    pub fn get_motion(&self, who: &Account) -> Option<MotionData> {
+        self.motions.get(who).copied()
+    }
+
+    /// Get specified player's acceleration (x, y, z)
+    pub fn get_acceleration(&self, who: &Account) -> Option<[f64; 3]> {
+        self.motions.get(who).map(|motion| motion.acceleration)
+    }
+
+    /// Get specified player's angular velocity (x, y, z)
+    pub fn get_angular_velocity(&self, who: &Account) -> Option<[f64; 3]> {
+        self.motions.get(who).map(|motion| motion.angular_velocity)
+    }
+
+    /// Get specified player's orientation quaternion (x, y, z, w)
+    pub fn get_orientation(&self, who: &Account) -> Option<[f64; 4]> {
+        self.motions.get(who).map(|motion| motion.orientation)
+    }
+
+    /// Get specified player's direction value by the key name
+    pub fn get_direction_by_name(&self, who: &Account, name: &str) -> Option<(f64, f64)> {
+        self.keys.find_key(KeyKind::Direction, name)
+            .and_then(|key| self.get_direction(who, &key))
+    }
+
+    /// Get specified player's axis value by the key name
+    pub fn get_axis_by_name(&self, who: &Account, name: &str) -> Option<f64> {
+        self.keys.find_key(KeyKind::Axis, name)
+            .and_then(|key| self.get_axis(who, &key))
+    }
+
+    /// Get specified player's button status by the key name
+    pub fn get_button_status_by_name(&self, who: &Account, name: &str) -> Option<bool> {
+        self.keys.find_key(KeyKind::Button, name)
+            .and_then(|key| self.get_button_status(who, &key))
+    }
+
+    /// Get the registered keys
+    pub fn keys(&self) -> &GameControlData {
+        &self.keys
+    }
+
+    /// Reset all inputs of the player to their defaults, called when it leaves
+    /// Held buttons and fingers are released, and moved axes and directions reset, with synthetic events,
+    /// so the game sees them let go
+    pub(crate) fn release_all(&mut self, who: &Account) {
+        let mut pressed: Vec<u8> = self.button.iter()
+            .filter(|(_, players)| players.get(who) == Some(&true))
+            .map(|(key, _)| *key)
+            .collect();
+        pressed.sort();
+        for key in pressed {
+            Self::change_value(&mut self.button, key, who, false);
+            Self::increase_count(&mut self.releases, key, who);
+            self.observer.record(ObservedEvent::Released(who.clone(), key));
+            self.send_event(who, Released(key));
+        }
+
+        let mut axes: Vec<u8> = self.axes.iter()
+            .filter_map(|(key, players)| players.get(who).map(|_| *key))
+            .collect();
+        axes.sort();
+        for key in axes {
+            let previous = self.get_axis(who, &key);
+            self.axes.get_mut(&key).map(|players| players.remove(who));
+            self.axis_targets.get_mut(&key).map(|targets| targets.remove(who));
+            let default = self.get_axis(who, &key).unwrap_or_default();
+            if previous != Some(default) {
+                self.observer.record(ObservedEvent::AxisChanged(who.clone(), key, default));
+                self.send_event(who, Axis(key, default));
+            }
+        }
+
+        let mut directions: Vec<u8> = self.directions.iter()
+            .filter_map(|(key, players)| players.get(who).map(|_| *key))
+            .collect();
+        directions.sort();
+        for key in directions {
+            let previous = self.get_direction(who, &key);
+            self.directions.get_mut(&key).map(|players| players.remove(who));
+            self.direction_targets.get_mut(&key).map(|targets| targets.remove(who));
+            let default = self.get_direction(who, &key).unwrap_or_default();
+            if previous != Some(default) {
+                self.observer.record(ObservedEvent::DirectionChanged(who.clone(), key, default));
+                self.send_event(who, Dir(key, default));
+            }
+        }
+
+        let mut touches: Vec<(u8, TouchPoint)> = self.touches.iter_mut()
+            .filter_map(|(key, players)| players.remove(who).map(|points| (*key, points)))
+            .flat_map(|(key, points)| points.into_values().map(move |point| (key, point)))
+            .collect();
+        touches.sort_by_key(|(key, point)| (*key, point.id));
+        for (key, point) in touches {
+            let touch = TouchData::new(point.id, TouchPhase::End, point.x, point.y, point.pressure);
+            self.observer.record(ObservedEvent::Touched(who.clone(), key, touch));
+            self.send_event(who, Touch(key, touch));
+        }
+
+        self.motions.remove(who);
+        trace!("[Control Runtime] Inputs of player \"{}\" released", &who.id);
+    }
+
+    /// Check that the key is registered with the kind and accepts the values
+    fn check_key(&self, key: u8, kind: KeyKind, values: &[f64]) -> Result<(), (u8, InputError)> {
+        match self.keys.key(&key) {
+            None => { Err((key, InputError::KeyNotRegistered)) }
+            Some(data) if data.kind != kind => { Err((key, InputError::KindMismatch)) }
+            Some(data) if !values.iter().all(|value| data.accepts(*value)) => { Err((key, InputError::ValueOutOfRange)) }
+            Some(_) => { Ok(()) }
+        }
+    }
+
+    /// Apply the input processing of the key, unknown keys are left to the check
+    /// Returns the value and, if the key is smoothed, the target it keeps moving toward on the next frames
+    fn process_axis(&self, who: &Account, key: u8, axis: f64) -> (f64, Option<f64>) {
+        match self.keys.key(&key) {
+            Some(data) if data.kind == KeyKind::Axis && data.processing.is_enabled() && !axis.is_nan() => {
+                let previous = self.get_axis(who, &key).unwrap_or(data.default);
+                let target = data.processing.filter_axis(axis, data.min, data.max);
+                let smoothed = data.processing.step_axis(target, previous);
+                (smoothed, (data.processing.smoothing > 0.0).then_some(target))
+            }
+            _ => { (axis, None) }
+        }
+    }
+
+    /// Apply the input processing of the key, unknown keys are left to the check
+    /// Returns the value and, if the key is smoothed, the target it keeps moving toward on the next frames
+    fn process_direction(&self, who: &Account, key: u8, dir: (f64, f64)) -> ((f64, f64), Option<(f64, f64)>) {
+        match self.keys.key(&key) {
+            Some(data) if data.kind == KeyKind::Direction && data.processing.is_enabled() && !dir.0.is_nan() && !dir.1.is_nan() => {
+                let previous = self.get_direction(who, &key).unwrap_or((data.default, data.default));
+                let target = data.processing.filter_direction(dir, data.min, data.max);
+                let smoothed = data.processing.step_direction(target, previous);
+                (smoothed, (data.processing.smoothing > 0.0).then_some(target))
+            }
+            _ => { (dir, None) }
+        }
+    }
+
+    fn change_target<V>(map: &mut HashMap<u8, HashMap<Account, V>>, key: u8, who: &Account, target: Option<V>) {
+        match target {
+            Some(target) => { map.entry(key).or_default().insert(who.clone(), target); }
+            None => { map.get_mut(&key).map(|targets| targets.remove(who)); }
+        }
+    }
+
+    fn default_of(&self, key: &u8, kind: KeyKind) -> Option<f64> {
+        self.keys.key(key)
+            .filter(|data| data.kind == kind)
+            .map(|data| data.default)
     }
 
     fn get<V: Clone>(map: &HashMap<u8, HashMap<Account, V>>, who: &Account, key: &u8) -> Option<V> {
@@ -419,7 +1261,20 @@ impl GameControlRuntime {
             .insert(who.clone(), msg);
     }
 
+    fn increase_count(map: &mut HashMap<u8, HashMap<Account, u32>>, key: u8, who: &Account) {
+        *map.entry(key)
+            .or_insert_with(HashMap::new)
+            .entry(who.clone())
+            .or_insert(0) += 1;
+    }
+
     fn send_event(&mut self, who: &Account, msg: ControlMessage) {
-        self.events.push_back((who.clone(), msg));
+        self.events.push_back((who.clone(), ControlEvent::Control(msg)));
+    }
+
+    /// Check if a leave of the account is still queued in the events
+    fn is_leave_pending(&self, who: &Account) -> bool {
+        self.events.iter().any(|(account, event)| account == who
+            && matches!(event, ControlEvent::Left(_) | ControlEvent::TimedOut | ControlEvent::Kicked | ControlEvent::Banned))
     }
 }
\ No newline at end of file
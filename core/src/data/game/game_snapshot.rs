@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use crate::data::game::game_data::KeyKind;
+use crate::data::game::game_runtime::{GameControlRuntime, GameRuntime};
+use crate::data::message::message_touch::TouchPoint;
+use crate::data::player::player_data::Account;
+
+/// Input snapshot
+/// State of all online players' inputs at the moment the snapshot is taken
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct InputSnapshot {
+    pub players: Vec<PlayerSnapshot>,
+}
+
+/// Inputs of a single player, keys are ordered
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct PlayerSnapshot {
+    pub account: Account,
+    pub seat: Option<u8>,
+    pub buttons: Vec<ButtonSnapshot>,
+    pub axes: Vec<(u8, f64)>,
+    pub directions: Vec<(u8, (f64, f64))>,
+    pub touches: Vec<(u8, Vec<TouchPoint>)>,
+}
+
+/// Button state with the edges accumulated since the last snapshot
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct ButtonSnapshot {
+    pub key: u8,
+
+    /// Current level of the button
+    pub pressed: bool,
+
+    /// The button was pressed at least once since the last snapshot
+    pub just_pressed: bool,
+
+    /// The button was released at least once since the last snapshot
+    pub just_released: bool,
+
+    /// Count of presses since the last snapshot
+    pub press_count: u32,
+}
+
+impl InputSnapshot {
+
+    /// Get the inputs of the specified player
+    pub fn player(&self, account: &Account) -> Option<&PlayerSnapshot> {
+        self.players.iter().find(|player| &player.account == account)
+    }
+}
+
+impl PlayerSnapshot {
+
+    /// Get the state of the specified button
+    pub fn button(&self, key: u8) -> Option<&ButtonSnapshot> {
+        self.buttons.iter().find(|button| button.key == key)
+    }
+
+    /// Get the value of the specified axis
+    pub fn axis(&self, key: u8) -> Option<f64> {
+        self.axes.iter().find(|(k, _)| *k == key).map(|(_, axis)| *axis)
+    }
+
+    /// Get the value of the specified direction
+    pub fn direction(&self, key: u8) -> Option<(f64, f64)> {
+        self.directions.iter().find(|(k, _)| *k == key).map(|(_, dir)| *dir)
+    }
+
+    /// Get the fingers on the specified touchpad
+    pub fn touches(&self, key: u8) -> &[TouchPoint] {
+        self.touches.iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, points)| points.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+impl GameRuntime {
+
+    /// Take a snapshot of all online players' inputs and begin a new frame
+    /// Edges and press counts are reset after the snapshot, and prompts past their deadlines expire
+    pub fn snapshot(&mut self) -> InputSnapshot {
+        let snapshot = self.peek_snapshot();
+        self.control.begin_frame();
+        self.expire_prompts();
+        snapshot
+    }
+
+    /// Take a snapshot without beginning a new frame
+    pub fn peek_snapshot(&self) -> InputSnapshot {
+        let mut accounts = self.data.online_accounts();
+        accounts.sort_by_key(|account| self.data.seat_of(account).unwrap_or(u8::MAX));
+
+        let players = accounts.into_iter()
+            .map(|account| {
+                let seat = self.data.seat_of(&account);
+                self.control.player_snapshot(account, seat)
+            })
+            .collect();
+
+        InputSnapshot { players }
+    }
+}
+
+impl GameControlRuntime {
+
+    /// Reset the edges and press counts accumulated since the last frame
+    pub fn begin_frame(&mut self) {
+        self.presses.clear();
+        self.releases.clear();
+    }
+
+    fn player_snapshot(&self, account: Account, seat: Option<u8>) -> PlayerSnapshot {
+        let buttons = self.keys.keys_of(KeyKind::Button).into_iter()
+            .map(|key| {
+                let press_count = Self::count(&self.presses, &account, key);
+                ButtonSnapshot {
+                    key,
+                    pressed: self.get_button_status(&account, &key).unwrap_or(false),
+                    just_pressed: press_count > 0,
+                    just_released: Self::count(&self.releases, &account, key) > 0,
+                    press_count,
+                }
+            })
+            .collect();
+
+        let axes = self.keys.keys_of(KeyKind::Axis).into_iter()
+            .map(|key| (key, self.get_axis(&account, &key).unwrap_or_default()))
+            .collect();
+
+        let directions = self.keys.keys_of(KeyKind::Direction).into_iter()
+            .map(|key| (key, self.get_direction(&account, &key).unwrap_or_default()))
+            .collect();
+
+        let touches = self.keys.keys_of(KeyKind::Touchpad).into_iter()
+            .map(|key| (key, self.get_touches(&account, &key)))
+            .collect();
+
+        PlayerSnapshot { account, seat, buttons, axes, directions, touches }
+    }
+
+    fn count(map: &HashMap<u8, HashMap<Account, u32>>, who: &Account, key: u8) -> u32 {
+        map.get(&key)
+            .and_then(|counts| counts.get(who))
+            .copied()
+            .unwrap_or(0)
+    }
+}
\ No newline at end of file
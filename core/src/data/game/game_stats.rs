@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+use nogamepads::entry_mutex;
+use crate::data::game::game_data::unix_now;
+use crate::data::game::game_runtime::GameRuntimeData;
+use crate::data::message::message_enums::ExitReason;
+use crate::data::player::player_data::Account;
+
+/// Player statistics
+/// Session history of an account, kept across restarts through the archive
+#[derive(Default, Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct PlayerStats {
+    pub account: Account,
+
+    /// Sessions ordered by join time, the last one is open while the player is online
+    #[serde(default)]
+    pub sessions: Vec<SessionRecord>,
+}
+
+/// Session record
+/// A stay of the player, from joining to leaving the game
+#[derive(Default, Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct SessionRecord {
+
+    /// Seconds since the Unix epoch
+    pub joined_at: u64,
+
+    /// Seconds since the Unix epoch (None while online)
+    #[serde(default)]
+    pub left_at: Option<u64>,
+
+    /// Why the player left (None while online, Err if timed out)
+    #[serde(default)]
+    pub exit_reason: Option<ExitReason>,
+
+    /// Count of accepted inputs
+    #[serde(default)]
+    pub inputs: u64,
+
+    /// Count of button presses
+    #[serde(default)]
+    pub presses: u64,
+}
+
+impl SessionRecord {
+
+    /// Check if the player is still in the session
+    pub fn is_open(&self) -> bool {
+        self.left_at.is_none()
+    }
+
+    /// Length of the session in seconds, open sessions last until now
+    pub fn play_time(&self) -> u64 {
+        self.left_at.unwrap_or_else(unix_now).saturating_sub(self.joined_at)
+    }
+}
+
+impl PlayerStats {
+
+    /// Count of sessions
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Total play time in seconds
+    pub fn total_play_time(&self) -> u64 {
+        self.sessions.iter().map(SessionRecord::play_time).sum()
+    }
+
+    /// Total count of accepted inputs
+    pub fn total_inputs(&self) -> u64 {
+        self.sessions.iter().map(|session| session.inputs).sum()
+    }
+
+    /// Total count of button presses
+    pub fn total_presses(&self) -> u64 {
+        self.sessions.iter().map(|session| session.presses).sum()
+    }
+
+    /// When the player first joined (Seconds since the Unix epoch)
+    pub fn first_joined_at(&self) -> Option<u64> {
+        self.sessions.first().map(|session| session.joined_at)
+    }
+
+    /// When the player was last seen, now if online (Seconds since the Unix epoch)
+    pub fn last_seen_at(&self) -> Option<u64> {
+        self.sessions.last().map(|session| session.left_at.unwrap_or_else(unix_now))
+    }
+
+    fn open_session(&mut self) -> Option<&mut SessionRecord> {
+        self.sessions.last_mut().filter(|session| session.is_open())
+    }
+}
+
+impl GameRuntimeData {
+
+    /// Get the statistics of account
+    pub fn stats_of(&self, account: &Account) -> Option<PlayerStats> {
+        let mut result = None;
+        entry_mutex!(self.stats, |guard| {
+            result = guard.get(account).cloned();
+        });
+        result
+    }
+
+    /// Returns the statistics of all accounts who joined the game, ordered by account id
+    pub fn all_stats(&self) -> Vec<PlayerStats> {
+        let mut vec = Vec::new();
+        entry_mutex!(self.stats, |guard| {
+            vec = guard.values().cloned().collect();
+        });
+        vec.sort_by(|a, b| a.account.id.cmp(&b.account.id));
+        vec
+    }
+
+    /// Open a session for the account
+    pub(crate) fn begin_session(&self, account: &Account) {
+        entry_mutex!(self.stats, |guard| {
+            let stats = guard.entry(account.clone())
+                .or_insert_with(|| PlayerStats { account: account.clone(), ..Default::default() });
+
+            // A session left open by a crash ends when the next one begins
+            let now = unix_now();
+            if let Some(session) = stats.open_session() {
+                session.left_at = Some(now);
+                session.exit_reason = Some(ExitReason::Err);
+            }
+            stats.sessions.push(SessionRecord { joined_at: now, ..Default::default() });
+        });
+    }
+
+    /// Close the open session of the account
+    pub(crate) fn end_session(&self, account: &Account, reason: ExitReason) {
+        entry_mutex!(self.stats, |guard| {
+            if let Some(session) = guard.get_mut(account).and_then(PlayerStats::open_session) {
+                session.left_at = Some(unix_now());
+                session.exit_reason = Some(reason);
+            }
+        });
+    }
+
+    /// Count an accepted input in the open session of the account
+    pub(crate) fn count_input(&self, account: &Account, press: bool) {
+        entry_mutex!(self.stats, |guard| {
+            if let Some(session) = guard.get_mut(account).and_then(PlayerStats::open_session) {
+                session.inputs += 1;
+                if press {
+                    session.presses += 1;
+                }
+            }
+        });
+    }
+}
\ No newline at end of file
@@ -1,5 +1,13 @@
 pub mod game_cli;
 
+pub mod game_admin;
 pub mod game_data;
+pub mod game_hub;
+pub mod game_input;
+pub mod game_observer;
+pub mod game_profile;
+pub mod game_prompt;
 pub mod game_runtime;
+pub mod game_snapshot;
+pub mod game_stats;
 pub mod types;
\ No newline at end of file
@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 use std::sync::Mutex;
+use std::time::Instant;
 use crate::data::player::player_data::{Account, Player};
 
 pub(crate) type GameInfo = HashMap<String, String>;
 
-pub(crate) type Players = Mutex<HashMap<Account, Player>>;
\ No newline at end of file
+pub(crate) type Players = Mutex<HashMap<Account, Player>>;
+
+/// Latest inputs of smoothed keys, with the time the stored values were set
+pub(crate) type SmoothingTargets<V> = HashMap<u8, HashMap<Account, (V, Instant)>>;
\ No newline at end of file
@@ -0,0 +1,39 @@
+use bincode::{Decode, Encode};
+
+/// Admin commands.
+/// Moderation requests sent by the controllers of admin accounts, players are addressed by their account ids
+#[derive(Encode, Decode, PartialEq, Debug, Clone)]
+pub enum AdminCommand {
+    /// Lock the game, no further joins allowed
+    Lock,
+
+    /// Unlock the game
+    Unlock,
+
+    /// Kick the online player
+    Kick(String),
+
+    /// Ban the online player, with the reason if given
+    Ban(String, Option<String>),
+
+    /// Pardon the banned player
+    Pardon(String),
+
+    /// Close the game
+    Close,
+}
+
+/// Admin command results.
+/// Reply of the game to an admin command
+#[derive(Default, Encode, Decode, PartialEq, Debug, Clone)]
+pub enum AdminResult {
+    /// The command was carried out
+    #[default]
+    Done,
+
+    /// The account is not an admin, or the target is an admin too
+    PermissionDenied,
+
+    /// No online (or banned, for pardons) player with the account id
+    PlayerNotFound,
+}
\ No newline at end of file
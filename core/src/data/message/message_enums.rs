@@ -1,6 +1,15 @@
+use std::collections::HashMap;
 use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use crate::data::game::game_data::GameControlData;
+use crate::data::message::message_admin::{AdminCommand, AdminResult};
 use crate::data::game::types::GameInfo;
-use crate::data::player::player_data::Player;
+use crate::data::message::message_haptics::HapticPattern;
+use crate::data::message::message_prompt::TextPrompt;
+use crate::data::message::message_sensors::MotionData;
+use crate::data::message::message_touch::TouchData;
+use crate::data::player::player_data::{Account, Customize, Player};
+use crate::data::player::player_profile::ProfileError;
 
 /// Control messages.
 /// Messages sent from controller to game pad_client after establishing persistent connection
@@ -26,6 +35,26 @@ pub enum ControlMessage {
     /// Indicates that the value of a direction has been changed
     Dir(u8, (f64, f64)),
 
+    /// Motion input
+    /// Sample of the motion sensors, only accepted if the game enables them
+    Motion(MotionData),
+
+    /// Touch input
+    /// Indicates that a finger touched, moved on or left a touchpad
+    Touch(u8, TouchData),
+
+    /// Prompt reply
+    /// Replies to the text prompt with the id, None if the player dismissed it
+    PromptReply(u32, Option<String>),
+
+    /// Admin command
+    /// Asks the game to moderate, only carried out for admin accounts
+    Admin(AdminCommand),
+
+    /// Profile update
+    /// Asks the game to replace the custom information of the player, the game replies with ProfileChanged or ProfileRejected
+    UpdateProfile(Customize),
+
     /// Exit command
     /// Sends a disconnect request to the pad_server
     Exit,
@@ -50,6 +79,42 @@ pub enum GameMessage {
     /// The message will be handed over to the controller for its own processing
     Msg(String),
 
+    /// Team change
+    /// Notifies the controller of its team (None if unassigned) and its custom information with the team color applied
+    TeamChanged(Option<String>, Customize),
+
+    /// Input rejected
+    /// Notifies the controller that the input of the key was not accepted
+    InputRejected(u8, InputError),
+
+    /// Vibration request
+    /// Asks the controller to play the haptic pattern, controllers without vibration may show it instead
+    Vibrate(HapticPattern),
+
+    /// Sample rate request
+    /// Asks the controller to send motion samples at the rate (Hz)
+    SampleRate(u16),
+
+    /// Text prompt
+    /// Asks the controller to show a text field, the player replies with PromptReply
+    Prompt(TextPrompt),
+
+    /// Prompt cancellation
+    /// Notifies the controller that the prompt with the id is no longer waiting for a reply
+    CancelPrompt(u32),
+
+    /// Admin reply
+    /// Tells the controller how its admin command ended
+    AdminReply(AdminCommand, AdminResult),
+
+    /// Profile change
+    /// Notifies the controller of its custom information as accepted by the game after a profile update, e.g. with a suffixed nickname
+    ProfileChanged(Customize),
+
+    /// Profile rejection
+    /// Notifies the controller that its profile update was not accepted, the previous profile is kept
+    ProfileRejected(ProfileError),
+
     /// Disconnect request
     /// Notifies the pad_client that the connection will be terminated
     LetExit(ExitReason),
@@ -64,7 +129,7 @@ pub enum GameMessage {
 
 /// Exit reasons.
 /// Reason provided when requesting disconnection
-#[derive(Default, Encode, Decode, PartialEq, Debug, Clone)]
+#[derive(Default, Encode, Decode, Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub enum ExitReason {
     /// Normal exit
     /// No specific reason, simply requesting to disconnect
@@ -87,6 +152,22 @@ pub enum ExitReason {
     Err
 }
 
+/// Input errors.
+/// Reason provided when an input is rejected
+#[derive(Default, Encode, Decode, PartialEq, Debug, Clone)]
+pub enum InputError {
+    /// The key is not registered by the game
+    #[default]
+    KeyNotRegistered,
+
+    /// The key is registered with another kind
+    /// e.g. An axis value sent to a button
+    KindMismatch,
+
+    /// The value is out of the range of the key
+    ValueOutOfRange,
+}
+
 /// Connection messages.
 /// Messages sent by pad_client when requesting pad_server connection
 #[derive(Default, Encode, Decode, PartialEq, Debug, Clone)]
@@ -106,6 +187,23 @@ pub enum ConnectionMessage {
     /// Ready state to establish persistent connection
     Ready,
 
+    /// Enter the specified room
+    /// The following requests on this connection target the game hosted in that room
+    EnterRoom(String),
+
+    /// Request for all rooms and their game information
+    RequestRooms,
+
+    /// Request for the keys registered by the game
+    RequestKeys,
+
+    /// Signature of the challenge, proving the key account of the join request
+    Signature(Vec<u8>),
+
+    /// Requests to join the game as a guest with the custom information
+    /// The pad_server generates the account, the guest is a new player every time
+    JoinAsGuest(Customize),
+
     /// Error state
     #[default]
     Err
@@ -118,6 +216,12 @@ pub enum ConnectionResponseMessage {
     /// Game information data
     GameInfos(GameInfo),
 
+    /// Rooms hosted by the pad_server and their game information
+    Rooms(HashMap<String, GameInfo>),
+
+    /// Keys registered by the game
+    Keys(GameControlData),
+
     /// Rejection with reason
     Deny(JoinFailedMessage),
 
@@ -130,6 +234,18 @@ pub enum ConnectionResponseMessage {
     /// Welcome acknowledgment
     Welcome,
 
+    /// Challenge for key accounts
+    /// The pad_client replies with its signature of the bytes before the join is processed
+    Challenge(Vec<u8>),
+
+    /// Welcome acknowledgment for guests, with the account generated for the guest,
+    /// its team (None if unassigned) and its custom information as applied by the game
+    GuestWelcome(Account, Option<String>, Customize),
+
+    /// Welcome acknowledgment for joined players,
+    /// with their team (None if unassigned) and their custom information as applied by the game
+    JoinWelcome(Option<String>, Customize),
+
     /// Error state
     #[default]
     Err
@@ -142,12 +258,34 @@ pub enum JoinFailedMessage {
     /// Game already contains identical player
     ContainIdenticalPlayer,
 
-    /// Player is banned
-    PlayerBanned,
+    /// Player is banned, with the reason if given
+    /// and the seconds until the ban is lifted (Permanent if not set)
+    PlayerBanned(Option<String>, Option<u64>),
 
     /// Game is locked, no further joins allowed
     GameLocked,
 
+    /// The requested room is not hosted by the pad_server
+    RoomNotFound,
+
+    /// All seats of the game are taken
+    GameFull,
+
+    /// The game only admits the accounts on its allow list
+    NotAllowed,
+
+    /// Player was kicked recently, with the seconds until it can rejoin
+    KickCooldown(u64),
+
+    /// The key account didn't sign the challenge with its key
+    IdentityNotProven,
+
+    /// The game doesn't admit guests
+    GuestsNotAllowed,
+
+    /// The custom information of the player was not accepted
+    InvalidProfile(ProfileError),
+
     /// Unknown error
     #[default]
     UnknownError
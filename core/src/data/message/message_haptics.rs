@@ -0,0 +1,132 @@
+use bincode::{Decode, Encode};
+
+/// Haptic patterns.
+/// Vibration played by the controller
+#[derive(Encode, Decode, PartialEq, Debug, Clone)]
+pub enum HapticPattern {
+    /// Named preset, the controller may play its native effect instead of the pulses
+    Preset(HapticPreset),
+
+    /// Pulses played in order
+    Custom(Vec<HapticPulse>),
+}
+
+/// Haptic presets.
+/// Common vibrations known by all controllers
+#[derive(Default, Encode, Decode, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum HapticPreset {
+    /// Short and light, e.g. A button is accepted
+    #[default]
+    Tap,
+
+    /// Two short taps
+    DoubleTap,
+
+    /// Long and strong, e.g. The player is hit
+    Long,
+
+    /// Two beats of a heart, e.g. Low health
+    Heartbeat,
+
+    /// Three strong taps, e.g. An action failed
+    Error,
+}
+
+/// Haptic pulse.
+/// Vibrates at the intensity for the duration, a pulse with 0 intensity is a pause
+#[derive(Default, Encode, Decode, PartialEq, Debug, Clone, Copy)]
+pub struct HapticPulse {
+    /// Duration in milliseconds
+    pub duration_ms: u32,
+
+    /// Intensity (Range: 0 - 1)
+    pub intensity: f32,
+}
+
+impl Default for HapticPattern {
+    fn default() -> Self {
+        HapticPattern::Preset(HapticPreset::default())
+    }
+}
+
+impl From<HapticPreset> for HapticPattern {
+    fn from(preset: HapticPreset) -> Self {
+        HapticPattern::Preset(preset)
+    }
+}
+
+impl From<Vec<HapticPulse>> for HapticPattern {
+    fn from(pulses: Vec<HapticPulse>) -> Self {
+        HapticPattern::Custom(pulses)
+    }
+}
+
+impl HapticPattern {
+
+    /// Pulses of the pattern, presets are expanded
+    pub fn pulses(&self) -> Vec<HapticPulse> {
+        match self {
+            HapticPattern::Preset(preset) => { preset.pulses() }
+            HapticPattern::Custom(pulses) => { pulses.clone() }
+        }
+    }
+
+    /// Total duration in milliseconds
+    pub fn duration_ms(&self) -> u32 {
+        self.pulses().iter().map(|pulse| pulse.duration_ms).sum()
+    }
+}
+
+impl HapticPreset {
+
+    /// All presets
+    pub const ALL: [HapticPreset; 5] = [
+        HapticPreset::Tap,
+        HapticPreset::DoubleTap,
+        HapticPreset::Long,
+        HapticPreset::Heartbeat,
+        HapticPreset::Error,
+    ];
+
+    /// Pulses played for the preset when the controller has no native effect
+    pub fn pulses(&self) -> Vec<HapticPulse> {
+        let pulse = HapticPulse::new;
+        let pause = HapticPulse::pause;
+        match self {
+            HapticPreset::Tap => { vec![pulse(40, 0.5)] }
+            HapticPreset::DoubleTap => { vec![pulse(40, 0.5), pause(60), pulse(40, 0.5)] }
+            HapticPreset::Long => { vec![pulse(400, 1.0)] }
+            HapticPreset::Heartbeat => { vec![pulse(80, 0.8), pause(100), pulse(120, 0.5)] }
+            HapticPreset::Error => { vec![pulse(60, 1.0), pause(50), pulse(60, 1.0), pause(50), pulse(60, 1.0)] }
+        }
+    }
+
+    /// Name of the preset
+    pub fn name(&self) -> &'static str {
+        match self {
+            HapticPreset::Tap => { "tap" }
+            HapticPreset::DoubleTap => { "double-tap" }
+            HapticPreset::Long => { "long" }
+            HapticPreset::Heartbeat => { "heartbeat" }
+            HapticPreset::Error => { "error" }
+        }
+    }
+
+    /// Find the preset by its name (Case insensitive)
+    pub fn from_name(name: &str) -> Option<HapticPreset> {
+        Self::ALL.into_iter().find(|preset| preset.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+impl HapticPulse {
+
+    /// Create a pulse, the intensity is clamped into 0 - 1
+    pub fn new(duration_ms: u32, intensity: f32) -> HapticPulse {
+        HapticPulse { duration_ms, intensity: intensity.clamp(0.0, 1.0) }
+    }
+
+    /// Create a pause
+    pub fn pause(duration_ms: u32) -> HapticPulse {
+        HapticPulse { duration_ms, intensity: 0.0 }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use bincode::{Decode, Encode};
+
+/// Text prompt.
+/// Asks a player to type something, the controller replies with the same id
+#[derive(Default, Encode, Decode, PartialEq, Debug, Clone)]
+pub struct TextPrompt {
+    /// Request id, assigned by the game runtime
+    pub id: u32,
+
+    /// Title shown above the text field
+    pub title: String,
+
+    /// Hint shown in the empty text field
+    pub placeholder: String,
+
+    /// Maximum count of characters (Unlimited if not set)
+    pub max_length: Option<u32>,
+
+    /// Kind of the text field
+    pub kind: TextInputKind,
+}
+
+/// Text input kinds.
+/// Lets the controller pick a fitting keyboard
+#[derive(Default, Encode, Decode, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TextInputKind {
+    /// Single line of text
+    #[default]
+    Text,
+
+    /// Numbers only
+    Number,
+
+    /// Hidden text
+    Password,
+
+    /// Several lines of text
+    Multiline,
+}
+
+/// Prompt result.
+/// How a text prompt was resolved on the game side
+#[derive(PartialEq, Debug, Clone)]
+pub enum PromptResult {
+    /// The player submitted the text
+    Submitted(String),
+
+    /// The player dismissed the prompt
+    Dismissed,
+
+    /// No reply before the timeout
+    TimedOut,
+
+    /// The game cancelled the prompt
+    Cancelled,
+
+    /// The player left before replying
+    Disconnected,
+}
+
+impl TextPrompt {
+
+    /// Create a prompt with the title
+    pub fn new(title: String) -> TextPrompt {
+        TextPrompt { title, ..Default::default() }
+    }
+
+    /// Set the placeholder
+    pub fn placeholder(&mut self, placeholder: String) -> &mut TextPrompt {
+        self.placeholder = placeholder;
+        self
+    }
+
+    /// Set the maximum count of characters
+    pub fn max_length(&mut self, max_length: u32) -> &mut TextPrompt {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Set the kind of the text field
+    pub fn kind(&mut self, kind: TextInputKind) -> &mut TextPrompt {
+        self.kind = kind;
+        self
+    }
+
+    /// Cut the text to the maximum length
+    pub fn limit(&self, text: String) -> String {
+        match self.max_length {
+            Some(max_length) if text.chars().count() > max_length as usize => {
+                text.chars().take(max_length as usize).collect()
+            }
+            _ => { text }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use bincode::{Decode, Encode};
+
+/// Motion sample.
+/// Values read from the motion sensors of the controller at the same moment
+#[derive(Encode, Decode, PartialEq, Debug, Clone, Copy)]
+pub struct MotionData {
+    /// Acceleration including gravity (x, y, z), in m/s²
+    pub acceleration: [f64; 3],
+
+    /// Angular velocity around each axis (x, y, z), in rad/s
+    pub angular_velocity: [f64; 3],
+
+    /// Orientation quaternion (x, y, z, w)
+    pub orientation: [f64; 4],
+}
+
+impl Default for MotionData {
+    fn default() -> Self {
+        MotionData {
+            acceleration: [0.0; 3],
+            angular_velocity: [0.0; 3],
+            orientation: [0.0, 0.0, 0.0, 1.0],
+        }
+    }
+}
+
+impl MotionData {
+
+    /// Create a motion sample
+    pub fn new(acceleration: [f64; 3], angular_velocity: [f64; 3], orientation: [f64; 4]) -> MotionData {
+        MotionData { acceleration, angular_velocity, orientation }
+    }
+
+    /// Check if all values are finite
+    pub fn is_valid(&self) -> bool {
+        self.acceleration.iter()
+            .chain(self.angular_velocity.iter())
+            .chain(self.orientation.iter())
+            .all(|value| value.is_finite())
+    }
+
+    /// Orientation scaled to a unit quaternion, identity if the length is 0
+    pub fn normalized_orientation(&self) -> [f64; 4] {
+        let [x, y, z, w] = self.orientation;
+        let length = (x * x + y * y + z * z + w * w).sqrt();
+        if length > 0.0 {
+            [x / length, y / length, z / length, w / length]
+        } else {
+            [0.0, 0.0, 0.0, 1.0]
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use bincode::{Decode, Encode};
+
+/// Touch sample.
+/// State of a finger on a touchpad, fingers are identified by their touch ids
+#[derive(Default, Encode, Decode, PartialEq, Debug, Clone, Copy)]
+pub struct TouchData {
+    /// Touch id, stays the same from the begin to the end of a touch
+    pub id: u32,
+
+    /// Phase of the touch
+    pub phase: TouchPhase,
+
+    /// Normalized position (Range: 0 - 1, from left to right)
+    pub x: f64,
+
+    /// Normalized position (Range: 0 - 1, from top to bottom)
+    pub y: f64,
+
+    /// Pressure (Range: 0 - 1, 1 if the device can't measure it)
+    pub pressure: f64,
+}
+
+/// Touch phases.
+#[derive(Default, Encode, Decode, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TouchPhase {
+    /// The finger touched the touchpad
+    #[default]
+    Begin,
+
+    /// The finger moved on the touchpad
+    Move,
+
+    /// The finger left the touchpad
+    End,
+}
+
+/// Touch point.
+/// A finger currently on the touchpad
+#[derive(Default, PartialEq, Debug, Clone, Copy)]
+pub struct TouchPoint {
+    pub id: u32,
+    pub x: f64,
+    pub y: f64,
+    pub pressure: f64,
+}
+
+impl TouchData {
+
+    /// Create a touch sample
+    pub fn new(id: u32, phase: TouchPhase, x: f64, y: f64, pressure: f64) -> TouchData {
+        TouchData { id, phase, x, y, pressure }
+    }
+
+    /// The point of the touch
+    pub fn point(&self) -> TouchPoint {
+        TouchPoint { id: self.id, x: self.x, y: self.y, pressure: self.pressure }
+    }
+}
\ No newline at end of file
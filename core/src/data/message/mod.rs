@@ -1,3 +1,8 @@
+pub mod message_admin;
 pub mod message_encoders;
 pub mod message_enums;
+pub mod message_haptics;
+pub mod message_prompt;
+pub mod message_sensors;
+pub mod message_touch;
 pub mod traits;
\ No newline at end of file
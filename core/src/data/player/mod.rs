@@ -1,3 +1,4 @@
+pub mod player_credential;
 pub mod player_data;
-
-pub const ACCOUNT_HASH_SALT : &str = env!("TEST_PLAYER_ACCOUNT");
\ No newline at end of file
+pub mod player_identity;
+pub mod player_profile;
\ No newline at end of file
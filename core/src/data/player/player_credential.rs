@@ -0,0 +1,139 @@
+use std::env;
+use std::sync::Once;
+use argon2::{Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version};
+use argon2::password_hash::SaltString;
+use hex::encode;
+use log::warn;
+use rand_core::OsRng;
+use sha1::{Digest, Sha1};
+use crate::data::player::player_identity::PUBLIC_KEY_PREFIX;
+
+/// Environment variable holding the optional secret mixed into new credentials
+/// Changing it makes the credentials created with the old one unusable
+pub const ACCOUNT_PEPPER_ENV: &str = "NOGAMEPADS_ACCOUNT_PEPPER";
+
+/// Environment variable holding the salt of the legacy credentials
+/// Only needed to unlock accounts created before the credential format was versioned
+pub const LEGACY_SALT_ENV: &str = "NOGAMEPADS_LEGACY_SALT";
+
+/// Salt the legacy credentials were built with, used with a warning if LEGACY_SALT_ENV is not set
+const DEFAULT_LEGACY_SALT: &str = "ame_na_lisa";
+
+static DEFAULT_LEGACY_SALT_WARNING: Once = Once::new();
+
+/// Prefix of the player hash of guest accounts, followed by a random token
+pub const GUEST_PREFIX: &str = "guest:";
+
+/// Credential formats.
+/// How the player hash of an account was derived from its password
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CredentialVersion {
+    /// SHA-1 of the id, the password and a salt shared by all accounts
+    Legacy,
+
+    /// Argon2id with a random salt per account, stored as a PHC string
+    Argon2,
+
+    /// Ed25519 public key, the account has no password and proves itself by signing challenges
+    PublicKey,
+
+    /// Ephemeral guest account generated by the pad_server, it has no credentials
+    Guest,
+}
+
+impl CredentialVersion {
+
+    /// Current format of new credentials
+    pub const CURRENT: CredentialVersion = CredentialVersion::Argon2;
+
+    /// Detect the format of the player hash
+    pub fn of(player_hash: &str) -> CredentialVersion {
+        if player_hash.starts_with("$argon2") {
+            CredentialVersion::Argon2
+        } else if player_hash.starts_with(PUBLIC_KEY_PREFIX) {
+            CredentialVersion::PublicKey
+        } else if player_hash.starts_with(GUEST_PREFIX) {
+            CredentialVersion::Guest
+        } else {
+            CredentialVersion::Legacy
+        }
+    }
+}
+
+/// Credential errors.
+/// Reason a player hash cannot be derived from the password
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum CredentialError {
+    /// The secret in ACCOUNT_PEPPER_ENV is not accepted by the hash function
+    InvalidPepper(String),
+
+    /// The password could not be hashed
+    HashFailed(String),
+}
+
+/// Derive a player hash from the password with a fresh random salt
+pub(crate) fn derive(processed_id: &str, password: &str) -> Result<String, CredentialError> {
+    let pepper = pepper();
+    let salt = SaltString::generate(&mut OsRng);
+    argon2(&pepper)?
+        .hash_password(credential_input(processed_id, password).as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|err| CredentialError::HashFailed(err.to_string()))
+}
+
+/// Check the password against the player hash, in any supported format
+pub(crate) fn verify(processed_id: &str, password: &str, player_hash: &str) -> bool {
+    match CredentialVersion::of(player_hash) {
+        CredentialVersion::Legacy => {
+            legacy_hash(processed_id, password) == player_hash
+        }
+        CredentialVersion::Argon2 => {
+            let Ok(hash) = PasswordHash::new(player_hash) else {
+                return false;
+            };
+            let pepper = pepper();
+            let Ok(argon2) = argon2(&pepper) else {
+                return false;
+            };
+            argon2.verify_password(credential_input(processed_id, password).as_bytes(), &hash).is_ok()
+        }
+        CredentialVersion::PublicKey | CredentialVersion::Guest => { false }
+    }
+}
+
+/// The id is bound to the hash, so the same password gives unrelated hashes for other accounts
+fn credential_input(processed_id: &str, password: &str) -> String {
+    format!("{}\u{0}{}", processed_id, password)
+}
+
+/// The pepper is never dropped silently, a rejected one fails instead of hashing without it
+fn argon2(pepper: &[u8]) -> Result<Argon2<'_>, CredentialError> {
+    if pepper.is_empty() {
+        return Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, Params::default()));
+    }
+    Argon2::new_with_secret(pepper, Algorithm::Argon2id, Version::V0x13, Params::default())
+        .map_err(|err| CredentialError::InvalidPepper(err.to_string()))
+}
+
+fn pepper() -> Vec<u8> {
+    env::var(ACCOUNT_PEPPER_ENV).unwrap_or_default().into_bytes()
+}
+
+fn legacy_hash(processed_id: &str, password: &str) -> String {
+    let salt = legacy_salt();
+    let combined = format!("{}{}{}", processed_id, password, salt);
+    let mut hasher = Sha1::new();
+    hasher.update(combined);
+    let result = hasher.finalize();
+    encode(&result[..])
+}
+
+/// The built-in salt is public, so relying on it is reported once
+fn legacy_salt() -> String {
+    env::var(LEGACY_SALT_ENV).unwrap_or_else(|_| {
+        DEFAULT_LEGACY_SALT_WARNING.call_once(|| {
+            warn!("[Player Credential] {} is not set, legacy credentials are checked with the built-in salt.", LEGACY_SALT_ENV);
+        });
+        DEFAULT_LEGACY_SALT.to_string()
+    })
+}
\ No newline at end of file
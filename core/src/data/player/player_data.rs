@@ -1,9 +1,14 @@
-use crate::data::player::ACCOUNT_HASH_SALT;
-use hex::encode;
-use sha1::{Digest, Sha1};
+use crate::data::player::player_credential;
+use crate::data::player::player_credential::GUEST_PREFIX;
+use crate::data::player::player_credential::{CredentialError, CredentialVersion};
+use crate::data::player::player_identity::PlayerKey;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::mem;
 use bincode::{Decode, Encode};
+use hex::encode;
+use rand_core::{OsRng, RngCore};
 use serde::{Deserialize, Serialize};
 use nogamepads::string_utils::process_id_text;
 
@@ -16,7 +21,13 @@ pub struct Player {
     pub account: Account,
 
     /// Custom information (Optional)
-    pub customize: Option<Customize>
+    pub customize: Option<Customize>,
+
+    /// Legacy player hash of the account before its credentials were upgraded
+    /// Games keep knowing the upgraded account by it, so its bans, seats and stats are carried over
+    /// It is sent on every join for as long as it is kept, clearing it makes games see a new account
+    #[serde(default)]
+    pub previous_hash: Option<String>
 }
 
 /// Account information
@@ -46,29 +57,75 @@ pub struct Customize {
     pub color_saturation: f64,
 
     /// HSV Color - Value (Range: 0 - 1)
-    pub color_value: f64
+    pub color_value: f64,
+
+    /// Extra profile attributes, such as a preferred language or an avatar reference
+    #[serde(default)]
+    pub attributes: HashMap<String, String>
 }
 
 impl Player {
 
     /// Create new player information using a username and password
-    pub fn register(id: String, password: String) -> Player {
+    /// The player hash is salted randomly, save it and restore the player with login, registering again creates another account
+    pub fn register(id: String, password: String) -> Result<Player, CredentialError> {
         let mut player = Player {
             customize: None,
-            account: Account::default()
+            account: Account::default(),
+            previous_hash: None
         };
 
         let processed_id = process_id_text(id);
 
         player.account.id = processed_id.clone();
-        player.account.player_hash = Self::gen_hash(processed_id, password);
-        player
+        player.account.player_hash = player_credential::derive(&processed_id, &password)?;
+        Ok(player)
+    }
+
+    /// Restore a registered player from its saved player hash
+    /// Returns None if the password does not match the hash
+    pub fn login(id: String, password: String, player_hash: String) -> Option<Player> {
+        let player = Player {
+            customize: None,
+            account: Account { id: process_id_text(id), player_hash },
+            previous_hash: None
+        };
+
+        if player.check(password) { Some(player) } else { None }
+    }
+
+    /// Create new player information identified by the public key
+    /// The account id is the fingerprint of the key, the account has no password
+    pub fn register_with_key(key: &PlayerKey) -> Player {
+        Player {
+            customize: None,
+            account: key.account(),
+            previous_hash: None
+        }
+    }
+
+    /// Create an ephemeral guest with the custom information chosen by the player
+    /// The account id and hash are random, so the guest is a new player every time it joins
+    pub(crate) fn guest(customize: Customize) -> Player {
+        let mut id = [0u8; 4];
+        let mut token = [0u8; 16];
+        OsRng.fill_bytes(&mut id);
+        OsRng.fill_bytes(&mut token);
+        Player {
+            customize: Some(customize),
+            account: Account {
+                id: format!("guest_{}", encode(id)),
+                player_hash: format!("{}{}", GUEST_PREFIX, encode(token))
+            },
+            previous_hash: None
+        }
     }
 
     pub fn register_from_hash(hash: String) -> Player {
         let mut player = Player {
             customize: None,
-            account: Account::default()
+            account: Account::default(),
+            previous_hash: None
         };
 
         player.account.id = "$only_hash$".to_string();
@@ -77,16 +134,50 @@ impl Player {
     }
 
     pub fn check(&self, password: String) -> bool {
-        let hash = Self::gen_hash(self.account.id.clone(), password);
-        hash == self.account.player_hash
+        player_credential::verify(&self.account.id, &password, &self.account.player_hash)
+    }
+
+    /// Format of the player hash
+    pub fn credential_version(&self) -> CredentialVersion {
+        CredentialVersion::of(&self.account.player_hash)
+    }
+
+    /// Check if the player hash is in an older format
+    pub fn needs_migration(&self) -> bool {
+        self.credential_version() == CredentialVersion::Legacy
     }
 
-    fn gen_hash(processed_id: String, password: String) -> String {
-        let combined = format!("{}{}{}", processed_id, password, ACCOUNT_HASH_SALT);
-        let mut hasher = Sha1::new();
-        hasher.update(combined);
-        let result = hasher.finalize();
-        encode(&result[..])
+    /// Check if the account is identified by a public key instead of a password
+    pub fn is_key_account(&self) -> bool {
+        self.credential_version() == CredentialVersion::PublicKey
+    }
+
+    /// Check if the player is an ephemeral guest
+    pub fn is_guest(&self) -> bool {
+        self.account.is_guest()
+    }
+
+    /// Check the password and derive the player hash again in the current format if it is older
+    /// Returns true if migrated, the older hash is kept as the previous hash
+    pub fn migrate(&mut self, password: String) -> Result<bool, CredentialError> {
+        if !self.needs_migration() || !self.check(password.clone()) {
+            return Ok(false);
+        }
+        let player_hash = player_credential::derive(&self.account.id, &password)?;
+        self.previous_hash = Some(mem::replace(&mut self.account.player_hash, player_hash));
+        Ok(true)
+    }
+
+    /// Returns the player with the account games know it by
+    /// Upgraded accounts are known by their previous hash for as long as the controller keeps it,
+    /// only legacy hashes are accepted as previous hashes
+    pub(crate) fn into_known(mut self) -> Player {
+        if let Some(previous) = self.previous_hash.take()
+            && self.credential_version() == CredentialVersion::Argon2
+            && CredentialVersion::of(&previous) == CredentialVersion::Legacy {
+            self.account.player_hash = previous;
+        }
+        self
     }
 }
 
@@ -123,6 +214,20 @@ impl Player {
         })
     }
 
+    /// Set a profile attribute, an empty value removes it
+    pub fn attribute(&mut self, key: &str, value: &str) -> &mut Player {
+        let key = key.trim().to_lowercase();
+        let value = value.to_string();
+        self.change(|custom| {
+            if value.is_empty() {
+                custom.attributes.remove(&key);
+            } else {
+                custom.attributes.insert(key, value);
+            }
+            custom
+        })
+    }
+
     fn init(&mut self) {
         if self.customize.is_none() {
             self.customize = Some(Customize::default());
@@ -161,11 +266,29 @@ impl From<Account> for Player {
     fn from(account: Account) -> Self {
         Player {
             account,
-            customize: None
+            customize: None,
+            previous_hash: None
         }
     }
 }
 
+impl Customize {
+
+    /// Get the value of a profile attribute
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.get(key).map(String::as_str)
+    }
+}
+
+impl Account {
+
+    /// Check if the account is an ephemeral guest generated by the pad_server
+    /// Guests are never archived
+    pub fn is_guest(&self) -> bool {
+        CredentialVersion::of(&self.player_hash) == CredentialVersion::Guest
+    }
+}
+
 impl Display for Account {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.id.as_str())
@@ -0,0 +1,124 @@
+use std::fmt::{Debug, Formatter};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hex::{decode, encode};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::data::player::player_data::Account;
+
+/// Prefix of the player hash of key accounts, followed by the public key in hex
+pub const PUBLIC_KEY_PREFIX: &str = "ed25519:";
+
+/// Length of the challenges the pad_server asks key accounts to sign
+pub const CHALLENGE_LENGTH: usize = 32;
+
+/// Domain of the signed challenges, so the signatures can't be reused for anything else
+const CHALLENGE_DOMAIN: &[u8] = b"nogamepads:join:";
+
+/// Player key.
+/// Ed25519 keypair of a key account, kept by the controller side and never sent to the game
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlayerKey {
+
+    /// Secret key in hex
+    secret: String
+}
+
+impl PlayerKey {
+
+    /// Generate a new keypair
+    pub fn generate() -> PlayerKey {
+        let mut secret = [0u8; 32];
+        OsRng.fill_bytes(&mut secret);
+        PlayerKey { secret: encode(secret) }
+    }
+
+    /// Restore the keypair from the secret key in hex
+    pub fn from_secret(secret: &str) -> Option<PlayerKey> {
+        let bytes: [u8; 32] = decode(secret.trim()).ok()?.try_into().ok()?;
+        Some(PlayerKey { secret: encode(bytes) })
+    }
+
+    /// Secret key in hex
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    /// Public key in hex
+    pub fn public_key(&self) -> String {
+        encode(self.signing_key().verifying_key().as_bytes())
+    }
+
+    /// Fingerprint of the public key, used as the account id
+    pub fn fingerprint(&self) -> String {
+        fingerprint_of(self.signing_key().verifying_key().as_bytes())
+    }
+
+    /// Account identified by the public key
+    pub fn account(&self) -> Account {
+        Account {
+            id: self.fingerprint(),
+            player_hash: format!("{}{}", PUBLIC_KEY_PREFIX, self.public_key()),
+        }
+    }
+
+    /// Sign the challenge sent by the pad_server
+    /// The signature is bound to the room the player joins, None for the default room
+    pub fn sign(&self, challenge: &[u8], room: Option<&str>) -> Vec<u8> {
+        self.signing_key().sign(&challenge_message(challenge, room)).to_bytes().to_vec()
+    }
+
+    fn signing_key(&self) -> SigningKey {
+        let bytes: [u8; 32] = decode(&self.secret).ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .unwrap_or_default();
+        SigningKey::from_bytes(&bytes)
+    }
+}
+
+impl Debug for PlayerKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PlayerKey")
+            .field("fingerprint", &self.fingerprint())
+            .finish()
+    }
+}
+
+/// Fingerprint of the public key: the first 16 bytes of its SHA-256 in hex
+pub fn fingerprint_of(public_key: &[u8]) -> String {
+    let digest = Sha256::digest(public_key);
+    encode(&digest[..16])
+}
+
+/// Generate a random challenge for a key account
+pub(crate) fn challenge() -> Vec<u8> {
+    let mut challenge = vec![0u8; CHALLENGE_LENGTH];
+    OsRng.fill_bytes(&mut challenge);
+    challenge
+}
+
+/// Check that the signature of the challenge was made by the key of the account for the room,
+/// and that the account id is the fingerprint of that key
+pub(crate) fn verify_challenge(account: &Account, challenge: &[u8], room: Option<&str>, signature: &[u8]) -> bool {
+    let Some(public_key) = account.player_hash.strip_prefix(PUBLIC_KEY_PREFIX) else {
+        return false;
+    };
+    let Some(public_key) = decode(public_key).ok().and_then(|bytes| <[u8; 32]>::try_from(bytes).ok()) else {
+        return false;
+    };
+    if fingerprint_of(&public_key) != account.id {
+        return false;
+    }
+    let Ok(verifying_key) = VerifyingKey::from_bytes(&public_key) else {
+        return false;
+    };
+    let Ok(signature) = Signature::from_slice(signature) else {
+        return false;
+    };
+    verifying_key.verify(&challenge_message(challenge, room), &signature).is_ok()
+}
+
+/// Only values both sides agree on are signed, addresses differ between them behind NAT or proxies
+fn challenge_message(challenge: &[u8], room: Option<&str>) -> Vec<u8> {
+    [CHALLENGE_DOMAIN, room.unwrap_or_default().as_bytes(), b"\0", challenge].concat()
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use bincode::{Decode, Encode};
+use crate::data::player::player_data::Customize;
+
+/// Maximum length of nicknames, in characters
+pub const NICKNAME_MAX_LENGTH: usize = 24;
+
+/// Maximum number of attributes of a profile
+pub const MAX_ATTRIBUTES: usize = 16;
+
+/// Maximum length of attribute keys, in characters
+pub const ATTRIBUTE_KEY_MAX_LENGTH: usize = 32;
+
+/// Maximum length of attribute values, in characters
+pub const ATTRIBUTE_VALUE_MAX_LENGTH: usize = 256;
+
+/// Profile errors.
+/// Reason provided when the custom information of a player is not accepted
+#[derive(Default, Encode, Decode, PartialEq, Debug, Clone)]
+pub enum ProfileError {
+    /// The nickname is longer than NICKNAME_MAX_LENGTH
+    NicknameTooLong,
+
+    /// The nickname contains characters other than letters, digits, spaces and "_-."
+    #[default]
+    InvalidNickname,
+
+    /// Another online player uses the nickname, and the game doesn't suffix it
+    NicknameTaken,
+
+    /// The profile has more than MAX_ATTRIBUTES attributes
+    TooManyAttributes,
+
+    /// The attribute key is empty, too long, or contains characters other than lowercase letters, digits and "_-."
+    InvalidAttributeKey(String),
+
+    /// The value of the attribute is too long or contains control characters
+    InvalidAttributeValue(String),
+}
+
+/// Normalize the custom information and check it
+/// The nickname is trimmed and the color is clamped, an empty nickname means the player has none
+pub fn validate(customize: &mut Customize) -> Result<(), ProfileError> {
+    customize.nickname = customize.nickname.trim().to_string();
+    customize.color_hue = customize.color_hue.clamp(0, 360);
+    customize.color_saturation = customize.color_saturation.clamp(0.0, 1.0);
+    customize.color_value = customize.color_value.clamp(0.0, 1.0);
+
+    if customize.nickname.chars().count() > NICKNAME_MAX_LENGTH {
+        return Err(ProfileError::NicknameTooLong);
+    }
+    if !customize.nickname.chars().all(is_nickname_char) {
+        return Err(ProfileError::InvalidNickname);
+    }
+
+    if customize.attributes.len() > MAX_ATTRIBUTES {
+        return Err(ProfileError::TooManyAttributes);
+    }
+    for (key, value) in &customize.attributes {
+        if !is_attribute_key(key) {
+            return Err(ProfileError::InvalidAttributeKey(key.clone()));
+        }
+        if value.chars().count() > ATTRIBUTE_VALUE_MAX_LENGTH || value.chars().any(char::is_control) {
+            return Err(ProfileError::InvalidAttributeValue(key.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Key used to compare nicknames, so "Player" and "player" are the same name
+pub fn nickname_key(nickname: &str) -> String {
+    nickname.trim().to_lowercase()
+}
+
+/// The nickname with the number appended, e.g. "Player 2"
+/// The nickname is cut so the result never exceeds NICKNAME_MAX_LENGTH
+pub fn suffixed(nickname: &str, number: usize) -> String {
+    let suffix = format!(" {}", number);
+    let keep = NICKNAME_MAX_LENGTH.saturating_sub(suffix.chars().count());
+    let base: String = nickname.chars().take(keep).collect();
+    format!("{}{}", base.trim_end(), suffix)
+}
+
+fn is_nickname_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.')
+}
+
+fn is_attribute_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.chars().count() <= ATTRIBUTE_KEY_MAX_LENGTH
+        && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-' | '.'))
+}
\ No newline at end of file
@@ -1,12 +1,12 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::Ordering::SeqCst;
 use log::{error, info, trace, warn};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
 use tokio::spawn;
 use nogamepads::entry_mutex;
-use crate::data::message::message_enums::{ControlMessage, GameMessage};
+use crate::data::game::game_runtime::GameRuntime;
+use crate::data::message::message_enums::{ControlMessage, ExitReason, GameMessage};
 use crate::data::message::message_enums::ExitReason::GameOver;
 use crate::data::message::message_enums::GameMessage::{End, LetExit};
 use crate::data::message::traits::{MessageEncoder, MessageManager};
@@ -15,35 +15,36 @@ use crate::service::service_types::ServiceType;
 use crate::service::service_types::ServiceType::TCPConnection;
 use crate::service::tcp_network::pad_client::pad_client_service::PadClientNetwork;
 use crate::service::tcp_network::pad_server::pad_server_service::PadServerNetwork;
+use crate::service::tcp_network::utils::stream_utils::{read_frame, write_frame};
 
 impl PadServerNetwork {
 
-    pub async fn start_long_connection(self: Arc<Self>, player: Player, stream: TcpStream) {
+    pub async fn start_long_connection(self: Arc<Self>, runtime: Arc<Mutex<GameRuntime>>, player: Player, stream: TcpStream) {
         let (reader, writer) = stream.into_split();
-        spawn(Self::read_task(Arc::clone(&self), player.clone(), reader));
-        spawn(Self::write_task(Arc::clone(&self), player.clone(), writer));
+        spawn(Self::read_task(Arc::clone(&runtime), player.clone(), reader));
+        spawn(Self::write_task(Arc::clone(&runtime), player.clone(), writer));
     }
 
-    async fn read_task(self: Arc<Self>, player: Player, mut reader: OwnedReadHalf) {
+    async fn read_task(runtime: Arc<Mutex<GameRuntime>>, player: Player, mut reader: OwnedReadHalf) {
         info!("[TCP Server] [Runtime] Reader started.");
-        entry_mutex!(self.runtime, |guard| {
+        entry_mutex!(runtime, |guard| {
             guard.reader_count += 1;
         });
 
-        let mut buffer = [0u8; 1024];
         let mut err_message_counter = 0;
 
         loop {
-            let read = reader.read(&mut buffer).await;
+            let read = read_frame(&mut reader).await;
             match read {
-                Ok(size) => {
-                    let message : ControlMessage = ControlMessage::de(buffer[0..size].to_vec());
+                Ok(bytes) => {
+                    let message : ControlMessage = ControlMessage::de(bytes);
 
                     // Preprocess messages: handle exit messages.
                     match message {
                         ControlMessage::Exit => {
                             info!("[TCP Server] [Runtime] Player {} exited.", player.account.id);
-                            entry_mutex!(self.runtime, |guard| {
+                            entry_mutex!(runtime, |guard| {
+                                guard.data.mark_exit_reason(&player.account, ExitReason::Exit);
                                 guard.send((player.account.clone(), End), player.account.clone(), TCPConnection);
                             });
                             break;
@@ -55,7 +56,7 @@ impl PadServerNetwork {
                                 err_message_counter += 1;
                             } else {
                                 warn!("[TCP Server] [Runtime] Too many error messages! Connection closed.");
-                                entry_mutex!(self.runtime, |guard| {
+                                entry_mutex!(runtime, |guard| {
                                     guard.send((player.account.clone(), End), player.account.clone(), TCPConnection);
                                 });
                                 break;
@@ -66,7 +67,7 @@ impl PadServerNetwork {
                     }
 
                     // Process messages
-                    entry_mutex!(self.runtime, |guard| {
+                    entry_mutex!(runtime, |guard| {
                         trace!("[TCP Server] [Runtime] Received: {:?}", &message);
                         guard.put_into_receive_list((player.account.clone(), message), player.account.clone(), ServiceType::TCPConnection);
                     });
@@ -74,12 +75,15 @@ impl PadServerNetwork {
 
                 Err(error) => {
                     warn!("[TCP Server] [Runtime] Error reading from socket: {:?}", error);
+                    entry_mutex!(runtime, |guard| {
+                        guard.report_connection_error(&player.account, error.to_string());
+                    });
                     break;
                 }
             }
 
             // Check close
-            entry_mutex!(self.runtime, |guard| {
+            entry_mutex!(runtime, |guard| {
                 if guard.data.close.load(SeqCst) {
                     break;
                 }
@@ -87,15 +91,15 @@ impl PadServerNetwork {
         }
 
         info!("[TCP Server] [Runtime] Reader between {} closed.", player.account.id);
-        entry_mutex!(self.runtime, |guard| {
+        entry_mutex!(runtime, |guard| {
             guard.send((player.account.clone(), End), player.account.clone(), TCPConnection);
             guard.reader_count -= 1;
         })
     }
 
-    async fn write_task(self: Arc<Self>, player: Player, mut writer: OwnedWriteHalf) {
+    async fn write_task(runtime: Arc<Mutex<GameRuntime>>, player: Player, mut writer: OwnedWriteHalf) {
         info!("[TCP Server] [Runtime] Writer started.");
-        entry_mutex!(self.runtime, |guard| {
+        entry_mutex!(runtime, |guard| {
             guard.writer_count += 1;
         });
 
@@ -103,15 +107,16 @@ impl PadServerNetwork {
 
         loop {
             // Check close
-            entry_mutex!(self.runtime, |guard| {
+            entry_mutex!(runtime, |guard| {
                 if guard.data.close.load(SeqCst) && !closed {
+                    guard.data.mark_exit_reason(&player.account, GameOver);
                     guard.send((player.account.clone(), LetExit(GameOver)), player.account.clone(), ServiceType::TCPConnection);
                     closed = true;
                 }
             });
 
             let mut message = None;
-            entry_mutex!(self.runtime, |guard| {
+            entry_mutex!(runtime, |guard| {
                 message = guard.pop_from_send_list(player.account.clone(), ServiceType::TCPConnection);
             });
 
@@ -129,13 +134,15 @@ impl PadServerNetwork {
                 }
 
                 // Process messages
-                match writer.write_all(GameMessage::en(&message.1).as_slice()).await {
+                match write_frame(&mut writer, GameMessage::en(&message.1).as_slice()).await {
                     Ok(_) => {
                         trace!("[TCP Server] [Runtime] Sent {:?} to {}", &message.1, player.account.id);
-                        writer.flush().await.expect("[TCP Client] [Runtime] Writer encountered an error");
                     }
                     Err(error) => {
                         warn!("[TCP Server] [Runtime] Sent {:?} to {} failed: {}", &message.1, player.account.id, error);
+                        entry_mutex!(runtime, |guard| {
+                            guard.report_connection_error(&player.account, error.to_string());
+                        });
                         break;
                     }
                 }
@@ -143,8 +150,8 @@ impl PadServerNetwork {
         }
 
         info!("[TCP Server] [Runtime] Writer between {} closed.", player.account.id);
-        entry_mutex!(self.runtime, |guard| {
-            guard.data.sign_player_online_status(&player, TCPConnection, false);
+        entry_mutex!(runtime, |guard| {
+            guard.leave_player(&player, TCPConnection);
             guard.writer_count -= 1;
         })
     }
@@ -161,7 +168,6 @@ impl PadClientNetwork {
     async fn read_task(self: Arc<Self>, mut reader: OwnedReadHalf) {
         info!("[TCP Client] [Runtime] Reader started.");
 
-        let mut buffer = [0u8; 1024];
         let mut err_message_counter = 0;
         loop {
             // Check close
@@ -171,10 +177,10 @@ impl PadClientNetwork {
                 }
             });
 
-            let read = reader.read(&mut buffer).await;
+            let read = read_frame(&mut reader).await;
             match read {
-                Ok(size) => {
-                    let message : GameMessage = GameMessage::de(buffer[0..size].to_vec());
+                Ok(bytes) => {
+                    let message : GameMessage = GameMessage::de(bytes);
 
                     // Preprocess messages: handle exit messages.
                     match message {
@@ -256,10 +262,9 @@ impl PadClientNetwork {
                 }
 
                 // Process messages
-                match writer.write_all(ControlMessage::en(&message).as_slice()).await {
+                match write_frame(&mut writer, ControlMessage::en(&message).as_slice()).await {
                     Ok(_) => {
                         trace!("[TCP Client] [Runtime] Sent {:?}.", &message);
-                        writer.flush().await.expect("[TCP Client] [Runtime] Writer encountered an error");
                     }
                     Err(error) => {
                         warn!("[TCP Client] [Runtime] Sent {:?} failed: {}", &message, error);
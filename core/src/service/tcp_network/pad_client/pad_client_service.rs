@@ -4,10 +4,11 @@ use std::sync::atomic::Ordering::SeqCst;
 use std::time::Duration;
 use log::{error, info, warn};
 use tokio::{join, spawn};
+use tokio::net::TcpStream;
 use tokio::time::sleep;
 use nogamepads::entry_mutex;
 use crate::data::controller::controller_runtime::ControllerRuntime;
-use crate::data::message::message_enums::ConnectionMessage::{Join, RequestGameInfos};
+use crate::data::message::message_enums::ConnectionMessage::{EnterRoom, Join, JoinAsGuest, RequestGameInfos, RequestKeys, Signature};
 use crate::data::message::message_enums::ConnectionResponseMessage;
 use crate::service::service_runner::NoGamepadsService;
 use crate::service::service_types::ServiceType;
@@ -17,16 +18,21 @@ use crate::service::tcp_network::utils::tokio_utils::build_tokio_runtime;
 
 pub struct PadClientNetwork {
     pub(crate) addr: SocketAddr,
+    pub(crate) room: Option<String>,
     pub(crate) runtime: Arc<Mutex<ControllerRuntime>>
 }
 
 macro_rules! connect_once {
-    ($addr:expr, |$conn:ident| $code:block) => {{
+    ($self:expr, |$conn:ident| $code:block) => {{
         use tokio::net::TcpStream;
-        match TcpStream::connect($addr).await {
+        match TcpStream::connect($self.addr).await {
             Ok(mut $conn) => {
-                $code
-                true
+                if $self.enter_room(&mut $conn).await {
+                    $code
+                    true
+                } else {
+                    false
+                }
             },
             Err(e) => {
                 error!("[TCP Client] [Main] Connection failed {:?}", e);
@@ -45,6 +51,7 @@ impl PadClientNetwork {
 
         PadClientNetwork {
             addr: SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT)),
+            room: None,
             runtime
         }
     }
@@ -64,6 +71,12 @@ impl PadClientNetwork {
         self
     }
 
+    /// Bind the room to join, the pad_server's default room is used if not bound
+    pub fn bind_room(&mut self, room: String) -> &mut PadClientNetwork {
+        self.room = Some(room);
+        self
+    }
+
     pub fn build_entry(self) -> NoGamepadsService {
         let arc = Arc::new(self);
 
@@ -94,13 +107,11 @@ impl PadClientNetwork {
     async fn connection_thread(self: Arc<PadClientNetwork>) {
         info!("[TCP Client] Connecting to {}:{}", self.addr.ip().to_string(), self.addr.port());
 
-        let mut buffer = [0; 1024];
-
         // Requests game infos
-        if !connect_once!(self.addr, |stream| {
+        if !connect_once!(self, |stream| {
             info!("[TCP Client] [Main] Requesting game infos.");
             send_msg(&mut stream, RequestGameInfos).await;
-            let response : ConnectionResponseMessage = read_msg(&mut buffer, &mut stream).await;
+            let response : ConnectionResponseMessage = read_msg(&mut stream).await;
             match response {
                 ConnectionResponseMessage::GameInfos(infos) => {
                     entry_mutex!(self.runtime, |guard| {
@@ -119,25 +130,82 @@ impl PadClientNetwork {
             return;
         }
 
+        // Requests keys
+        if !connect_once!(self, |stream| {
+            info!("[TCP Client] [Main] Requesting keys.");
+            send_msg(&mut stream, RequestKeys).await;
+            let response : ConnectionResponseMessage = read_msg(&mut stream).await;
+            match response {
+                ConnectionResponseMessage::Keys(keys) => {
+                    entry_mutex!(self.runtime, |guard| {
+                        guard.keys = keys;
+                    });
+                    info!("[TCP Client] [Main] Download keys successfully.");
+                }
+                _ => {
+                    warn!("[TCP Client] [Main] Download keys failed, keys can only be addressed by IDs.");
+                }
+            }
+        }) {
+            return;
+        }
+
         // TODO :: Download game layouts
 
         // TODO :: Download skin assets
 
         // Try to join game
-        let _ = connect_once!(self.addr, |connection| {
+        let _ = connect_once!(self, |connection| {
             let mut player = None;
+            let mut guest = false;
             entry_mutex!(self.runtime, |guard| {
                 player = Some(guard.player.clone());
+                guest = guard.guest;
             });
             if player.is_some() {
-                info!("[TCP Client] [Main] Trying to join game.");
-                send_msg(&mut connection, Join(player.unwrap())).await;
-                let response : ConnectionResponseMessage = read_msg(&mut buffer, &mut connection).await;
+                let player = player.unwrap();
+                if guest {
+                    info!("[TCP Client] [Main] Trying to join game as a guest.");
+                    send_msg(&mut connection, JoinAsGuest(player.customize.unwrap_or_default())).await;
+                } else {
+                    info!("[TCP Client] [Main] Trying to join game.");
+                    send_msg(&mut connection, Join(player)).await;
+                }
+                let mut response : ConnectionResponseMessage = read_msg(&mut connection).await;
+
+                // Key accounts are asked to sign a challenge first
+                if let ConnectionResponseMessage::Challenge(challenge) = &response {
+                    let mut signature = Vec::new();
+                    entry_mutex!(self.runtime, |guard| {
+                        if let Some(key) = &guard.key {
+                            signature = key.sign(challenge, self.room.as_deref());
+                        }
+                    });
+                    if signature.is_empty() {
+                        warn!("[TCP Client] [Main] Challenged, but no key is bound to sign it.");
+                    }
+                    send_msg(&mut connection, Signature(signature)).await;
+                    response = read_msg(&mut connection).await;
+                }
+
+                // Guests learn the account generated for them
+                if let ConnectionResponseMessage::GuestWelcome(account, team, customize) = response {
+                    info!("[TCP Client] [Main] Joined as guest \"{}\".", account.id);
+                    entry_mutex!(self.runtime, |guard| {
+                        guard.player.account = account.clone();
+                    });
+                    response = ConnectionResponseMessage::JoinWelcome(team, customize);
+                }
                 match response {
-                    ConnectionResponseMessage::Welcome => {
+                    ConnectionResponseMessage::JoinWelcome(team, customize) => {
 
                         // Long Connection
                         info!("[TCP Client] [Main] Welcome");
+                        entry_mutex!(self.runtime, |guard| {
+                            guard.team = team;
+                            guard.player.customize = Some(customize);
+                            guard.resync_inputs();
+                        });
                         spawn(Self::start_long_connection(Arc::clone(&self), connection));
                     }
                     ConnectionResponseMessage::Deny(why) => {
@@ -162,4 +230,27 @@ impl PadClientNetwork {
 
         info!("[TCP Client] [Main] Main thread closed.");
     }
+
+    async fn enter_room(&self, stream: &mut TcpStream) -> bool {
+        let Some(room) = &self.room else {
+            return true;
+        };
+
+        send_msg(stream, EnterRoom(room.clone())).await;
+        let response : ConnectionResponseMessage = read_msg(stream).await;
+        match response {
+            ConnectionResponseMessage::Welcome => {
+                info!("[TCP Client] [Main] Entered room \"{}\".", room);
+                true
+            }
+            ConnectionResponseMessage::Deny(why) => {
+                error!("[TCP Client] [Main] Enter room \"{}\" denied: {:?}", room, why);
+                false
+            }
+            _ => {
+                error!("[TCP Client] [Main] Enter room \"{}\" failed.", room);
+                false
+            }
+        }
+    }
 }
\ No newline at end of file
@@ -1,6 +1,5 @@
 use std::net::{IpAddr, SocketAddr};
 use std::sync::{Arc, Mutex};
-use std::sync::atomic::Ordering::SeqCst;
 use std::time::Duration;
 use log::{error, info, trace, warn};
 use tokio::{join, select, spawn};
@@ -8,10 +7,14 @@ use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::watch::{channel, Receiver, Sender};
 use tokio::time::sleep;
 use nogamepads::entry_mutex;
+use crate::data::game::game_hub::GameHub;
 use crate::data::game::game_runtime::GameRuntime;
 use crate::data::message::message_enums::ConnectionMessage;
-use crate::data::message::message_enums::ConnectionMessage::{Join, RequestGameInfos, RequestLayoutConfigure, RequestSkinPackage, Ready};
-use crate::data::message::message_enums::ConnectionResponseMessage::{Deny, GameInfos, Welcome};
+use crate::data::message::message_enums::ConnectionMessage::{EnterRoom, Join, JoinAsGuest, RequestGameInfos, RequestKeys, RequestLayoutConfigure, RequestRooms, RequestSkinPackage, Ready, Signature};
+use crate::data::message::message_enums::ConnectionResponseMessage::{Challenge, Deny, GameInfos, GuestWelcome, JoinWelcome, Keys, Rooms, Welcome};
+use crate::data::message::message_enums::JoinFailedMessage::{IdentityNotProven, RoomNotFound};
+use crate::data::player::player_data::Player;
+use crate::data::player::player_identity;
 use crate::service::service_runner::NoGamepadsService;
 use crate::service::tcp_network::DEFAULT_PORT;
 use crate::service::tcp_network::utils::stream_utils::{get_target_address, read_msg, send_msg};
@@ -19,7 +22,7 @@ use crate::service::tcp_network::utils::tokio_utils::build_tokio_runtime;
 
 pub struct PadServerNetwork {
     pub(crate) addr: SocketAddr,
-    pub(crate) runtime: Arc<Mutex<GameRuntime>>,
+    pub(crate) hub: GameHub,
 
     pub(crate) close_tx: Sender<bool>,
     pub(crate) close_rx: Receiver<bool>,
@@ -28,15 +31,26 @@ pub struct PadServerNetwork {
 impl PadServerNetwork {
 
     pub fn build(runtime: Arc<Mutex<GameRuntime>>) -> PadServerNetwork {
+        Self::build_hub(GameHub::single(runtime))
+    }
+
+    /// Build a server hosting all rooms of the hub
+    pub fn build_hub(hub: GameHub) -> PadServerNetwork {
         let (close_tx, close_rx) = channel(false);
         PadServerNetwork {
             addr: SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT)),
-            runtime,
+            hub,
             close_tx,
             close_rx
         }
     }
 
+    /// Host another runtime in the specified room
+    pub fn add_room(&mut self, name: String, runtime: Arc<Mutex<GameRuntime>>) -> &mut PadServerNetwork {
+        self.hub.add_room(name, runtime);
+        self
+    }
+
     pub fn bind_ip(&mut self, ip: IpAddr) -> &mut PadServerNetwork {
         self.addr.set_ip(ip);
         self
@@ -124,44 +138,98 @@ impl PadServerNetwork {
     }
 
     async fn process_connection(self: Arc<Self>, mut stream: TcpStream) {
-        let mut buffer = [0; 1024];
-        let message: ConnectionMessage = read_msg(&mut buffer, &mut stream).await;
+        let mut message: ConnectionMessage = read_msg(&mut stream).await;
         let from_address = get_target_address(&stream);
 
+        // Select room: the default room is used unless the client enters another one first
+        let mut runtime = self.hub.default_room();
+        let mut room_name = None;
+        if let EnterRoom(room) = &message {
+            room_name = Some(room.clone());
+            runtime = self.hub.room(room);
+            if runtime.is_none() {
+                warn!("[TCP Server] [Main] Client({}) requests unknown room \"{}\".", from_address, room);
+                send_msg(&mut stream, Deny(RoomNotFound)).await;
+                return;
+            }
+            info!("[TCP Server] [Main] Client({}) entered room \"{}\".", from_address, room);
+            send_msg(&mut stream, Welcome).await;
+            message = read_msg(&mut stream).await;
+        }
+
+        if message == RequestRooms {
+            info!("[TCP Server] [Main] Client({}) requests rooms.", from_address);
+            send_msg(&mut stream, Rooms(self.hub.room_infos())).await;
+            info!("[TCP Server] [Main] Rooms sent.");
+            return;
+        }
+
+        let Some(runtime) = runtime else {
+            warn!("[TCP Server] [Main] No room is hosted!");
+            send_msg(&mut stream, Deny(RoomNotFound)).await;
+            return;
+        };
+
         match message {
 
             Join(player) => {
                 trace!("[TCP Server] [Main] Trying to join Player \"{}\"", &player.account.id);
-                let mut result = Ok(());
-                entry_mutex!(self.runtime, |guard| {
-                    match guard.try_join_player(player.clone()) {
-                        Ok(_) => { result = Ok(()); }
-                        Err(why) => { result = Err(why); }
-                    }
-                });
-                if result.is_err() {
-                    let fail_message = result.unwrap_err();
-                    error!("[TCP Server] [Main] Player join failed: {:?}", &fail_message);
-                    send_msg(&mut stream, Deny(fail_message)).await;
-                } else {
 
-                    // Long Connection
-                    info!("[TCP Server] [Main] Player joined, begin long connection.");
-                    send_msg(&mut stream, Welcome).await;
-                    spawn(Self::start_long_connection(Arc::clone(&self), player, stream));
+                // Guest accounts are only generated by the pad_server
+                if player.is_guest() {
+                    warn!("[TCP Server] [Main] Client({}) tried to join with a guest account.", from_address);
+                    send_msg(&mut stream, Deny(IdentityNotProven)).await;
+                    return;
                 }
+
+                // Key accounts prove their identity by signing a challenge
+                if player.is_key_account() {
+                    let challenge = player_identity::challenge();
+                    send_msg(&mut stream, Challenge(challenge.clone())).await;
+                    let reply: ConnectionMessage = read_msg(&mut stream).await;
+                    let proven = match reply {
+                        Signature(signature) => { player_identity::verify_challenge(&player.account, &challenge, room_name.as_deref(), &signature) }
+                        _ => { false }
+                    };
+                    if !proven {
+                        warn!("[TCP Server] [Main] Client({}) failed to prove the key of \"{}\".", from_address, &player.account.id);
+                        send_msg(&mut stream, Deny(IdentityNotProven)).await;
+                        return;
+                    }
+                }
+
+                // Upgraded accounts keep the identity the game knows them by
+                let player = player.into_known();
+
+                Self::join_player(Arc::clone(&self), runtime, player, stream).await;
+            }
+
+            JoinAsGuest(customize) => {
+                let player = Player::guest(customize);
+                trace!("[TCP Server] [Main] Trying to join Guest \"{}\"", &player.account.id);
+                Self::join_player(Arc::clone(&self), runtime, player, stream).await;
             }
 
             RequestGameInfos => {
                 info!("[TCP Server] [Main] Client({}) requests game infos.", from_address);
                 let mut info = Default::default();
-                entry_mutex!(self.runtime, |guard| {
+                entry_mutex!(runtime, |guard| {
                     info = guard.info.clone();
                 });
                 send_msg(&mut stream, GameInfos(info)).await;
                 info!("[TCP Server] [Main] Game infos sent.");
             }
 
+            RequestKeys => {
+                info!("[TCP Server] [Main] Client({}) requests keys.", from_address);
+                let mut keys = Default::default();
+                entry_mutex!(runtime, |guard| {
+                    keys = guard.control.keys().clone();
+                });
+                send_msg(&mut stream, Keys(keys)).await;
+                info!("[TCP Server] [Main] Keys sent.");
+            }
+
             RequestLayoutConfigure => {
                 info!("[TCP Server] [Main] Client({}) requests layout configures.", from_address);
             }
@@ -179,15 +247,43 @@ impl PadServerNetwork {
         }
     }
 
+    async fn join_player(self: Arc<Self>, runtime: Arc<Mutex<GameRuntime>>, player: Player, mut stream: TcpStream) {
+        let mut result = Err(Default::default());
+        let mut team = None;
+        entry_mutex!(runtime, |guard| {
+            result = guard.try_join_player(player.clone());
+            team = guard.data.team_of(&player.account);
+        });
+        match result {
+            Err(fail_message) => {
+                error!("[TCP Server] [Main] Player join failed: {:?}", &fail_message);
+                send_msg(&mut stream, Deny(fail_message)).await;
+            }
+            Ok(joined) => {
+
+                // The welcome tells the player its team and custom information as applied by the game
+                let customize = joined.customize.clone().unwrap_or_default();
+                let welcome = if joined.is_guest() {
+                    GuestWelcome(joined.account.clone(), team, customize)
+                } else {
+                    JoinWelcome(team, customize)
+                };
+
+                // Long Connection
+                info!("[TCP Server] [Main] Player joined, begin long connection.");
+                send_msg(&mut stream, welcome).await;
+                spawn(Self::start_long_connection(Arc::clone(&self), runtime, player, stream));
+            }
+        }
+    }
+
     async fn close_checker(self: Arc<Self>) {
         loop {
             sleep(Duration::from_millis(1000)).await;
-            entry_mutex!(self.runtime, |guard| {
-                if guard.data.close.load(SeqCst) {
-                    let _ = self.close_tx.send(true);
-                    break;
-                }
-            })
+            if self.hub.is_all_closed() {
+                let _ = self.close_tx.send(true);
+                break;
+            }
         }
     }
 }
\ No newline at end of file
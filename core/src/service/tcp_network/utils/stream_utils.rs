@@ -1,29 +1,57 @@
 use std::fmt::Debug;
+use std::io;
+use std::io::ErrorKind;
 use bincode::{Decode, Encode};
 use log::{error, trace};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
 use crate::data::message::traits::MessageEncoder;
 
+/// Maximum length of a frame, longer frames are treated as a broken stream
+pub const MAX_FRAME_LENGTH: usize = 4 * 1024 * 1024;
+
+/// Write the bytes as one frame
+/// Frames are prefixed with their length (u32, big endian), so messages never merge or split on the stream
+pub async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    if bytes.len() > MAX_FRAME_LENGTH {
+        return Err(io::Error::new(ErrorKind::InvalidInput, format!("Frame of {} bytes is too long", bytes.len())));
+    }
+    let mut frame = Vec::with_capacity(4 + bytes.len());
+    frame.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    frame.extend_from_slice(bytes);
+    writer.write_all(&frame).await?;
+    writer.flush().await
+}
+
+/// Read the next frame written by write_frame
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let length = reader.read_u32().await? as usize;
+    if length > MAX_FRAME_LENGTH {
+        return Err(io::Error::new(ErrorKind::InvalidData, format!("Frame of {} bytes is too long", length)));
+    }
+    let mut bytes = vec![0u8; length];
+    reader.read_exact(&mut bytes).await?;
+    Ok(bytes)
+}
+
 pub async fn send_msg<Message>(
     stream: &mut TcpStream,
     msg: impl MessageEncoder<Message> + Encode + Decode<()> + Default + Debug
 )
 where Message: MessageEncoder<Message> + Encode + Decode<()> + Default + Debug {
-    match stream.write_all(MessageEncoder::en(&msg).as_slice()).await {
+    match write_frame(stream, MessageEncoder::en(&msg).as_slice()).await {
         Ok(_) => { trace!("[Message Sender] Sent {:?} to {}", msg, get_target_address(stream)); }
         Err(err) => { error!("[Message Sender] Failed to send message: {}", err); }
     }
 }
 
 pub async fn read_msg<Message>(
-    buffer: &mut [u8],
     stream: &mut TcpStream
 ) -> Message
 where Message: MessageEncoder<Message> + Encode + Decode<()> + Default + Debug {
-    match stream.read(buffer).await {
-        Ok(read) => {
-            let received = Message::de(Vec::from(&buffer[..read]));
+    match read_frame(stream).await {
+        Ok(bytes) => {
+            let received = Message::de(bytes);
             trace!("[Message Reader] Received {:?} from {}", received, get_target_address(stream));
             received
         }